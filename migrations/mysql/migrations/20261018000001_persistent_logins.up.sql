@@ -0,0 +1,19 @@
+#
+# 18/10/2026.
+#
+
+#
+# "Remember me" persistent logins. The cookie value is selector:validator, only
+# the SHA-256 hash of the validator is stored. There is no foreign key to
+# `employees`: logins authenticated by LDAP might not have a matching row.
+#
+CREATE TABLE `persistent_logins` (
+  `selector` CHAR(24) NOT NULL,
+  `validator_hash` CHAR(64) NOT NULL,
+  `email` VARCHAR(255) NOT NULL,
+  `created_at` DATETIME NOT NULL,
+  `last_used_at` DATETIME NOT NULL,
+  `expires_at` DATETIME NOT NULL,
+  PRIMARY KEY (`selector`),
+  INDEX `persistent_logins_email` (`email` ASC)
+);
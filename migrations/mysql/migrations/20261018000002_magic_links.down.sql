@@ -0,0 +1,5 @@
+#
+# 18/10/2026.
+#
+
+DROP TABLE IF EXISTS `magic_links`;
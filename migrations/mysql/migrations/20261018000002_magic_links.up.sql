@@ -0,0 +1,18 @@
+#
+# 18/10/2026.
+#
+
+#
+# Passwordless sign-in links. Only SHA-256 hashes of the link token, and of the
+# secret in the requesting browser's cookie, are stored.
+#
+CREATE TABLE `magic_links` (
+  `token_hash` CHAR(64) NOT NULL,
+  `browser_hash` CHAR(64) NOT NULL,
+  `email` VARCHAR(255) NOT NULL,
+  `created_at` DATETIME NOT NULL,
+  `expires_at` DATETIME NOT NULL,
+  `used_at` DATETIME NULL,
+  PRIMARY KEY (`token_hash`),
+  INDEX `magic_links_email` (`email` ASC)
+);
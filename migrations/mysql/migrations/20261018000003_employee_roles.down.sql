@@ -0,0 +1,7 @@
+#
+# 18/10/2026.
+#
+
+ALTER TABLE `employees` DROP COLUMN `max_sessions`;
+
+ALTER TABLE `employees` DROP COLUMN `role`;
@@ -0,0 +1,13 @@
+#
+# 18/10/2026.
+#
+
+#
+# Employee roles, e.g. `employee`, `hr`, `admin`. And an optional per-employee
+# maximum number of concurrent sessions, which overrides the role maximum.
+#
+# Added last: stored procedure `get_employees` selects columns by position.
+#
+ALTER TABLE `employees` 
+ADD COLUMN `role` VARCHAR(32) NOT NULL DEFAULT 'employee',
+ADD COLUMN `max_sessions` INT UNSIGNED NULL;
@@ -0,0 +1,18 @@
+#
+# 18/10/2026.
+#
+
+#
+# Logged in sessions, one per access token session Id. A session is active while
+# it is not revoked, and has been used within JWT_MINS_VALID_FOR minutes.
+#
+CREATE TABLE `sessions` (
+  `session_id` CHAR(36) NOT NULL,
+  `email` VARCHAR(255) NOT NULL,
+  `created_at` DATETIME NOT NULL,
+  `last_active_at` DATETIME NOT NULL,
+  `revoked_at` DATETIME NULL,
+  `revoked_reason` VARCHAR(32) NULL,
+  PRIMARY KEY (`session_id`),
+  INDEX `sessions_email` (`email` ASC, `created_at` ASC)
+);
@@ -0,0 +1,5 @@
+#
+# 18/10/2026.
+#
+
+ALTER TABLE `employees` DROP COLUMN `status`;
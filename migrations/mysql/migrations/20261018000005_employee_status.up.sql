@@ -0,0 +1,12 @@
+#
+# 18/10/2026.
+#
+
+#
+# Account status: `active`, `disabled`, `locked` or `must_change_password`. Only 
+# `active` and `must_change_password` accounts can log in.
+#
+# Added last: stored procedure `get_employees` selects columns by position.
+#
+ALTER TABLE `employees` 
+ADD COLUMN `status` VARCHAR(32) NOT NULL DEFAULT 'active';
@@ -0,0 +1,7 @@
+#
+# 18/10/2026.
+#
+
+ALTER TABLE `departments` DROP COLUMN `updated_at`;
+
+ALTER TABLE `employees` DROP COLUMN `updated_at`;
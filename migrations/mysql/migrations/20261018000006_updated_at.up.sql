@@ -0,0 +1,15 @@
+#
+# 18/10/2026.
+#
+
+#
+# When `employees` and `departments` rows were last changed, for the `Last-Modified`
+# header of conditional GET requests. Existing rows get the time of this migration.
+#
+# Added last: stored procedure `get_employees` selects columns by position.
+#
+ALTER TABLE `employees` 
+ADD COLUMN `updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP;
+
+ALTER TABLE `departments` 
+ADD COLUMN `updated_at` TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP;
@@ -0,0 +1,8 @@
+#
+# 18/10/2026.
+#
+
+ALTER TABLE `employees` 
+DROP INDEX `employees_deleted_at`,
+DROP COLUMN `deleted_by`,
+DROP COLUMN `deleted_at`;
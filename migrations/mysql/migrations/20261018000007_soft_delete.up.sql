@@ -0,0 +1,17 @@
+#
+# 18/10/2026.
+#
+
+#
+# Soft delete: a deleted employee keeps its row, so that its `salaries`, `titles` and
+# `dept_emp` history is kept. `deleted_at` is when, in UTC, and `deleted_by` the email
+# of who deleted it; both NULL for employees who are not deleted. Deleted rows are 
+# purged for good after DELETED_EMPLOYEES_DAYS_RETAINED days, by the command
+# `learn_actix_web purge-deleted-employees`.
+#
+# Added last: stored procedure `get_employees` selects columns by position.
+#
+ALTER TABLE `employees` 
+ADD COLUMN `deleted_at` DATETIME NULL DEFAULT NULL,
+ADD COLUMN `deleted_by` VARCHAR(255) NULL DEFAULT NULL,
+ADD INDEX `employees_deleted_at` (`deleted_at` ASC);
@@ -0,0 +1,7 @@
+#
+# 18/10/2026.
+#
+
+ALTER TABLE `persistent_logins`
+  DROP COLUMN `previous_validator_hash`,
+  DROP COLUMN `rotated_at`;
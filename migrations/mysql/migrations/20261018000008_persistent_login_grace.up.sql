@@ -0,0 +1,12 @@
+#
+# 18/10/2026.
+#
+
+#
+# The validator hash a persistent login had before its last rotation. Concurrent
+# requests sending the same cookie, e.g. two tabs, present it right after the
+# rotation: it is accepted for a short while, see src/models/persistent_login.rs.
+#
+ALTER TABLE `persistent_logins`
+  ADD COLUMN `previous_validator_hash` CHAR(64) NULL AFTER `validator_hash`,
+  ADD COLUMN `rotated_at` DATETIME NULL AFTER `last_used_at`;
@@ -0,0 +1,199 @@
+/* Date Created: 18/10/2026. */
+
+//! Workforce analytics request handlers: headcount by department over time, hires and
+//! separations per year, and gender distribution and average tenure per department
+//! and per title. Under ``/data``, see [`crate::models::analytics`].
+//!
+//! Every route returns JSON, or CSV with ``format=csv``, see
+//! [`crate::helper::csv_utils`].
+
+use actix_web::{get, web, HttpResponse};
+use actix_web::http::StatusCode;
+
+use crate::models::analytics::{
+    AnalyticsGroup, AnalyticsQuery,
+    select_data_years, select_headcount, select_hires_separations,
+    select_gender_distribution, select_tenure
+};
+use crate::helper::csv_utils::{ReportFormat, make_report_response};
+use crate::helper::app_utils::make_api_status_response;
+
+/// Retrieves the headcount of each department at the end of each year.
+///
+/// # Arguments
+///
+/// * `query` - ``from_year``, ``to_year`` and ``format``, see [`AnalyticsQuery`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/analytics/headcount``
+/// * Route: ``http://localhost:5000/data/analytics/headcount?from_year=1995&to_year=2000&format=csv``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [Headcount](`crate::models::analytics::Headcount`), ordered by ``year`` then
+/// ``dept_no``. Or the same rows as a CSV attachment.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`), for an
+/// invalid ``format`` or invalid years.
+///
+#[get("/analytics/headcount")]
+pub async fn headcount(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<AnalyticsQuery>
+) -> HttpResponse {
+    let (format, years) = match yearly_params(&app_state, &query).await {
+        Ok(params) => params,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    make_report_response(format, "headcount", select_headcount(&app_state.db, years).await)
+}
+
+/// Retrieves the numbers of hires and separations of each year. See [`headcount`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/analytics/hires-separations``
+/// * Route: ``http://localhost:5000/data/analytics/hires-separations?from_year=1999&format=csv``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: an array of [HiresSeparations](`crate::models::analytics::HiresSeparations`),
+/// ordered by ``year``.
+///
+#[get("/analytics/hires-separations")]
+pub async fn hires_separations(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<AnalyticsQuery>
+) -> HttpResponse {
+    let (format, years) = match yearly_params(&app_state, &query).await {
+        Ok(params) => params,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    make_report_response(format, "hires-separations", select_hires_separations(&app_state.db, years).await)
+}
+
+/// Retrieves the gender distribution of the current employees of each department.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/analytics/gender/departments``
+/// * Route: ``http://localhost:5000/data/analytics/gender/departments?format=csv``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [GenderDistribution](`crate::models::analytics::GenderDistribution`), ordered by
+/// ``key``. Or the same rows as a CSV attachment.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`), for an
+/// invalid ``format``.
+///
+#[get("/analytics/gender/departments")]
+pub async fn department_gender(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<AnalyticsQuery>
+) -> HttpResponse {
+    gender_response(&app_state, &query, AnalyticsGroup::Department, "gender-departments").await
+}
+
+/// Retrieves the gender distribution of the current holders of each title. See
+/// [`department_gender`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/analytics/gender/titles``
+/// * Method: ``GET``
+///
+#[get("/analytics/gender/titles")]
+pub async fn title_gender(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<AnalyticsQuery>
+) -> HttpResponse {
+    gender_response(&app_state, &query, AnalyticsGroup::Title, "gender-titles").await
+}
+
+/// Retrieves the average tenure of the current employees of each department. See
+/// [`department_gender`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/analytics/tenure/departments``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: an array of [Tenure](`crate::models::analytics::Tenure`), ordered by ``key``.
+///
+#[get("/analytics/tenure/departments")]
+pub async fn department_tenure(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<AnalyticsQuery>
+) -> HttpResponse {
+    tenure_response(&app_state, &query, AnalyticsGroup::Department, "tenure-departments").await
+}
+
+/// Retrieves the average tenure of the current holders of each title. See
+/// [`department_tenure`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/analytics/tenure/titles?format=csv``
+/// * Method: ``GET``
+///
+#[get("/analytics/tenure/titles")]
+pub async fn title_tenure(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<AnalyticsQuery>
+) -> HttpResponse {
+    tenure_response(&app_state, &query, AnalyticsGroup::Title, "tenure-titles").await
+}
+
+/// The format and the years of a yearly report. The default years are only read
+/// when the request does not give both.
+async fn yearly_params(
+    app_state: &super::AppState,
+    query: &AnalyticsQuery
+) -> Result<(ReportFormat, (i32, i32)), String> {
+    let format = ReportFormat::from_query(query.format.as_deref())?;
+
+    let data_years = match (query.from_year, query.to_year) {
+        (Some(from_year), Some(to_year)) => (from_year, to_year),
+        _ => select_data_years(&app_state.db).await,
+    };
+
+    Ok((format, query.resolve_years(data_years)?))
+}
+
+async fn gender_response(
+    app_state: &super::AppState,
+    query: &AnalyticsQuery,
+    group: AnalyticsGroup,
+    file_stem: &str
+) -> HttpResponse {
+    match ReportFormat::from_query(query.format.as_deref()) {
+        Ok(format) => make_report_response(format, file_stem,
+            select_gender_distribution(&app_state.db, group).await),
+        Err(message) => make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    }
+}
+
+async fn tenure_response(
+    app_state: &super::AppState,
+    query: &AnalyticsQuery,
+    group: AnalyticsGroup,
+    file_stem: &str
+) -> HttpResponse {
+    match ReportFormat::from_query(query.format.as_deref()) {
+        Ok(format) => make_report_response(format, file_stem, select_tenure(&app_state.db, group).await),
+        Err(message) => make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    }
+}
@@ -3,6 +3,8 @@
 //! Application authentication-related request handlers. Responsible for 
 //! serving login page, managing login request, serving home page, etc.
 
+use std::collections::HashMap;
+
 use tera::{Context, Tera};
 use actix_web::{
     HttpMessage, get, post, web, HttpRequest, HttpResponse, Responder, Either
@@ -11,15 +13,26 @@ use actix_web::http::{header, StatusCode, header::ContentType};
 
 use actix_identity::Identity;
 
-use argon2::{password_hash::{PasswordHash, PasswordVerifier}, Argon2};
-
 use crate::helper::constants::{
     REDIRECT_MESSAGE,
-    ORIGINAL_CONTENT_TYPE
+    ORIGINAL_CONTENT_TYPE,
+    PERSISTENT_LOGIN,
+    MAGIC_LINK_BROWSER
+};
+use crate::helper::messages::{
+    LOGIN_FAILURE_MSG,
+    UNAUTHORISED_ACCESS_MSG,
+    PASSWORD_MANAGED_EXTERNALLY_MSG,
+    CURRENT_PASSWORD_MISMATCH_MSG,
+    PASSWORD_CHANGED_MSG,
+    MAGIC_LINK_SENT_MSG,
+    MAGIC_LINK_INVALID_MSG,
+    MAGIC_LINK_MAIL_SUBJECT,
+    SESSION_LIMIT_REACHED_MSG,
 };
-use crate::helper::messages::LOGIN_FAILURE_MSG;
 use crate::helper::endpoint::{
     http_status_code, 
+    serialise_api_status,
     login_success_json_response
 };
 use crate::helper::app_utils::{
@@ -28,16 +41,47 @@ use crate::helper::app_utils::{
     build_authorization_cookie,
     remove_authorization_cookie,
     build_original_content_type_cookie,
-    remove_original_content_type_cookie
+    remove_original_content_type_cookie,
+    build_persistent_login_cookie,
+    remove_persistent_login_cookie,
+    build_magic_link_browser_cookie,
+    remove_magic_link_browser_cookie,
+    remove_token_binding_cookie,
+    remove_login_next_cookie
 };
 use crate::bh_libs::api_status::ApiStatus;
-use crate::models::{EmployeeLogin, select_employee};
+use crate::models::{
+    EmployeeLogin, MagicLinkRequest, PasswordChange, select_employee, update_employee_password
+};
+use crate::models::persistent_login::{
+    PersistentLoginToken,
+    insert_persistent_login,
+    delete_persistent_login,
+    delete_persistent_logins
+};
+use crate::models::magic_link::{
+    MAGIC_LINK_TOKEN_BYTES,
+    MagicLinkRejection,
+    insert_magic_link,
+    consume_magic_link
+};
+use crate::models::session::{REVOKED_LOGOUT, open_session, revoke_session};
+use crate::models::account_status::{AccountStatus, select_account_status, update_account_status};
+use crate::authenticators::DATABASE_BACKEND;
+use crate::mailers::MailMessage;
 
-use crate::helper::jwt_utils::{make_token, make_bearer_token};
+use crate::helper::jwt_utils::{JWTPayload, make_bearer_token};
+use crate::helper::token_binding::make_bound_token;
+use crate::helper::login_next::{login_next, validate_next};
+use crate::helper::password_utils::{
+    hash_password, verify_password, validate_new_password, random_token
+};
 
 /// Renders the login page and return the complete content as a 
 /// [`std::string::String`].
-fn render_login_page(message: &str) -> String {
+/// 
+/// A ``next`` is carried over to the login form, see [`crate::helper::login_next`].
+fn render_login_page(message: &str, next: Option<&str>) -> String {
     // Create a new Tera instance and add a template from a string
     let tera = Tera::new("templates/auth/**/*").unwrap();
 
@@ -46,6 +90,9 @@ fn render_login_page(message: &str) -> String {
     if message.len() > 0 {
         ctx.insert("message", &message);
     }
+    if let Some(next) = next {
+        ctx.insert("next", next);
+    }
 
     tera.render("login.html", &ctx).expect("Failed to render template")
 }
@@ -86,44 +133,6 @@ fn first_stage_login_error_response(
         .finish()
 }
 
-/// A login process helper method. 
-/// 
-/// A database record has been successfully retrieved using exact email matched.
-/// This methods de-hashes the database password and compares it against the 
-/// submitted password, if matches, returns successful. If passwords don't match,
-/// calls to [`first_stage_login_error_response`] to return appropriate error 
-/// response.
-/// 
-/// Called by ``/api/login`` handler method [`login`].
-/// 
-/// # Arguments
-/// 
-/// * `request` - the original login request.
-/// 
-/// * submitted_login - the deserialised submitted login info.
-/// 
-/// * selected_login - email matched record read from database.
-/// 
-/// # Return
-/// 
-/// * Ok(()) - if passwords matched.
-/// 
-/// * [`first_stage_login_error_response`] - if passwords don't match.
-/// 
-fn match_password_response(
-    request: &HttpRequest,
-    submitted_login: &EmployeeLogin, 
-    selected_login: &EmployeeLogin
-) -> Result<(), HttpResponse> {
-    let password_hash = String::from(&selected_login.password);
-    let parsed_hash = PasswordHash::new(&password_hash).expect("Failed to parse hashed password.");
-    if Argon2::default().verify_password(submitted_login.password.as_bytes(), &parsed_hash).is_ok() {
-        return Ok(());
-    };
-
-    Err(first_stage_login_error_response(request, LOGIN_FAILURE_MSG))
-}
-
 /// Serves the “login page” response conditionally as HTML or JSON.
 /// 
 /// * Route: ``http://0.0.0.0:5000/ui/login``
@@ -205,6 +214,11 @@ fn match_password_response(
 /// See ``tests/test_handlers.rs``' method 
 /// ``get_employees_json2_with_content_type_no_access_token()``.
 /// 
+/// # Query String
+/// 
+/// An optional ``next``, where to go after logging in. If safe, see 
+/// [`crate::helper::login_next::validate_next`], it is carried over to the login form.
+/// 
 /// # Cookies Clean Up
 /// 
 /// The redirection mechanism relies on two per-request server-side cookies
@@ -226,6 +240,10 @@ pub async fn login_page(
     request: HttpRequest
 ) -> Either<impl Responder, HttpResponse> {
     let mut content_type: String = String::from(request.content_type());
+
+    // E.g. ``/ui/login?next=/ui/home``.
+    let next = web::Query::<HashMap<String, String>>::from_query(request.query_string()).ok()
+        .and_then(|query| query.get("next").and_then(|next| validate_next(next)));
     let mut status_code = StatusCode::OK;
     let mut message = String::from("");
 
@@ -263,7 +281,7 @@ pub async fn login_page(
                 .cookie(remove_login_redirect_cookie())
                 // Always removes cookie ORIGINAL_CONTENT_TYPE.
                 .cookie(remove_original_content_type_cookie())
-                .body(render_login_page(&message)) 
+                .body(render_login_page(&message, next.as_deref())) 
         )
     }
     else {
@@ -285,13 +303,24 @@ pub async fn login_page(
 /// 1. Deserialising the submitted byte stream into [`crate::models::EmployeeLogin`].
 /// If fails, log in fails. 
 /// 
-/// 2. The ``submitted email`` is used to identify an employee from the database. 
-/// If no employee found, log in fails. 
+/// 2. The submitted login is passed to the configured authentication backends, 
+/// [`crate::authenticators::AuthenticatorChain`]. If no backend accepts it, log in fails. 
+/// For the ``database`` backend, the ``submitted email`` is used to identify an employee 
+/// from the database, then the ``database password`` is de-hashed and compared against 
+/// the ``submitted password``. For the ``ldap`` backend, it is an LDAP simple bind.
+/// 
+/// 3. If none fails, log in succeeds. The email forms part the JSON Web Token payload.
 /// 
-/// 3. The ``database password`` is de-hashed and compared against the ``submitted password``, 
-/// if does not match, log in fails. 
+/// 4. If ``remember_me`` is ``true``, a persistent login is also created, see
+/// [`crate::models::persistent_login`]. It is sent in the server-side only cookie
+/// [`crate::helper::constants::PERSISTENT_LOGIN`], which lasts
+/// [`crate::config::Config::persistent_login_days_valid_for`] days. While this cookie
+/// is valid, the web session is silently re-established after the access token has
+/// expired.
 /// 
-/// 4. If none fails, log in succeeds. The email forms part the JSON Web Token payload.
+/// 5. The originally requested page is the submitted ``next``, or else the
+/// [`crate::helper::constants::LOGIN_NEXT`] cookie, if either is a safe same-origin
+/// path, see [`crate::helper::login_next`].
 /// 
 /// # Arguments
 /// 
@@ -319,7 +348,8 @@ pub async fn login_page(
 /// # Response - Successful
 /// 
 /// * If the original request content type is ``application/x-www-form-urlencoded``,
-///   then redirect to home page.
+///   then redirect to the originally requested page if there is one, otherwise to 
+///   home page.
 /// 
 /// * If the original request content type is ``application/json``, then returns a
 ///   JSON in the form:
@@ -331,11 +361,14 @@ pub async fn login_page(
 ///       "session_id": null,
 ///       "data": {
 ///           "email": "behai_nguyen@hotmail.com",
-///           "access_token": "xxxx...zzzz"
+///           "access_token": "xxxx...zzzz",
+///           "next": "/ui/home"
 ///       }
 ///   }
 ///   ``
 /// 
+///   ``next`` is the originally requested page. It is left out if there is none.
+/// 
 /// The header part is [`crate::bh_libs::api_status::ApiStatus`], the ``data`` 
 /// object is [`crate::models::LoginSuccess`].
 /// 
@@ -362,6 +395,12 @@ pub async fn login_page(
 /// * Content type: ``application/json``; 
 /// request body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password"}``.
 /// 
+/// * Content type: ``application/json``; 
+/// request body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password", "remember_me": true}``.
+/// 
+/// * Content type: ``application/json``; 
+/// request body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password", "next": "/ui/home"}``.
+/// 
 #[post("/login")]
 pub async fn login(
     request: HttpRequest,
@@ -373,35 +412,109 @@ pub async fn login(
         Either::Right(form) => form.into_inner(),
     };
 
-    let query_result = select_employee(&app_state.db, &submitted_login.email).await;
-    if query_result.is_none() {
-        return first_stage_login_error_response(&request, LOGIN_FAILURE_MSG);
+    let email = match app_state.authenticators.authenticate(&submitted_login).await {
+        Some(email) => email,
+        None => return first_stage_login_error_response(&request, LOGIN_FAILURE_MSG),
+    };
+
+    let html = request.content_type() == ContentType::form_url_encoded().to_string();
+    let next = login_next(&request, submitted_login.next.as_deref());
+
+    login_success_response(&request, &app_state, &email, submitted_login.remember_me, next, html).await
+}
+
+/// The ``email`` has been authenticated, by [`login`] or by [`magic_link_login`]. 
+/// Makes the access token, attaches it to the active session as the
+/// [`actix_identity::Identity`] login, and creates the "remember me" persistent login
+/// if requested. The access token is bound to the client as configured, see
+/// [`crate::helper::token_binding`].
+/// 
+/// Accounts which can't log in, see [`crate::models::account_status`], fail with their
+/// status: if ``html`` is ``true``, its message as per [`first_stage_login_error_response`],
+/// otherwise its JSON [`crate::bh_libs::api_status::ApiStatus`], whose response status
+/// code is the ``code``, e.g. [LOCKED](`actix_web::http::StatusCode::LOCKED`) for a
+/// locked account.
+/// 
+/// The new session counts towards the employee's maximum number of active sessions,
+/// see [`crate::models::session`]. Over the maximum, depending on 
+/// ``SESSION_LIMIT_POLICY``, either the oldest sessions are evicted, or the login fails
+/// with [`crate::helper::messages::SESSION_LIMIT_REACHED_MSG`] as per
+/// [`first_stage_login_error_response`].
+/// 
+/// # Return
+/// 
+/// * If ``html`` is ``true``, a redirection to ``next`` if there is one, otherwise the
+///   home page. If ``html`` is ``false``, JSON of [`crate::models::LoginSuccessResponse`],
+///   whose ``data.next`` is ``next``. Either way, the access token is set to both the
+///   [`actix_web::http::header::AUTHORIZATION`] header and cookie, and the
+///   [`crate::helper::constants::LOGIN_NEXT`] cookie is removed.
+/// 
+async fn login_success_response(
+    request: &HttpRequest,
+    app_state: &super::AppState,
+    email: &str,
+    remember_me: bool,
+    next: Option<String>,
+    html: bool
+) -> HttpResponse {
+    let account_status = select_account_status(&app_state.db, email).await;
+    if !account_status.can_login() {
+        tracing::info!("{} can't log in, account is {}", email, account_status.as_str());
+        let api_status = account_status.api_status().unwrap();
+
+        return match html {
+            true => first_stage_login_error_response(request, &api_status.get_message().unwrap()),
+            false => api_status.respond_to(request),
+        };
     }
 
-    let selected_login = query_result.unwrap();
+    let payload = JWTPayload::new(email, app_state.cfg.jwt_mins_valid_for * 60);
 
-    let res = match_password_response(&request, &submitted_login, &selected_login);
-    if res.is_err() {
-        return res.err().unwrap();
+    if !open_session(&app_state.db, email, &payload.session_id(), &app_state.cfg).await {
+        return first_stage_login_error_response(request, SESSION_LIMIT_REACHED_MSG);
     }
 
-    let access_token = make_token(&selected_login.email, 
-        app_state.cfg.jwt_secret_key.as_ref(), app_state.cfg.jwt_mins_valid_for * 60);
+    let (access_token, binding_cookie) = make_bound_token(payload, request, &app_state.cfg);
 
     // https://docs.rs/actix-identity/latest/actix_identity/
     // Attach a verified user identity to the active session
     Identity::login(&request.extensions(), String::from( make_bearer_token(&access_token) )).unwrap();
 
-    // The request content type is "application/x-www-form-urlencoded", returns the home page.
-    if request.content_type() == ContentType::form_url_encoded().to_string() {
-        HttpResponse::Ok()
-            // Note this header.
-            .append_header((header::AUTHORIZATION, String::from(&access_token)))
-            // Note this client-side cookie.
-            .cookie(build_authorization_cookie(&access_token))
+    let mut builder = HttpResponse::Ok();
+    builder
+        // Note this header.
+        .append_header((header::AUTHORIZATION, String::from(&access_token)))
+        // Note this client-side cookie.
+        .cookie(build_authorization_cookie(&access_token))
+        // Consumed.
+        .cookie(remove_login_next_cookie());
+
+    if let Some(cookie) = binding_cookie {
+        // Note this server-side only cookie.
+        builder.cookie(cookie);
+    }
+
+    if remember_me {
+        let days_valid_for = app_state.cfg.persistent_login_days_valid_for;
+        let token = PersistentLoginToken::generate();
+        insert_persistent_login(&app_state.db, email, &token, days_valid_for).await;
+
+        // Note this server-side only cookie.
+        builder.cookie(build_persistent_login_cookie(&token.cookie_value(), days_valid_for));
+    }
+
+    // The request content type is "application/x-www-form-urlencoded", returns to the 
+    // originally requested page, or the home page.
+    if let (true, Some(next)) = (html, next.as_deref()) {
+        builder
+            .status(StatusCode::SEE_OTHER)
+            .append_header((header::LOCATION, next))
+            .finish()
+    }
+    else if html {
+        builder
             .content_type(ContentType::html())
-            .body(render_home_page(&request)
-        )
+            .body(render_home_page(request))
     }
     else {
         // The request content type is "application/json", returns a JSON content of
@@ -409,14 +522,148 @@ pub async fn login(
         // 
         // Token field is the access token which the users need to include in the future 
         // requests to get authenticated and hence access to protected resources.		
-        HttpResponse::Ok()
-            // Note this header.
-            .append_header((header::AUTHORIZATION, String::from(&access_token)))
-            // Note this client-side cookie.
-            .cookie(build_authorization_cookie(&access_token))
+        builder
             .content_type(ContentType::json())
-            .body(login_success_json_response(&selected_login.email, &access_token)
-        )
+            .body(login_success_json_response(email, &access_token, next.as_deref()))
+    }
+}
+
+/// Sends a passwordless sign-in link to an employee's email.
+/// 
+/// It accepts request body in both ``application/x-www-form-urlencoded`` and 
+/// ``application/json`` content types.
+/// 
+/// If the ``submitted email`` matches an employee, a single-use sign-in link is stored,
+/// see [`crate::models::magic_link`], and mailed to the employee by the configured
+/// [`crate::mailers::Mailer`]. The link is valid for 
+/// [`crate::config::Config::magic_link_mins_valid_for`] minutes.
+/// 
+/// The link is bound to this browser by the server-side only cookie 
+/// [`crate::helper::constants::MAGIC_LINK_BROWSER`], which is reused if the browser
+/// already has one. So several links requested from the same browser all work.
+/// 
+/// # Response
+/// 
+/// The response is the same whether or not the email matches an employee, so it 
+/// can't be used to discover registered emails. Mail delivery failures are logged only.
+/// 
+/// * If the request content type is ``application/x-www-form-urlencoded``, the login 
+/// page with message [`crate::helper::messages::MAGIC_LINK_SENT_MSG`].
+/// 
+/// * Otherwise, JSON of [`crate::bh_libs::api_status::ApiStatus`] with ``code`` ``200``
+/// and the same message.
+/// 
+/// # Valid Usage
+/// 
+/// * Route: ``http://0.0.0.0:5000/api/magic-link``
+/// * Method: ``POST``
+/// * Content type: ``application/json``; 
+/// request body: ``{"email": "chirstian.koblick.10004@gmail.com"}``.
+/// 
+#[post("/magic-link")]
+pub async fn request_magic_link(
+    request: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    body: Either<web::Json<MagicLinkRequest>, web::Form<MagicLinkRequest>>
+) -> HttpResponse {
+    let submitted = match body {
+        Either::Left(json) => json.into_inner(),
+        Either::Right(form) => form.into_inner(),
+    };
+
+    let mins_valid_for = app_state.cfg.magic_link_mins_valid_for;
+
+    let browser_secret = match request.cookie(MAGIC_LINK_BROWSER) {
+        Some(cookie) if cookie.value().len() == MAGIC_LINK_TOKEN_BYTES * 2 => String::from(cookie.value()),
+        _ => random_token(MAGIC_LINK_TOKEN_BYTES),
+    };
+
+    if let Some(employee) = select_employee(&app_state.db, &submitted.email).await {
+        let token = random_token(MAGIC_LINK_TOKEN_BYTES);
+        insert_magic_link(&app_state.db, &employee.email, &token, &browser_secret, mins_valid_for).await;
+
+        let message = MailMessage {
+            to: employee.email.clone(),
+            subject: String::from(MAGIC_LINK_MAIL_SUBJECT),
+            body: format!("Open this link in the browser you requested it from to sign in:\r\n\r\n\
+                {}/api/magic-link/{}\r\n\r\nIt can be used once, within {} minutes.\r\n",
+                app_state.cfg.app_base_url.trim_end_matches('/'), token, mins_valid_for),
+        };
+
+        if let Err(err) = app_state.mailer.send(&message).await {
+            tracing::error!("Sign-in link to {} not sent by {} mailer: {}", 
+                employee.email, app_state.mailer.name(), err);
+        }
+    }
+    else {
+        tracing::debug!("Sign-in link requested for unknown email {}", submitted.email);
+    }
+
+    let mut builder = HttpResponse::Ok();
+    // Note this server-side only cookie.
+    builder.cookie(build_magic_link_browser_cookie(&browser_secret, mins_valid_for));
+
+    if request.content_type() == ContentType::form_url_encoded().to_string() {
+        builder
+            .content_type(ContentType::html())
+            .body(render_login_page(MAGIC_LINK_SENT_MSG, None))
+    }
+    else {
+        builder
+            .content_type(ContentType::json())
+            .body(serialise_api_status(StatusCode::OK, MAGIC_LINK_SENT_MSG, None))
+    }
+}
+
+/// Signs in using a passwordless sign-in link, which has been mailed by 
+/// [`request_magic_link`].
+/// 
+/// The link is accepted only once, before it expires, and only from the browser which 
+/// requested it: see [`crate::models::magic_link::consume_magic_link`]. Attempts from
+/// another browser are logged as warnings.
+/// 
+/// # Response - Failure
+/// 
+/// Same as [`login`] failures, with message [`crate::helper::messages::MAGIC_LINK_INVALID_MSG`].
+/// 
+/// # Response - Successful
+/// 
+/// Same as [`login`]. Since the link is usually followed from an email client, the 
+/// response is the home page, unless the request content type is ``application/json``.
+/// 
+/// # Valid Usage
+/// 
+/// * Route: ``http://0.0.0.0:5000/api/magic-link/{token}``
+/// * Method: ``GET``
+/// 
+#[get("/magic-link/{token}")]
+pub async fn magic_link_login(
+    request: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    path: web::Path<String>
+) -> HttpResponse {
+    let token = path.into_inner();
+    let browser_secret = request.cookie(MAGIC_LINK_BROWSER).map(|cookie| String::from(cookie.value()));
+
+    match consume_magic_link(&app_state.db, &token, browser_secret.as_deref()).await {
+        Ok(email) => {
+            let html = request.content_type() != ContentType::json().to_string();
+
+            let next = login_next(&request, None);
+
+            let mut response = login_success_response(&request, &app_state, &email, false, next, html).await;
+            let _ = response.add_cookie(&remove_magic_link_browser_cookie());
+
+            response
+        }
+
+        Err(rejection) => {
+            if rejection == MagicLinkRejection::WrongBrowser {
+                tracing::warn!("Sign-in link followed from a browser other than the requesting one");
+            }
+
+            first_stage_login_error_response(&request, MAGIC_LINK_INVALID_MSG)
+        }
     }
 }
 
@@ -444,9 +691,101 @@ pub async fn home_page(
         .body(render_home_page(&request))
 }
 
+/// Changes the password of the logged in user.
+/// 
+/// It accepts request body in both ``application/x-www-form-urlencoded`` and 
+/// ``application/json`` content types. The response is always a JSON serialised
+/// [`crate::bh_libs::api_status::ApiStatus`].
+/// 
+/// The process is:
+/// 
+/// 1. Deserialising the submitted byte stream into [`crate::models::PasswordChange`].
+/// If fails, returns ``400``.
+/// 
+/// If the ``database`` authentication backend is not configured, passwords are not 
+/// stored in the database, returns ``400`` with message 
+/// [`crate::helper::messages::PASSWORD_MANAGED_EXTERNALLY_MSG`].
+/// 
+/// 2. The logged in user is identified by the [`crate::helper::jwt_utils::JWTPayload`]
+/// attached to the request by [`super::auth_middleware`]. The ``submitted current password``
+/// must match the ``database password``, if does not match, returns ``401`` with message 
+/// [`crate::helper::messages::CURRENT_PASSWORD_MISMATCH_MSG`].
+/// 
+/// 3. The ``submitted new password`` is checked against the breached passwords file,
+/// see [`crate::helper::password_utils::validate_new_password`]. If it has been breached,
+/// returns ``400`` with message [`crate::helper::messages::BREACHED_PASSWORD_MSG`].
+/// 
+/// 4. The new password is hashed using Argon2, and stored. All "remember me" persistent 
+/// logins of the user are revoked, including the current one. A ``must_change_password``
+/// account becomes ``active``, see [`crate::models::account_status`]. Returns ``200`` 
+/// with message [`crate::helper::messages::PASSWORD_CHANGED_MSG`].
+/// 
+/// # Valid Usage
+/// 
+/// * Route: ``http://0.0.0.0:5000/api/password``
+/// * Method: ``POST``
+/// * Content type: ``application/json``; 
+/// request body: ``{"current_password": "password", "new_password": "Correct Horse Battery Staple"}``.
+/// 
+#[post("/password")]
+pub async fn change_password(
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    body: Either<web::Json<PasswordChange>, web::Form<PasswordChange>>
+) -> impl Responder {
+    let submitted = match body {
+        Either::Left(json) => json.into_inner(),
+        Either::Right(form) => form.into_inner(),
+    };
+
+    // E.g. AUTH_BACKENDS=ldap: passwords live in the directory only.
+    if !app_state.authenticators.has_backend(DATABASE_BACKEND) {
+        return ApiStatus::new(http_status_code(StatusCode::BAD_REQUEST))
+            .set_message(PASSWORD_MANAGED_EXTERNALLY_MSG);
+    }
+
+    let email = jwt_payload.email();
+
+    let selected_login = match select_employee(&app_state.db, &email).await {
+        Some(selected_login) => selected_login,
+        None => return ApiStatus::new(http_status_code(StatusCode::UNAUTHORIZED))
+            .set_message(UNAUTHORISED_ACCESS_MSG),
+    };
+
+    if !verify_password(&submitted.current_password, &selected_login.password) {
+        return ApiStatus::new(http_status_code(StatusCode::UNAUTHORIZED))
+            .set_message(CURRENT_PASSWORD_MISMATCH_MSG);
+    }
+
+    if let Err(api_status) = validate_new_password(
+        &app_state.cfg.breached_passwords_file, &submitted.new_password) {
+        return api_status;
+    }
+
+    update_employee_password(&app_state.db, &email, 
+        &hash_password(&submitted.new_password)).await;
+
+    if select_account_status(&app_state.db, &email).await == AccountStatus::MustChangePassword {
+        update_account_status(&app_state.db, &email, AccountStatus::Active).await;
+    }
+
+    let revoked = delete_persistent_logins(&app_state.db, &email).await;
+    if revoked > 0 {
+        tracing::info!("Password changed, revoked {} persistent logins of {}", revoked, email);
+    }
+
+    ApiStatus::new(http_status_code(StatusCode::OK)).set_message(PASSWORD_CHANGED_MSG)
+}
+
 /// Log out a logged in session by clearing user's [`actix_identity::Identity`] and 
 /// also removes [`actix_web::http::header::AUTHORIZATION`] cookie.
 /// 
+/// If there is a "remember me" persistent login, it is deleted, and its
+/// [`crate::helper::constants::PERSISTENT_LOGIN`] cookie is removed.
+/// 
+/// The session no longer counts towards the employee's maximum number of active
+/// sessions, see [`crate::models::session`].
+/// 
 /// TO_DO redirects to '/ui/login'. TO_DO: possibly use content type to returns 
 /// either HTML or JSON.
 /// 
@@ -459,14 +798,25 @@ pub async fn home_page(
 ///
 #[post("/logout")]
 async fn logout(
-    _: HttpRequest,
-    user: Identity
+    request: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    user: Identity,
+    jwt_payload: web::ReqData<JWTPayload>
 ) -> impl Responder {
     user.logout();
 
+    revoke_session(&app_state.db, &jwt_payload.session_id(), REVOKED_LOGOUT).await;
+
+    if let Some(token) = request.cookie(PERSISTENT_LOGIN)
+        .and_then(|cookie| PersistentLoginToken::parse(cookie.value())) {
+        delete_persistent_login(&app_state.db, &token.selector).await;
+    }
+
     HttpResponse::Ok()
         // Note the cookie.
         .cookie(remove_authorization_cookie())
+        .cookie(remove_persistent_login_cookie())
+        .cookie(remove_token_binding_cookie())
         .status(StatusCode::SEE_OTHER)
         .append_header((header::LOCATION, "/ui/login"))
         .finish()
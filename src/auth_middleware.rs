@@ -22,7 +22,8 @@
 //!
 //! * If request to ``/favicon.ico`` should just go through.
 //!
-//! * Determine the status of the token.
+//! * Determine the status of the token. This includes the token binding check, see
+//!   [`crate::helper::token_binding`].
 //!
 //! * If the token is invalid, return [Unauthorized()](https://docs.rs/actix-web/latest/actix_web/struct.HttpResponse.html#method.Unauthorized)
 //!   whose body is JSON serialisation of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`),
@@ -30,6 +31,12 @@
 //! 
 //! * When authenticated
 //!
+//!     - Check the account status, see [`crate::models::account_status`]. A ``disabled``
+//!       or ``locked`` account is logged out, and the request gets the status error.
+//!       A ``must_change_password`` account only gets to [`PASSWORD_CHANGE_ROUTES`], other
+//!       requests get the status error. The error is attached to the request extension
+//!       as for an invalid token.
+//!
 //!     - Update the current [JWTPayload](`crate::helper::jwt_utils::JWTPayload`) to new expiry 
 //!       and last active. Make a new token from  this updated [JWTPayload](`crate::helper::jwt_utils::JWTPayload`). 
 //!
@@ -50,18 +57,36 @@
 //! 
 //! * When not authenticated
 //! 
-//!     - Requests to routes ``/ui/login`` and ``/api/login`` should go through.
+//!     - Requests to routes ``/ui/login`` and ``/api/login`` should go through. So should
+//!       passwordless sign-in link requests to ``/api/magic-link`` and ``/api/magic-link/{token}``.
 //! 
 //!     - Requests to any other route should get redirected to ``/ui/login``.
 //!       See [login_page](`crate::auth_handlers::login_page`) for more detail on response.
+//!       For ``GET`` and ``HEAD`` requests, the requested path is remembered, so that
+//!       users are returned to it after logging in. See [`crate::helper::login_next`].
+//! 
+//! # "Remember Me" Persistent Logins
+//! 
+//! [`restore_persistent_login`] runs just before this middleware. When there is no valid
+//! access token but a [`crate::helper::constants::PERSISTENT_LOGIN`] cookie, it logs the
+//! employee back in, so that this middleware sees an authenticated web session.
+//! 
+//! # Concurrent Session Limits
+//! 
+//! [`check_session`] runs just after this middleware. When authenticated, it rejects
+//! access tokens of sessions which have been revoked, e.g. evicted by a newer login.
+//! See [`crate::models::session`].
 //! 
 use std::future::{ready, Ready};
+use std::rc::Rc;
 
 use actix_web::{
-    body::EitherBody, dev::{self, Service, ServiceRequest, ServiceResponse, Transform}, 
-    http::{header, StatusCode}, web::Data, Error, HttpMessage, HttpResponse,
+    body::{EitherBody, MessageBody}, dev::{self, Service, ServiceRequest, ServiceResponse, Transform}, 
+    http::{header, Method, StatusCode}, web::Data, Error, HttpMessage, HttpResponse,
 };
 
+use actix_web_lab::middleware::Next;
+
 use futures_util::future::LocalBoxFuture;
 
 use actix_identity::{IdentityExt, Identity};
@@ -69,15 +94,29 @@ use actix_identity::{IdentityExt, Identity};
 use crate::{bh_libs::api_status::ApiStatus, helper::app_utils::{
     build_login_redirect_cookie,
     build_original_content_type_cookie,
+    build_login_next_cookie,
+    build_persistent_login_cookie,
+    remove_persistent_login_cookie,
 }};
 
-use crate::helper::messages::UNAUTHORISED_ACCESS_MSG;
+use crate::helper::constants::PERSISTENT_LOGIN;
+use crate::helper::login_next::validate_next;
+use crate::models::persistent_login::{PersistentLoginToken, rotate_persistent_login};
+use crate::models::session::{REVOKED_EVICTED, SessionState, open_session, touch_session};
+use crate::models::account_status::{AccountStatus, select_account_status};
+
+use crate::helper::messages::{
+    UNAUTHORISED_ACCESS_MSG,
+    SESSION_EVICTED_MSG,
+    SESSION_REVOKED_MSG
+};
 
 use super::AppState;
 use crate::helper::jwt_utils::{
-    JWTPayload, decode_bearer_token, 
+    JWTPayload, decode_bearer_token,
     make_token_from_payload, make_bearer_token
 };
+use crate::helper::token_binding::{make_bound_token, verify_binding};
 
 #[derive(Clone, Debug)]
 pub struct ResponseErrorStatus {
@@ -172,6 +211,9 @@ pub fn extract_access_token(
 
 /// Verify that there is a valid JSON Web Token access token for the current request.
 /// 
+/// A token whose binding does not match the current request is invalid, subject to 
+/// ``TOKEN_BINDING_STRICTNESS``. See [`crate::helper::token_binding::verify_binding`].
+/// 
 /// # Arguments
 /// 
 /// * `request` - contains [AppState](`super::AppState`).
@@ -199,13 +241,15 @@ fn verify_valid_access_token(
     // res.unwrap() -- the actual access token as a string.
     let res = decode_bearer_token(&res.unwrap(), app_state.cfg.jwt_secret_key.as_ref(), None);
 
-    if res.is_ok() {
-        // Token is valid and not expired.
-        TokenStatus{is_logged_in: true, payload: Some(res.unwrap()), api_status: None}
-    }
-    else {
+    match res {
+        // Token is valid and not expired, but might have been issued to another client.
+        Ok(payload) => match verify_binding(&payload, request.request(), &app_state.cfg) {
+            Ok(()) => TokenStatus{is_logged_in: true, payload: Some(payload), api_status: None},
+            Err(api_status) => TokenStatus{is_logged_in: false, payload: None, api_status: Some(api_status)},
+        },
+
         // Token is not valid. Set api_status to the invalid reason.
-        TokenStatus{is_logged_in: false, payload: None, api_status: Some(res.err().unwrap())}
+        Err(api_status) => TokenStatus{is_logged_in: false, payload: None, api_status: Some(api_status)},
     }
 }
 
@@ -216,6 +260,8 @@ fn verify_valid_access_token(
 /// [Identity](https://docs.rs/actix-identity/0.7.0/actix_identity/struct.Identity.html) login with 
 /// this updated token. Finally, set updated token to request extension, so that the next middleware 
 /// can pick it up and send it to clients via both response header and response cookie ``authorization``.
+/// The updated [JWTPayload](`crate::helper::jwt_utils::JWTPayload`) is also set to request extension
+/// for request handlers.
 /// 
 /// # Arguments
 /// 
@@ -232,9 +278,9 @@ fn update_and_set_updated_token(request: &ServiceRequest, token_status: TokenSta
 
     // Update current JWTPayload's expiry, last active. And make a new token from this 
     // updated JWTPayload.
+    let updated_payload = current_payload.update_expiry_secs(app_state.cfg.jwt_mins_valid_for * 60);
     let updated_token = make_token_from_payload(
-        &current_payload.update_expiry_secs(app_state.cfg.jwt_mins_valid_for * 60), 
-        app_state.cfg.jwt_secret_key.as_ref());
+        &updated_payload, app_state.cfg.jwt_secret_key.as_ref());
 
     // Replace actix-identity Identity login with updated token.
     Identity::login(&request.extensions(), String::from( make_bearer_token(&updated_token) )).unwrap();
@@ -242,6 +288,172 @@ fn update_and_set_updated_token(request: &ServiceRequest, token_status: TokenSta
     // Attach the updated token to request extension, so that the next middleware can pick it 
     // up and send it to clients via both response header and response cookie ``authorization``.
     request.extensions_mut().insert(updated_token);
+
+    // Attach the updated JWTPayload to request extension, so that request handlers can 
+    // identify the logged in user via [`actix_web::web::ReqData`].
+    request.extensions_mut().insert(updated_payload);
+}
+
+/// Routes which a ``must_change_password`` account can access.
+pub const PASSWORD_CHANGE_ROUTES: [&str; 2] = ["/api/password", "/api/logout"];
+
+/// Checks the account status of an authenticated request, see 
+/// [`crate::models::account_status`]. If the status does not allow the request, attaches
+/// the status error to request extension as [`ResponseErrorStatus`]. A ``disabled`` or
+/// ``locked`` account's [Identity](https://docs.rs/actix-identity/0.7.0/actix_identity/struct.Identity.html)
+/// is also logged out.
+/// 
+/// # Arguments
+/// 
+/// * `request` - contains [AppState](`super::AppState`).
+///
+/// * `email` - the logged in email.
+///
+async fn verify_account_status(request: &ServiceRequest, email: &str) {
+    let app_state = request.app_data::<Data<AppState>>().cloned().unwrap();
+
+    let status = select_account_status(&app_state.db, email).await;
+
+    let api_status = match status {
+        AccountStatus::MustChangePassword if PASSWORD_CHANGE_ROUTES.contains(&request.path()) => None,
+        _ => status.api_status(),
+    };
+
+    if let Some(api_status) = api_status {
+        tracing::info!("{} requested {}, account is {}", email, request.path(), status.as_str());
+
+        if !status.can_login() {
+            if let Ok(identity) = request.get_identity() {
+                identity.logout();
+            }
+        }
+
+        request.extensions_mut().insert(ResponseErrorStatus {
+            code: StatusCode::from_u16(api_status.get_code()).unwrap(),
+            body: api_status,
+        });
+    }
+}
+
+/// Standalone, async middleware function. It must run just before [`CheckLogin`].
+/// 
+/// Silently re-establishes a web session from a "remember me" persistent login, see
+/// [`crate::models::persistent_login`]. It only acts when all of the followings hold:
+/// 
+/// * There is a [`crate::helper::constants::PERSISTENT_LOGIN`] cookie.
+/// 
+/// * There is no [`actix_web::http::header::AUTHORIZATION`] header: API-like clients
+///   manage their own access tokens.
+/// 
+/// * There is no valid access token in [actix-identity](https://docs.rs/actix-identity/0.7.0/actix_identity/),
+///   e.g. it has expired.
+/// 
+/// If the persistent login is valid, a new access token is made and set as the 
+/// [Identity](https://docs.rs/actix-identity/0.7.0/actix_identity/struct.Identity.html) login,
+/// and the cookie is replaced with the rotated credential; or kept, if a concurrent
+/// request has just rotated it. Otherwise, the cookie is removed, and [`CheckLogin`]
+/// handles the request as not authenticated.
+/// 
+/// Accounts which can't log in, see [`crate::models::account_status`], are not restored,
+/// and the cookie is removed.
+/// 
+/// The restored session counts towards the employee's maximum number of active 
+/// sessions. If the limit is reached and ``SESSION_LIMIT_POLICY=reject``, the web 
+/// session is not restored, but the cookie is kept: it works again once another 
+/// session ends.
+/// 
+pub async fn restore_persistent_login(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let mut response_cookies = Vec::new();
+
+    if let Some(cookie) = req.cookie(PERSISTENT_LOGIN) {
+        if req.headers().get(header::AUTHORIZATION).is_none() && 
+           !verify_valid_access_token(&req).is_logged_in {
+            let app_state = req.app_data::<Data<AppState>>().cloned().unwrap();
+            let days_valid_for = app_state.cfg.persistent_login_days_valid_for;
+
+            let restored = match PersistentLoginToken::parse(cookie.value()) {
+                Some(token) => rotate_persistent_login(&app_state.db, &token, days_valid_for).await,
+                None => None,
+            };
+
+            match restored {
+                Some((email, _)) if !select_account_status(&app_state.db, &email).await.can_login() => {
+                    tracing::info!("Persistent login for {} not restored, account can't log in", email);
+                    response_cookies.push(remove_persistent_login_cookie());
+                }
+                Some((email, rotated)) => {
+                    if let Some(rotated) = rotated {
+                        response_cookies.push(build_persistent_login_cookie(&rotated.cookie_value(), days_valid_for));
+                    }
+
+                    let payload = JWTPayload::new(&email, app_state.cfg.jwt_mins_valid_for * 60);
+
+                    if open_session(&app_state.db, &email, &payload.session_id(), &app_state.cfg).await {
+                        tracing::info!("Persistent login restored for {}", email);
+
+                        let (access_token, binding_cookie) = make_bound_token(payload, req.request(), &app_state.cfg);
+                        Identity::login(&req.extensions(), make_bearer_token(&access_token)).unwrap();
+
+                        response_cookies.extend(binding_cookie);
+                    }
+                }
+                None => response_cookies.push(remove_persistent_login_cookie()),
+            };
+        }
+    }
+
+    let mut res = next.call(req).await?;
+
+    for cookie in response_cookies {
+        let _ = res.response_mut().add_cookie(&cookie);
+    }
+
+    Ok(res)
+}
+
+/// Standalone, async middleware function. It must run just after [`CheckLogin`].
+/// 
+/// When [`CheckLogin`] has authenticated the request, i.e. there is a
+/// [`crate::helper::jwt_utils::JWTPayload`] in the request extension, records that its
+/// session has been used. If the session has been revoked, the
+/// [Identity](https://docs.rs/actix-identity/0.7.0/actix_identity/struct.Identity.html)
+/// is logged out, and a 401 error is attached to the request extension as per
+/// [`CheckLogin`]. Its message is [`crate::helper::messages::SESSION_EVICTED_MSG`] for
+/// a session evicted by a newer login, [`crate::helper::messages::SESSION_REVOKED_MSG`]
+/// otherwise.
+/// 
+/// Access tokens of sessions not in the registry, see 
+/// [`crate::models::session::SessionState::Unknown`], are accepted.
+/// 
+pub async fn check_session(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let payload = req.extensions().get::<JWTPayload>().cloned();
+
+    if let Some(payload) = payload {
+        let app_state = req.app_data::<Data<AppState>>().cloned().unwrap();
+
+        if let SessionState::Revoked(reason) = touch_session(&app_state.db, &payload.session_id()).await {
+            tracing::info!("Session {} of {} has been revoked: {}", payload.session_id(), payload.email(), reason);
+
+            if let Ok(identity) = req.get_identity() {
+                identity.logout();
+            }
+
+            let message = if reason == REVOKED_EVICTED { SESSION_EVICTED_MSG } else { SESSION_REVOKED_MSG };
+
+            req.extensions_mut().insert(ResponseErrorStatus {
+                code: StatusCode::UNAUTHORIZED,
+                body: ApiStatus::new(StatusCode::UNAUTHORIZED.as_u16()).set_message(message),
+            });
+        }
+    }
+
+    next.call(req).await
 }
 
 /// The middleware factory. Naming and declaration remain as per 
@@ -253,7 +465,7 @@ pub struct CheckLogin;
 /// 
 impl<S, B> Transform<S, ServiceRequest> for CheckLogin
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -264,14 +476,16 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
-        ready(Ok(CheckLoginMiddleware { service }))
+        ready(Ok(CheckLoginMiddleware { service: Rc::new(service) }))
     }
 }
 
 /// As per [official example](https://github.com/actix/examples/blob/master/middleware/various/src/redirect.rs).
 /// 
 pub struct CheckLoginMiddleware<S> {
-    service: S,
+    /// Shared with the future of an authenticated request, which first checks the
+    /// account status in the database.
+    service: Rc<S>,
 }
 
 /// As per [official example](https://github.com/actix/examples/blob/master/middleware/various/src/redirect.rs).
@@ -280,7 +494,7 @@ pub struct CheckLoginMiddleware<S> {
 /// 
 impl<S, B> Service<ServiceRequest> for CheckLoginMiddleware<S>
 where
-    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
     S::Future: 'static,
     B: 'static,
 {
@@ -321,6 +535,17 @@ where
             // reason for the next anew redirected request.
             if route == "/ui/login" {
                 builder.cookie(build_login_redirect_cookie(UNAUTHORISED_ACCESS_MSG));
+
+                // Remembers the requested page, to return to it after logging in. Only 
+                // for requests which can be repeated by simply following a redirection.
+                if request.method() == Method::GET || request.method() == Method::HEAD {
+                    let requested = request.uri().path_and_query()
+                        .map_or(request.path(), |path_and_query| path_and_query.as_str());
+
+                    if let Some(next) = validate_next(requested) {
+                        builder.cookie(build_login_next_cookie(&next));
+                    }
+                }
             }
 
             let response = builder.finish().map_into_right_body();
@@ -366,6 +591,8 @@ where
 
         match token_status.is_logged_in {
             true => {
+                let email = token_status.payload.as_ref().unwrap().email();
+
                 // Update token new expiry, last active.
                 // Replace actix-identity Identity login with updated token.
                 // Set updated token to request extension, so that the next middleware can pick it up
@@ -374,7 +601,15 @@ where
 
                 match request.path().as_ref() {
                     "/ui/login" | "/api/login" => redirect_to_home(request),
-                    _ => call_request(request)
+                    _ => {
+                        let service = Rc::clone(&self.service);
+
+                        Box::pin(async move {
+                            verify_account_status(&request, &email).await;
+
+                            service.call(request).await.map(ServiceResponse::map_into_left_body)
+                        })
+                    }
                 }
             }
 
@@ -384,6 +619,10 @@ where
 
                     "/api/login" => call_request(request),
 
+                    "/api/magic-link" => call_request(request),
+
+                    path if path.starts_with("/api/magic-link/") => call_request(request),
+
                     _ => redirect_to_login(request)
                 }
             }
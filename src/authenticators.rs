@@ -0,0 +1,218 @@
+/* Date Created: 18/10/2026. */
+
+//! Pluggable authentication backends.
+//!
+//! An authentication backend implements the [`Authenticator`] trait: given the submitted
+//! [`crate::models::EmployeeLogin`], it decides whether the email and password are valid.
+//!
+//! Two backends are available:
+//!
+//! * ``database`` -- [`database::DatabaseAuthenticator`], the Argon2 hashed password stored
+//!   in ``employees.password``.
+//!
+//! * ``ldap`` -- [`ldap::LdapAuthenticator`], an LDAP simple bind. Passwords live in the
+//!   directory only.
+//!
+//! Backends are chosen with the ``AUTH_BACKENDS`` entry in the ``.env`` file, which is a
+//! comma separated list of backend names, e.g. ``AUTH_BACKENDS=ldap,database``. They are
+//! chained in the listed order by [`AuthenticatorChain`]: the first backend which accepts
+//! the login wins.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test authenticators::tests
+//!
+
+use futures_util::future::LocalBoxFuture;
+use sqlx::{Pool, MySql};
+
+use crate::config::Config;
+use crate::models::EmployeeLogin;
+
+pub mod database;
+pub mod ldap;
+
+/// The name of the [`database::DatabaseAuthenticator`] backend in ``AUTH_BACKENDS``.
+pub static DATABASE_BACKEND: &str = "database";
+/// The name of the [`ldap::LdapAuthenticator`] backend in ``AUTH_BACKENDS``.
+pub static LDAP_BACKEND: &str = "ldap";
+
+/// The outcome of an authentication attempt by a single backend.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthOutcome {
+    /// The email and password are valid. The value is the email as known by the
+    /// backend, which is the email that goes into the access token.
+    Authenticated(String),
+    /// The backend does not know the email, or the password does not match.
+    Rejected,
+    /// The backend could not be reached. The value is the reason, for logging.
+    Unavailable(String),
+}
+
+/// An authentication backend.
+///
+/// Implementations must not panic on bad credentials: they return
+/// [`AuthOutcome::Rejected`] instead.
+pub trait Authenticator: Send + Sync {
+    /// The name of the backend, as used in ``AUTH_BACKENDS``.
+    fn name(&self) -> &'static str;
+
+    /// Attempts to authenticate the submitted login.
+    fn authenticate<'a>(
+        &'a self,
+        login: &'a EmployeeLogin
+    ) -> LocalBoxFuture<'a, AuthOutcome>;
+}
+
+/// The configured authentication backends, in the order they are tried.
+pub struct AuthenticatorChain {
+    backends: Vec<Box<dyn Authenticator>>,
+}
+
+impl AuthenticatorChain {
+    /// Creates a chain from already constructed backends.
+    pub fn new(backends: Vec<Box<dyn Authenticator>>) -> Self {
+        Self { backends }
+    }
+
+    /// Creates the chain listed in [`crate::config::Config::auth_backends`].
+    ///
+    /// # Panics
+    ///
+    /// On an unknown backend name, or an empty list. This is a configuration error
+    /// which should stop the application from starting.
+    ///
+    pub fn from_config(config: &Config, pool: &Pool<MySql>) -> Self {
+        let backends: Vec<Box<dyn Authenticator>> = config.auth_backends.iter()
+            .map(|name| -> Box<dyn Authenticator> {
+                match name.as_str() {
+                    "database" => Box::new(database::DatabaseAuthenticator::new(pool.clone())),
+                    "ldap" => Box::new(ldap::LdapAuthenticator::new(
+                        &config.ldap_url, &config.ldap_bind_dn_template)),
+                    _ => panic!("Unknown authentication backend {} in AUTH_BACKENDS", name),
+                }
+            })
+            .collect();
+
+        if backends.is_empty() {
+            panic!("AUTH_BACKENDS must list at least one authentication backend");
+        }
+
+        Self::new(backends)
+    }
+
+    /// Returns ``true`` if a backend named `name` is in this chain.
+    pub fn has_backend(&self, name: &str) -> bool {
+        self.backends.iter().any(|backend| backend.name() == name)
+    }
+
+    /// Tries each backend in turn, until one authenticates the submitted login.
+    ///
+    /// # Return
+    ///
+    /// * ``Some(email)`` -- the email returned by the first backend which accepted the
+    ///   login.
+    ///
+    /// * ``None`` -- no backend accepted the login.
+    ///
+    pub async fn authenticate(&self, login: &EmployeeLogin) -> Option<String> {
+        for backend in self.backends.iter() {
+            match backend.authenticate(login).await {
+                AuthOutcome::Authenticated(email) => {
+                    tracing::debug!("Authenticated {} by {} backend", email, backend.name());
+                    return Some(email);
+                }
+                AuthOutcome::Rejected => {
+                    tracing::debug!("{} backend rejected {}", backend.name(), login.email);
+                }
+                AuthOutcome::Unavailable(reason) => {
+                    tracing::error!("{} backend unavailable: {}", backend.name(), reason);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test authenticators::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test authenticators::tests::test_chain_first_accepts -- --exact
+///    * cargo test authenticators::tests::test_chain_falls_through -- --exact
+///    * cargo test authenticators::tests::test_chain_all_reject -- --exact
+///    * cargo test authenticators::tests::test_has_backend -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A backend which always returns the same outcome.
+    struct FixedAuthenticator(&'static str, AuthOutcome);
+
+    impl Authenticator for FixedAuthenticator {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn authenticate<'a>(
+            &'a self,
+            _login: &'a EmployeeLogin
+        ) -> LocalBoxFuture<'a, AuthOutcome> {
+            let outcome = self.1.clone();
+            Box::pin(async move { outcome })
+        }
+    }
+
+    fn login() -> EmployeeLogin {
+        EmployeeLogin {
+            email: String::from("chirstian.koblick.10004@gmail.com"),
+            password: String::from("password"),
+            remember_me: false,
+            next: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_chain_first_accepts() {
+        let chain = AuthenticatorChain::new(vec![
+            Box::new(FixedAuthenticator("first", AuthOutcome::Authenticated(String::from("first@example.com")))),
+            Box::new(FixedAuthenticator("second", AuthOutcome::Authenticated(String::from("second@example.com")))),
+        ]);
+
+        assert_eq!(chain.authenticate(&login()).await, Some(String::from("first@example.com")));
+    }
+
+    #[actix_web::test]
+    async fn test_chain_falls_through() {
+        let chain = AuthenticatorChain::new(vec![
+            Box::new(FixedAuthenticator("first", AuthOutcome::Unavailable(String::from("down")))),
+            Box::new(FixedAuthenticator("second", AuthOutcome::Rejected)),
+            Box::new(FixedAuthenticator("third", AuthOutcome::Authenticated(String::from("third@example.com")))),
+        ]);
+
+        assert_eq!(chain.authenticate(&login()).await, Some(String::from("third@example.com")));
+    }
+
+    #[actix_web::test]
+    async fn test_chain_all_reject() {
+        let chain = AuthenticatorChain::new(vec![
+            Box::new(FixedAuthenticator("first", AuthOutcome::Rejected)),
+            Box::new(FixedAuthenticator("second", AuthOutcome::Unavailable(String::from("down")))),
+        ]);
+
+        assert_eq!(chain.authenticate(&login()).await, None);
+    }
+
+    #[test]
+    fn test_has_backend() {
+        let chain = AuthenticatorChain::new(vec![
+            Box::new(FixedAuthenticator(LDAP_BACKEND, AuthOutcome::Rejected)),
+        ]);
+
+        assert!(chain.has_backend(LDAP_BACKEND));
+        assert!(!chain.has_backend(DATABASE_BACKEND));
+    }
+}
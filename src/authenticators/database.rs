@@ -0,0 +1,45 @@
+/* Date Created: 18/10/2026. */
+
+//! The ``database`` authentication backend: the submitted password is compared against
+//! the Argon2 hashed password stored in ``employees.password``.
+
+use futures_util::future::LocalBoxFuture;
+use sqlx::{Pool, MySql};
+
+use crate::models::{EmployeeLogin, select_employee};
+use crate::helper::password_utils::verify_password;
+
+use super::{Authenticator, AuthOutcome};
+
+/// Authenticates against the ``employees`` table.
+pub struct DatabaseAuthenticator {
+    pool: Pool<MySql>,
+}
+
+impl DatabaseAuthenticator {
+    pub fn new(pool: Pool<MySql>) -> Self {
+        Self { pool }
+    }
+}
+
+impl Authenticator for DatabaseAuthenticator {
+    fn name(&self) -> &'static str {
+        super::DATABASE_BACKEND
+    }
+
+    /// The ``submitted email`` is used to identify an employee from the database. If no
+    /// employee found, the login is rejected. Otherwise the ``database password`` is
+    /// de-hashed and compared against the ``submitted password``.
+    fn authenticate<'a>(
+        &'a self,
+        login: &'a EmployeeLogin
+    ) -> LocalBoxFuture<'a, AuthOutcome> {
+        Box::pin(async move {
+            match select_employee(&self.pool, &login.email).await {
+                Some(selected_login) if verify_password(&login.password, &selected_login.password) =>
+                    AuthOutcome::Authenticated(selected_login.email),
+                _ => AuthOutcome::Rejected,
+            }
+        })
+    }
+}
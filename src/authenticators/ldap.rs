@@ -0,0 +1,144 @@
+/* Date Created: 18/10/2026. */
+
+//! The ``ldap`` authentication backend: an LDAP simple bind using
+//! [ldap3](https://docs.rs/ldap3/latest/ldap3/index.html).
+//!
+//! The bind DN is made from ``LDAP_BIND_DN_TEMPLATE`` in the ``.env`` file, where
+//! ``{email}`` is replaced with the DN escaped submitted email. For example, with
+//! ``LDAP_BIND_DN_TEMPLATE=mail={email},ou=people,dc=example,dc=com``, the login
+//! ``chirstian.koblick.10004@gmail.com`` binds as
+//! ``mail=chirstian.koblick.10004@gmail.com,ou=people,dc=example,dc=com``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test authenticators::ldap::tests
+//!
+//! See also ``tests/test_ldap_authenticator.rs``, which needs a local LDAP server.
+//!
+
+use std::time::Duration;
+
+use futures_util::future::LocalBoxFuture;
+use ldap3::{LdapConnAsync, LdapConnSettings, dn_escape};
+
+use crate::models::EmployeeLogin;
+
+use super::{Authenticator, AuthOutcome};
+
+/// The placeholder in ``LDAP_BIND_DN_TEMPLATE`` which gets replaced with the email.
+pub static EMAIL_PLACEHOLDER: &str = "{email}";
+
+/// LDAP result code ``invalidCredentials``.
+const LDAP_INVALID_CREDENTIALS: u32 = 49;
+
+/// How long to wait for the LDAP server to accept a connection.
+const LDAP_CONN_TIMEOUT_SECS: u64 = 5;
+
+/// Authenticates with an LDAP simple bind.
+pub struct LdapAuthenticator {
+    url: String,
+    bind_dn_template: String,
+}
+
+impl LdapAuthenticator {
+    /// # Arguments
+    ///
+    /// * `url` - the LDAP server URL, e.g. ``ldap://localhost:389``.
+    ///
+    /// * `bind_dn_template` - the bind DN with an ``{email}`` placeholder.
+    ///
+    pub fn new(url: &str, bind_dn_template: &str) -> Self {
+        Self {
+            url: String::from(url),
+            bind_dn_template: String::from(bind_dn_template)
+        }
+    }
+
+    /// Returns the bind DN for an email.
+    pub fn bind_dn(&self, email: &str) -> String {
+        self.bind_dn_template.replace(EMAIL_PLACEHOLDER, &dn_escape(email))
+    }
+}
+
+impl Authenticator for LdapAuthenticator {
+    fn name(&self) -> &'static str {
+        super::LDAP_BACKEND
+    }
+
+    /// Binds as the employee. A successful bind authenticates the login.
+    ///
+    /// A blank password is always rejected: LDAP servers treat a simple bind with a
+    /// blank password as an unauthenticated bind, which succeeds.
+    fn authenticate<'a>(
+        &'a self,
+        login: &'a EmployeeLogin
+    ) -> LocalBoxFuture<'a, AuthOutcome> {
+        Box::pin(async move {
+            if login.email.is_empty() || login.password.is_empty() {
+                return AuthOutcome::Rejected;
+            }
+
+            let settings = LdapConnSettings::new()
+                .set_conn_timeout(Duration::from_secs(LDAP_CONN_TIMEOUT_SECS));
+
+            let (conn, mut ldap) = match LdapConnAsync::with_settings(settings, &self.url).await {
+                Ok(res) => res,
+                Err(err) => return AuthOutcome::Unavailable(err.to_string()),
+            };
+            ldap3::drive!(conn);
+
+            let outcome = match ldap.simple_bind(&self.bind_dn(&login.email), &login.password).await {
+                Ok(res) if res.rc == 0 => AuthOutcome::Authenticated(login.email.clone()),
+                Ok(res) if res.rc == LDAP_INVALID_CREDENTIALS => AuthOutcome::Rejected,
+                Ok(res) => AuthOutcome::Unavailable(res.to_string()),
+                Err(err) => AuthOutcome::Unavailable(err.to_string()),
+            };
+
+            let _ = ldap.unbind().await;
+
+            outcome
+        })
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test authenticators::ldap::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test authenticators::ldap::tests::test_bind_dn -- --exact
+///    * cargo test authenticators::ldap::tests::test_blank_password_rejected -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_dn() {
+        let authenticator = LdapAuthenticator::new("ldap://localhost:389",
+            "mail={email},ou=people,dc=example,dc=com");
+
+        assert_eq!(authenticator.bind_dn("chirstian.koblick.10004@gmail.com"),
+            "mail=chirstian.koblick.10004@gmail.com,ou=people,dc=example,dc=com");
+
+        // DN special characters are escaped.
+        assert_eq!(authenticator.bind_dn("a,ou=admins@example.com"),
+            "mail=a\\2cou\\3dadmins@example.com,ou=people,dc=example,dc=com");
+    }
+
+    #[actix_web::test]
+    async fn test_blank_password_rejected() {
+        // Never reaches the server.
+        let authenticator = LdapAuthenticator::new("ldap://localhost:1",
+            "mail={email},ou=people,dc=example,dc=com");
+
+        let login = EmployeeLogin {
+            email: String::from("chirstian.koblick.10004@gmail.com"),
+            password: String::from(""),
+            remember_me: false,
+            next: None,
+        };
+
+        assert_eq!(authenticator.authenticate(&login).await, AuthOutcome::Rejected);
+    }
+}
@@ -14,6 +14,12 @@ pub mod australian_date_format {
     use time::macros::format_description;
     use serde::{self, Serializer, Deserialize, Deserializer};
 
+    /// Formats a date as ``dd/mm/yyyy``, e.g. for CSV, as serialised.
+    pub fn format(date: &Date) -> String {
+        let format = format_description!("[day]/[month]/[year]");
+        date.format(&format).unwrap()
+    }
+
     pub fn serialize<S>(
         date: &Date,
         serializer: S,
@@ -21,9 +27,13 @@ pub mod australian_date_format {
     where
         S: Serializer,
     {
+        serializer.serialize_str(&format(date))
+    }
+
+    /// Parses a ``dd/mm/yyyy`` date, e.g. from CSV, as deserialised.
+    pub fn parse(value: &str) -> Option<Date> {
         let format = format_description!("[day]/[month]/[year]");
-        let s = &date.format(&format).unwrap();
-        serializer.serialize_str(&s)
+        Date::parse(value, &format).ok()
     }
 
     pub fn deserialize<'de, D>(
@@ -33,10 +43,72 @@ pub mod australian_date_format {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        let format = format_description!("[day]/[month]/[year]");
-        match Date::parse(&s, &format).ok() {
+        match parse(&s) {
             Some(dt) => Ok(dt),
             None => Err(serde::de::Error::custom(format!("Error deserialise {} to YYYY-MM-DD", &s)))
         }
     }
 }
+
+/// As [`australian_date_format`], for optional dates: ``None`` is ``null``. Use with
+/// ``#[serde(default)]``, so that a missing field is also ``None``.
+pub mod australian_date_option_format {
+    use sqlx::types::time::Date;
+    use serde::{self, Serializer, Deserialize, Deserializer};
+
+    pub fn serialize<S>(
+        date: &Option<Date>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match date {
+            Some(date) => super::australian_date_format::serialize(date, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<Option<Date>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Wrapper(#[serde(with = "super::australian_date_format")] Date);
+
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(date)| date))
+    }
+}
+
+/// Serialises MySQL date time, e.g. UTC ``DATETIME`` columns, into Australian date time
+/// format ``dd/mm/yyyy hh:mm:ss``, and deserialises it back.
+pub mod australian_date_time_format {
+    use sqlx::types::time::PrimitiveDateTime;
+    use time::macros::format_description;
+    use serde::{self, Serializer, Deserialize, Deserializer};
+
+    pub fn serialize<S>(
+        date_time: &PrimitiveDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let format = format_description!("[day]/[month]/[year] [hour]:[minute]:[second]");
+        serializer.serialize_str(&date_time.format(&format).unwrap())
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<PrimitiveDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        let format = format_description!("[day]/[month]/[year] [hour]:[minute]:[second]");
+        PrimitiveDateTime::parse(&s, &format)
+            .map_err(|_| serde::de::Error::custom(format!("Error deserialise {} to YYYY-MM-DD HH:MM:SS", &s)))
+    }
+}
@@ -0,0 +1,167 @@
+/* Date Created: 18/10/2026. */
+
+//! Command line tasks, run by the application ``main()`` function instead of the
+//! HTTP server when given. E.g.:
+//!
+//!     * learn_actix_web import-employees onboarding.csv
+//!     * learn_actix_web import-employees onboarding.csv --commit
+//!     * learn_actix_web rebuild-search-index
+//!     * learn_actix_web purge-deleted-employees
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test cli::tests
+//!
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use sqlx::{Pool, MySql};
+
+use crate::config::Config;
+use crate::database::get_mysql_pool;
+use crate::models::employee_import::{ImportMode, import_employees};
+use crate::models::deleted_employee::purge_deleted_employees;
+use crate::search_index::EmployeeIndex;
+
+pub static USAGE: &str = "Usage: learn_actix_web [import-employees <file.csv> [--commit] | rebuild-search-index | purge-deleted-employees]";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Imports employees from a CSV file, see [`crate::models::employee_import`]. A dry
+    /// run, unless ``--commit`` is given.
+    ImportEmployees { path: PathBuf, mode: ImportMode },
+    /// Rebuilds the full-text search index from the database, see
+    /// [`crate::search_index`].
+    RebuildSearchIndex,
+    /// Deletes for good the employees deleted more than ``DELETED_EMPLOYEES_DAYS_RETAINED``
+    /// days ago, see [`crate::models::deleted_employee`]. Meant to be run daily, e.g. by
+    /// ``cron``.
+    PurgeDeletedEmployees,
+}
+
+impl Command {
+    /// Parses the command line arguments, without the program name.
+    ///
+    /// # Return
+    ///
+    /// * ``Ok(None)`` - no arguments: run the HTTP server.
+    ///
+    /// * ``Err(String)`` - [`USAGE`].
+    ///
+    pub fn parse(args: &[String]) -> Result<Option<Command>, String> {
+        match args {
+            [] => Ok(None),
+            [name, path] if name == "import-employees" =>
+                Ok(Some(Command::ImportEmployees { path: PathBuf::from(path), mode: ImportMode::DryRun })),
+            [name, path, flag] if name == "import-employees" && flag == "--commit" =>
+                Ok(Some(Command::ImportEmployees { path: PathBuf::from(path), mode: ImportMode::Commit })),
+            [name] if name == "rebuild-search-index" => Ok(Some(Command::RebuildSearchIndex)),
+            [name] if name == "purge-deleted-employees" => Ok(Some(Command::PurgeDeletedEmployees)),
+            _ => Err(String::from(USAGE)),
+        }
+    }
+
+    /// Runs the command against the database in ``.env``.
+    ///
+    /// # Return
+    ///
+    /// * The process exit code: ``0`` on success, ``1`` otherwise.
+    ///
+    pub async fn run(self) -> i32 {
+        let config = Config::init();
+        let pool = get_mysql_pool(config.max_connections, &config.database_url).await;
+
+        let search_index = match EmployeeIndex::open(Path::new(&config.search_index_dir)) {
+            Ok(search_index) => Arc::new(search_index),
+            Err(err) => {
+                eprintln!("{}: {}", config.search_index_dir, err);
+                return 1;
+            }
+        };
+
+        match self {
+            Command::ImportEmployees { path, mode } => run_import(&pool, &search_index, &path, mode).await,
+            Command::RebuildSearchIndex => match search_index.rebuild(&pool).await {
+                Ok(count) => {
+                    println!("{} employees indexed.", count);
+                    0
+                }
+                Err(message) => {
+                    eprintln!("{}: {}", config.search_index_dir, message);
+                    1
+                }
+            },
+            Command::PurgeDeletedEmployees => {
+                let count = purge_deleted_employees(&pool, config.deleted_employees_days_retained).await;
+                println!("{} employees deleted over {} days ago purged.", count, config.deleted_employees_days_retained);
+                0
+            }
+        }
+    }
+}
+
+async fn run_import(pool: &Pool<MySql>, search_index: &Arc<EmployeeIndex>, path: &Path, mode: ImportMode) -> i32 {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(err) => {
+            eprintln!("{}: {}", path.display(), err);
+            return 1;
+        }
+    };
+
+    match import_employees(pool, &data, mode).await {
+        Ok(report) => {
+            for error in &report.errors {
+                let emp_no = error.emp_no.map_or(String::new(), |emp_no| format!(" emp_no {}", emp_no));
+                println!("Row {}{}: {}", error.row, emp_no, error.errors.join(" "));
+            }
+            println!("{}: {} rows, {} invalid, {} imported.",
+                report.mode, report.rows, report.errors.len(), report.imported);
+
+            search_index.sync_employees(pool, &report.emp_nos).await;
+
+            match report.errors.is_empty() {
+                true => 0,
+                false => 1,
+            }
+        }
+        Err(message) => {
+            eprintln!("{}: {}", path.display(), message);
+            1
+        }
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test cli::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test cli::tests::test_command_parse -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_command_parse() {
+        assert_eq!(Command::parse(&args(&[])), Ok(None));
+
+        assert_eq!(Command::parse(&args(&["import-employees", "a.csv"])),
+            Ok(Some(Command::ImportEmployees { path: PathBuf::from("a.csv"), mode: ImportMode::DryRun })));
+        assert_eq!(Command::parse(&args(&["import-employees", "a.csv", "--commit"])),
+            Ok(Some(Command::ImportEmployees { path: PathBuf::from("a.csv"), mode: ImportMode::Commit })));
+
+        assert_eq!(Command::parse(&args(&["rebuild-search-index"])), Ok(Some(Command::RebuildSearchIndex)));
+        assert_eq!(Command::parse(&args(&["purge-deleted-employees"])), Ok(Some(Command::PurgeDeletedEmployees)));
+
+        assert_eq!(Command::parse(&args(&["import-employees"])), Err(String::from(USAGE)));
+        assert_eq!(Command::parse(&args(&["import-employees", "a.csv", "--force"])), Err(String::from(USAGE)));
+        assert_eq!(Command::parse(&args(&["serve"])), Err(String::from(USAGE)));
+    }
+}
@@ -0,0 +1,111 @@
+/* Date Created: 18/10/2026. */
+
+//! Deleted employee request handlers: the list of deleted employees, and their restore.
+//! As JSON under ``/data``. See [`crate::models::deleted_employee`].
+//!
+//! Both require the admin permission, see [`crate::models::permission`]. Requests
+//! without it get [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), before anything
+//! else is checked.
+
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web::http::StatusCode;
+
+use crate::helper::jwt_utils::JWTPayload;
+use crate::models::permission::Permission;
+use crate::models::deleted_employee::{
+    is_deleted_employees_page, select_deleted_employees, restore_employee
+};
+use crate::helper::app_utils::{make_api_status_response, make_page_response, forbidden_response};
+use crate::helper::pagination::{PageParams, link_header};
+use crate::helper::messages::{
+    DELETED_EMPLOYEE_NOT_FOUND_MSG,
+    EMPLOYEE_RESTORED_MSG,
+    PAGE_CURSOR_INVALID_MSG
+};
+
+/// Retrieves a page of the deleted employees, ordered by ``emp_no``.
+///
+/// # Arguments
+///
+/// * `page_params` - see [`crate::helper::pagination`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/deleted``
+/// * Route: ``http://localhost:5000/data/employees/deleted?limit=10``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [DeletedEmployee](`crate::models::deleted_employee::DeletedEmployee`), and ``page``
+/// is a [PageInfo](`crate::helper::pagination::PageInfo`). The ``Link`` header has the
+/// next, previous and first pages.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for invalid page parameters.
+///
+#[get("/employees/deleted")]
+pub async fn deleted_employees_json(
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    page_params: web::Query<PageParams>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Admin).await {
+        return response;
+    }
+
+    let page = match page_params.resolve(app_state.cfg.page_limit_default, app_state.cfg.page_limit_max) {
+        Ok(page) => page,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    if !is_deleted_employees_page(&page) {
+        return make_api_status_response(StatusCode::BAD_REQUEST, PAGE_CURSOR_INVALID_MSG, None);
+    }
+
+    let (employees, info) = select_deleted_employees(&app_state.db, &page).await;
+    let link = link_header("/data/employees/deleted", "", &info);
+
+    make_page_response(employees, info, &link)
+}
+
+/// Restores a deleted employee.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/600001/restore``
+/// * Method: ``POST``
+///
+/// # Return
+///
+/// * JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response status code
+/// is [OK](`actix_web::http::StatusCode::OK`), with message
+/// [`crate::helper::messages::EMPLOYEE_RESTORED_MSG`],
+/// [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or
+/// [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`) when there is no such deleted
+/// employee.
+///
+#[post("/employees/{emp_no:\\d+}/restore")]
+pub async fn restore_employee_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Admin).await {
+        return response;
+    }
+
+    let emp_no = req.match_info().get("emp_no").and_then(|emp_no| emp_no.parse::<i32>().ok());
+
+    match emp_no {
+        Some(emp_no) if restore_employee(&app_state.db, emp_no).await => {
+            tracing::info!("Employee {} restored by {}", emp_no, jwt_payload.email());
+
+            app_state.search_index.sync_employees(&app_state.db, &[emp_no]).await;
+            make_api_status_response(StatusCode::OK, EMPLOYEE_RESTORED_MSG, None)
+        }
+        _ => make_api_status_response(StatusCode::NOT_FOUND, DELETED_EMPLOYEE_NOT_FOUND_MSG, None),
+    }
+}
@@ -0,0 +1,314 @@
+/* Date Created: 18/10/2026. */
+
+//! Department-related request handlers: departments, their employees, and the
+//! departments of an employee. As JSON under ``/data``, and as HTML under ``/ui``.
+//!
+//! Responses are conditional, see [`crate::helper::conditional_get`], by ``ETag`` only:
+//! they are of collections, of departments, employees and memberships, and a row leaving
+//! a collection changes no ``updated_at`` of the rows left.
+
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_web::http::{header, StatusCode};
+
+use tera::{Context, Tera};
+
+use crate::models::select_employee_by_no;
+use crate::models::department::{
+    MembershipQuery, DepartmentDetail, DepartmentEmployee,
+    select_departments, select_department, select_employee_departments,
+    select_department_employees, is_department_employees_page
+};
+use crate::helper::conditional_get::conditional_response;
+use crate::helper::app_utils::{make_api_status_response, make_data_response, make_page_response};
+use crate::helper::pagination::{PageParams, PageInfo, PageLink, link_header, page_url};
+use crate::helper::messages::{
+    DEPARTMENT_NOT_FOUND_MSG,
+    EMPLOYEE_NOT_FOUND_MSG,
+    PAGE_CURSOR_INVALID_MSG
+};
+
+/// Retrieves all departments.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/departments``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [Department](`crate::models::department::Department`). Response status code is
+/// [OK](`actix_web::http::StatusCode::OK`).
+///
+#[get("/departments")]
+pub async fn departments_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+) -> HttpResponse {
+    conditional_response(&req, None,
+        make_data_response(StatusCode::OK, select_departments(&app_state.db).await))
+}
+
+/// Retrieves a department, with its current number of employees and its managers.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/departments/d005``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is a
+/// [DepartmentDetail](`crate::models::department::DepartmentDetail`). Response status
+/// code is [OK](`actix_web::http::StatusCode::OK`).
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/departments/{dept_no}")]
+pub async fn department_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+) -> HttpResponse {
+    let dept_no = dept_no_from_path(&req);
+
+    match select_department(&app_state.db, dept_no).await {
+        Some(department) => conditional_response(&req, None, make_data_response(StatusCode::OK, department)),
+        None => department_not_found_response(),
+    }
+}
+
+/// Retrieves a page of the employees of a department, ordered by ``emp_no``.
+///
+/// # Arguments
+///
+/// * `membership` - ``current``, defaults to ``true``: only current employees of the
+/// department. ``false`` includes former employees.
+///
+/// * `page_params` - see [`crate::helper::pagination`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/departments/d005/employees``
+/// * Route: ``http://localhost:5000/data/departments/d005/employees?current=false&limit=10``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [DepartmentEmployee](`crate::models::department::DepartmentEmployee`), and ``page``
+/// is a [`PageInfo`]. The ``Link`` header has the next, previous and first pages.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`), or
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for invalid page parameters.
+///
+#[get("/departments/{dept_no}/employees")]
+pub async fn department_employees_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    membership: web::Query<MembershipQuery>,
+    page_params: web::Query<PageParams>
+) -> HttpResponse {
+    let dept_no = dept_no_from_path(&req);
+
+    match department_employees_page(&app_state, dept_no, &membership, &page_params).await {
+        Ok((_, employees, info)) => {
+            let link = link_header(&format!("/data/departments/{}/employees", dept_no),
+                &serde_urlencoded::to_string(&*membership).unwrap(), &info);
+
+            conditional_response(&req, None, make_page_response(employees, info, &link))
+        }
+        Err(response) => response,
+    }
+}
+
+/// Retrieves the departments of an employee, ordered by ``from_date``.
+///
+/// # Arguments
+///
+/// * `membership` - ``current``, defaults to ``false``: all departments the employee
+/// has been in. ``true`` for the current department only.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/10010/departments``
+/// * Route: ``http://localhost:5000/data/employees/10010/departments?current=true``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [DepartmentMembership](`crate::models::department::DepartmentMembership`).
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/employees/{emp_no:\\d+}/departments")]
+pub async fn employee_departments_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    membership: web::Query<MembershipQuery>
+) -> HttpResponse {
+    let emp_no = match existing_emp_no(&req, &app_state).await {
+        Some(emp_no) => emp_no,
+        None => return make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    };
+
+    let memberships = select_employee_departments(&app_state.db, emp_no,
+        membership.current.unwrap_or(false)).await;
+
+    conditional_response(&req, None, make_data_response(StatusCode::OK, memberships))
+}
+
+/// Retrieves all departments as a complete HTML page, based on
+/// ``templates/departments/departments.html``.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/ui/departments``
+/// * Method: ``GET``
+///
+#[get("/departments")]
+pub async fn departments_html(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+) -> HttpResponse {
+    let mut ctx = Context::new();
+    ctx.insert("departments", &select_departments(&app_state.db).await);
+
+    conditional_response(&req, None, html_response(render_template("departments.html", &ctx)))
+}
+
+/// Retrieves a department and a page of its employees as a complete HTML page,
+/// based on ``templates/departments/department.html``. See [`department_json`] and
+/// [`department_employees_json`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/ui/departments/d005``
+/// * Route: ``http://localhost:5000/ui/departments/d005?current=false&limit=10``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: HTML. Failure: JSON, same as [`department_employees_json`].
+///
+#[get("/departments/{dept_no}")]
+pub async fn department_html(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    membership: web::Query<MembershipQuery>,
+    page_params: web::Query<PageParams>
+) -> HttpResponse {
+    let dept_no = dept_no_from_path(&req);
+
+    match department_employees_page(&app_state, dept_no, &membership, &page_params).await {
+        Ok((department, employees, info)) => {
+            let path = format!("/ui/departments/{}", dept_no);
+            let query = serde_urlencoded::to_string(&*membership).unwrap();
+
+            let mut ctx = Context::new();
+            ctx.insert("department", &department);
+            ctx.insert("employees", &employees);
+            ctx.insert("page", &info);
+            ctx.insert("next_url", &page_url(&path, &query, &info, PageLink::Next));
+            ctx.insert("prev_url", &page_url(&path, &query, &info, PageLink::Prev));
+            ctx.insert("first_url", &page_url(&path, &query, &info, PageLink::First));
+
+            let mut response = html_response(render_template("department.html", &ctx));
+            response.headers_mut().insert(header::LINK,
+                header::HeaderValue::from_str(&link_header(&path, &query, &info)).unwrap());
+
+            conditional_response(&req, None, response)
+        }
+        Err(response) => response,
+    }
+}
+
+/// Retrieves the departments of an employee as a complete HTML page, based on
+/// ``templates/departments/employee_departments.html``. See [`employee_departments_json`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/ui/employees/10010/departments``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: HTML. Failure: JSON, same as [`employee_departments_json`].
+///
+#[get("/employees/{emp_no:\\d+}/departments")]
+pub async fn employee_departments_html(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    membership: web::Query<MembershipQuery>
+) -> HttpResponse {
+    let emp_no = match existing_emp_no(&req, &app_state).await {
+        Some(emp_no) => emp_no,
+        None => return make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    };
+
+    let mut ctx = Context::new();
+    ctx.insert("emp_no", &emp_no);
+    ctx.insert("memberships", &select_employee_departments(&app_state.db, emp_no,
+        membership.current.unwrap_or(false)).await);
+
+    conditional_response(&req, None, html_response(render_template("employee_departments.html", &ctx)))
+}
+
+fn dept_no_from_path(req: &HttpRequest) -> &str {
+    req.match_info().get("dept_no").unwrap()
+}
+
+/// The ``emp_no`` path segment, if there is such an employee.
+async fn existing_emp_no(req: &HttpRequest, app_state: &super::AppState) -> Option<i32> {
+    let emp_no = req.match_info().get("emp_no")?.parse::<i32>().ok()?;
+
+    select_employee_by_no(&app_state.db, emp_no).await.map(|employee| employee.emp_no)
+}
+
+fn department_not_found_response() -> HttpResponse {
+    make_api_status_response(StatusCode::NOT_FOUND, DEPARTMENT_NOT_FOUND_MSG, None)
+}
+
+/// Retrieves a department, and the requested page of its employees.
+///
+/// # Return
+///
+/// * ``Err(HttpResponse)`` - [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`) or
+///   [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) JSON response.
+///
+async fn department_employees_page(
+    app_state: &super::AppState,
+    dept_no: &str,
+    membership: &MembershipQuery,
+    page_params: &PageParams
+) -> Result<(DepartmentDetail, Vec<DepartmentEmployee>, PageInfo), HttpResponse> {
+    let page = page_params.resolve(app_state.cfg.page_limit_default, app_state.cfg.page_limit_max)
+        .map_err(|message| make_api_status_response(StatusCode::BAD_REQUEST, &message, None))?;
+
+    if !is_department_employees_page(&page) {
+        return Err(make_api_status_response(StatusCode::BAD_REQUEST, PAGE_CURSOR_INVALID_MSG, None));
+    }
+
+    let department = select_department(&app_state.db, dept_no).await
+        .ok_or_else(department_not_found_response)?;
+
+    let (employees, info) = select_department_employees(&app_state.db, dept_no,
+        membership.current.unwrap_or(true), &page).await;
+
+    Ok((department, employees, info))
+}
+
+fn render_template(template: &str, ctx: &Context) -> String {
+    let tera = Tera::new("templates/departments/**/*").unwrap();
+
+    tera.render(template, ctx).expect("Failed to render template")
+}
+
+fn html_response(body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(body)
+}
@@ -0,0 +1,166 @@
+/* Date Created: 18/10/2026. */
+
+//! Employee CSV request handlers: export of employee search results, and bulk import,
+//! under ``/data``.
+//!
+//! The export is streamed: each record is sent as its row is read from the database
+//! cursor, so that large exports are never held in memory.
+
+use actix_web::{get, post, web, HttpResponse};
+use actix_web::http::{header, StatusCode};
+use actix_web::web::Bytes;
+use futures_util::StreamExt;
+
+use crate::models::Employee;
+use crate::models::employee_search::EmployeeSearch;
+use crate::models::employee_export::{ExportOptions, EmployeeCsvWriter};
+use crate::models::employee_import::{ImportMode, ImportQuery, import_employees};
+use crate::models::permission::Permission;
+use crate::helper::csv_utils::CSV_CONTENT_TYPE;
+use crate::helper::app_utils::{make_api_status_response, make_data_response, forbidden_response};
+use crate::helper::jwt_utils::JWTPayload;
+
+/// Exports all employees which match a search as CSV, see
+/// [`crate::models::employee_export`].
+///
+/// # Arguments
+///
+/// * `search` - the same criteria as [`crate::handlers::employees_by_query`]: partial
+/// names, filters and sort order. Page parameters are ignored: all matched employees
+/// are exported.
+///
+/// * `options` - ``columns`` and ``header``, see [`ExportOptions`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/export.csv?last_name=%25chi&first_name=%25ak``
+/// * Route: ``http://localhost:5000/data/employees/export.csv?last_name=%25&first_name=%25&gender=F&sort=-hire_date&columns=emp_no,last_name,hire_date&header=false``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: a CSV attachment, ``employees.csv``.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`), for invalid
+/// filters, sort order or columns.
+///
+/// A database error after the response has started can only abort it: the client
+/// gets an incomplete CSV, and the error is logged.
+///
+#[get("/employees/export.csv")]
+pub async fn export_employees_csv(
+    app_state: web::Data<super::AppState>,
+    search: web::Query<EmployeeSearch>,
+    options: web::Query<ExportOptions>
+) -> HttpResponse {
+    let params = search.resolve_sort()
+        .and_then(|sort| options.resolve_columns().map(|columns| (sort, columns)));
+
+    let (sort, columns) = match params {
+        Ok(params) => params,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    let pool = app_state.db.clone();
+    let search = search.into_inner();
+    let with_header = options.header();
+
+    let body = async_stream::stream! {
+        let writer = EmployeeCsvWriter::new(columns);
+
+        if with_header {
+            yield Ok(Bytes::from(writer.header()));
+        }
+
+        let mut builder = search.export_builder(&sort);
+        let mut rows = builder.build_query_as::<Employee>().fetch(&pool);
+
+        while let Some(row) = rows.next().await {
+            match row {
+                Ok(employee) => yield Ok(Bytes::from(writer.record(&employee))),
+                Err(error) => {
+                    tracing::error!("Employee CSV export failed: {}", error);
+                    yield Err(error);
+                    break;
+                }
+            }
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type(CSV_CONTENT_TYPE)
+        .insert_header((header::CONTENT_DISPOSITION, "attachment; filename=\"employees.csv\""))
+        .streaming(body)
+}
+
+/// Imports employees from an uploaded CSV, see [`crate::models::employee_import`].
+/// Requires the HR permission.
+///
+/// # Arguments
+///
+/// * `query` - ``mode``: ``dry-run``, the default, only checks the CSV. ``commit``
+/// also inserts the employees, in one transaction, if every record is valid.
+///
+/// * `body` - the CSV, as exported by [`export_employees_csv`], ``title`` is ignored.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/import``
+/// * Route: ``http://localhost:5000/data/employees/import?mode=commit``
+/// * Method: ``POST``
+/// * Content Type: ``text/csv``
+/// * Body: ``emp_no,email,birth_date,first_name,last_name,gender,hire_date``
+/// ``600001,siamak.bernardeschi.600001@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985``
+///
+/// # Return
+///
+/// * JSON of [`crate::models::DataResponse`], whose ``data`` is an
+/// [ImportReport](`crate::models::employee_import::ImportReport`), with the errors of
+/// each invalid record. Response status code is [OK](`actix_web::http::StatusCode::OK`)
+/// for a dry run, [CREATED](`actix_web::http::StatusCode::CREATED`) for a commit, or
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for a commit with errors:
+/// nothing has been written.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for an invalid ``mode``,
+/// or a CSV which can't be imported at all: e.g. unknown columns, or too many records.
+///
+#[post("/employees/import")]
+pub async fn import_employees_csv(
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    query: web::Query<ImportQuery>,
+    body: Bytes
+) -> HttpResponse {
+    let email = jwt_payload.email();
+
+    if let Some(response) = forbidden_response(&app_state, &email, Permission::Hr).await {
+        return response;
+    }
+
+    let mode = match query.mode() {
+        Ok(mode) => mode,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    let report = match import_employees(&app_state.db, &body, mode).await {
+        Ok(report) => report,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    let status = match (mode, report.errors.is_empty()) {
+        (ImportMode::DryRun, _) => StatusCode::OK,
+        (ImportMode::Commit, true) => StatusCode::CREATED,
+        (ImportMode::Commit, false) => StatusCode::BAD_REQUEST,
+    };
+
+    if report.imported > 0 {
+        tracing::info!("{} employees imported by {}", report.imported, email);
+
+        app_state.search_index.sync_employees(&app_state.db, &report.emp_nos).await;
+    }
+
+    make_data_response(status, report)
+}
@@ -2,70 +2,105 @@
 
 //! Application HTTP request handlers.
 
-use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder, web::ReqData};
+use actix_web::{get, post, put, patch, delete, web, HttpRequest, HttpResponse, Responder, web::ReqData};
+use actix_web::http::{header, StatusCode};
+use actix_web::body::MessageBody;
 
 use tera::{Context, Tera};
 
-use crate::models::{self, Employee};
-use models::{get_employees, EmployeeSearch};
+use crate::models::{self, Employee, DataResponse};
+use models::employee_export::{EmployeeCsvWriter, employees_xml};
+use models::employee_fields::{FieldsQuery, FieldSet, EmployeeInclude, EmployeeRelations, SparseEmployee};
+use models::permission::Permission;
+use models::employee_search::{search_employees, select_employees_updated_at, EmployeeSearch, SearchOptions};
+use models::{
+    NewEmployee, EmployeePatch, 
+    select_employee_by_no, lock_employee_by_no, insert_employee, update_employee, delete_employee
+};
+use crate::bh_libs::api_status::ApiStatus;
+
+use crate::helper::app_utils::{make_api_status_response, make_data_response, make_page_response, forbidden_response};
+use crate::helper::pagination::{PageInfo, PageLink, link_header, page_url};
+use crate::helper::content_negotiation::{
+    Representation, ScopeRepresentation, negotiate, make_not_acceptable_response
+};
+use crate::helper::conditional_get::{conditional_response, check_if_match, entity_tag};
+use crate::helper::messages::{
+    EMPLOYEE_NOT_FOUND_MSG,
+    EMPLOYEE_NO_MISMATCH_MSG,
+    EMPLOYEE_DELETED_MSG,
+    EMPLOYEE_IF_MATCH_REQUIRED_MSG,
+    EMPLOYEE_MODIFIED_MSG
+};
+use crate::helper::password_utils::{hash_password, validate_new_password, random_token};
+use crate::helper::jwt_utils::JWTPayload;
 
 use crate::middleware::Msg;
 
-/// Attempts to retrieve employee records based on partial last name and partial 
-/// first name, then returns matched records as JSON. Calls to [`get_employees`]
+/// Attempts to retrieve a page of employee records based on partial last name and 
+/// partial first name, then returns matched records as JSON, or as negotiated by the
+/// ``Accept`` header, see [`employees_response`]. Calls to [`search_employees`]
 /// method to do database work.
 /// 
 /// # Arguments
 /// 
-/// * `-` - un-used.
+/// * `req` - Submitted request, whose ``Accept`` header chooses the representation.
 /// 
 /// * `app_state` - [Application state](https://actix.rs/docs/application/#state). 
 /// This's where the application MySQL database connection pool is stored.
 /// This MySQL database connection pool is used to query data.
 /// 
+/// * `jwt_payload` - the logged in user, for the HR permission of ``include=salaries``.
+/// 
 /// * `body` - Effectively the submitted JSON [`actix_web::HttpRequest`] which 
-/// has been deserialised to struct [`models::EmployeeSearch`].
+/// has been deserialised to struct [`EmployeeSearch`].
+/// 
+/// * `fields` - ``fields`` and ``include`` in the query string, see [`FieldsQuery`].
 /// 
 /// # Usage Example
 /// 
 /// * Route: ``http://localhost:5000/data/employees``
+/// * Route: ``http://localhost:5000/data/employees?fields=emp_no,first_name,last_name&include=titles``
 /// * Method: ``POST``
 /// * Content Type: ``application/json``
-/// * Body: ``{"last_name": "%chi", "first_name": "%ak"}``
+/// * Body: ``{"last_name": "%chi", "first_name": "%ak", "gender": "F", "sort": "-hire_date", "limit": 10}``
 /// 
 /// # Return
 /// 
 /// * Success: JSON. Failure: JSON.
 /// 
 /// * Response status code: looks for [OK](`actix_web::http::StatusCode::OK`),
-/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) or 
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`),
+/// [NOT_ACCEPTABLE](`actix_web::http::StatusCode::NOT_ACCEPTABLE`) or 
 /// [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`).
 /// 
 /// * Response status code of [OK](`actix_web::http::StatusCode::OK`) is a successful
-/// response. The actual response is a JSON array of serialised [Employee](`crate::models::Employee`).
+/// response. The actual response is a serialised [`crate::models::DataResponse`], whose
+/// ``data`` is a JSON array of serialised [Employee](`crate::models::Employee`), and
+/// ``page`` is a [`PageInfo`]. The ``Link`` header has the next, previous and first pages.
 /// 
-/// * Response status codes of the later two indicate a failure response. The actual 
+/// * Response status codes of the later three indicate a failure response. The actual 
 /// response is a JSON serialised of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`).
 /// 
-/// # TO_DO
-/// 
-/// For successful responses, make [ApiStatus](`crate::bh_libs::api_status::ApiStatus`) 
-/// the header part of the response, the JSON array mentioned is ``data`` field.
-/// 
 #[post("/employees")]
 pub async fn employees_json1(
-    _: HttpRequest,
+    req: HttpRequest,
     app_state: web::Data<super::AppState>,
-    body: web::Json<EmployeeSearch>
-) -> impl Responder {
-    let query_result = get_employees(&app_state.db, &body.last_name, &body.first_name).await;
-    web::Json(query_result)
+    jwt_payload: web::ReqData<JWTPayload>,
+    body: web::Json<EmployeeSearch>,
+    fields: web::Query<FieldsQuery>
+) -> HttpResponse {
+    employees_response(&req, &app_state, &jwt_payload, ScopeRepresentation::DATA, &body, &fields).await
 }
 
-/// Attempts to retrieve employee records based on partial last name and partial 
-/// first name, then returns matched records as JSON. Calls to [`get_employees`]
+/// Attempts to retrieve a page of employee records based on partial last name and 
+/// partial first name, then returns matched records in the representation negotiated by
+/// the ``Accept`` header, see [`employees_response`]. Calls to [`search_employees`]
 /// method to do database work.
 /// 
+/// Registered under both ``/data`` and ``/ui``, which only differ in the representation
+/// of requests without a preference: JSON and HTML respectively.
+/// 
 /// # Arguments
 /// 
 /// * `req` - Submitted request, where URL contains path information which are partial
@@ -75,40 +110,603 @@ pub async fn employees_json1(
 /// This's where the application MySQL database connection pool is stored.
 /// This MySQL database connection pool is used to query data.
 /// 
+/// * `jwt_payload` - the logged in user, for the HR permission of ``include=salaries``.
+/// 
+/// * `scope` - the [`ScopeRepresentation`] of the scope the route is under.
+/// 
+/// * `options` - the optional filters, sort order and page in the query string.
+/// 
+/// * `fields` - ``fields`` and ``include`` in the query string, see [`FieldsQuery`].
+/// 
 /// # Usage Example
 /// 
 /// * Route: ``http://localhost:5000/data/employees/%chi/%ak``
+/// * Route: ``http://localhost:5000/data/employees/%chi/%ak?fields=emp_no,last_name``
+/// * Route: ``http://localhost:5000/ui/employees/%chi/%ak?gender=M&sort=last_name&limit=10&offset=20``
 /// * Method: ``GET``
+/// * Header: ``Accept: text/csv``, optional
 /// 
 /// # Return
 /// 
-/// * Success: JSON. Failure: JSON.
+/// * Same as [`employees_response`].
 /// 
-/// * Response status code: looks for [OK](`actix_web::http::StatusCode::OK`),
-/// [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`).
+#[get("/employees/{last_name}/{first_name}")]
+pub async fn employees_by_name(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    scope: web::Data<ScopeRepresentation>,
+    options: web::Query<SearchOptions>,
+    fields: web::Query<FieldsQuery>
+) -> HttpResponse {
+    let search = path_employee_search(&req, options.into_inner());
+
+    employees_response(&req, &app_state, &jwt_payload, **scope, &search, &fields).await
+}
+
+/// Attempts to retrieve a page of employee records based on partial last name and 
+/// partial first name in the query string, then returns matched records in the
+/// representation negotiated by the ``Accept`` header, see [`employees_response`].
+/// This is where the ``Link`` header and page links of all employee searches point to.
+/// 
+/// Registered under both ``/data`` and ``/ui``, as [`employees_by_name`] is.
+/// 
+/// # Arguments
+/// 
+/// * `req` - Submitted request, whose ``Accept`` header chooses the representation.
+/// 
+/// * `app_state` - [Application state](https://actix.rs/docs/application/#state). 
+/// This's where the application MySQL database connection pool is stored.
+/// This MySQL database connection pool is used to query data.
+/// 
+/// * `jwt_payload` - the logged in user, for the HR permission of ``include=salaries``.
+/// 
+/// * `scope` - the [`ScopeRepresentation`] of the scope the route is under.
+/// 
+/// * `query` - the query string deserialised to struct [`EmployeeSearch`].
+/// 
+/// * `fields` - ``fields`` and ``include`` in the query string, see [`FieldsQuery`].
+/// 
+/// # Usage Example
+/// 
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=10``
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&include=departments,salaries``
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25&first_name=%25&hire_date_from=01/01/1995&sort=-hire_date``
+/// * Route: ``http://localhost:5000/ui/employees?last_name=%25chi&first_name=%25ak&limit=10``
+/// * Method: ``GET``
+/// * Header: ``Accept: application/xml``, optional
+/// 
+/// # Return
+/// 
+/// * Same as [`employees_response`].
+/// 
+#[get("/employees")]
+pub async fn employees_by_query(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    scope: web::Data<ScopeRepresentation>,
+    query: web::Query<EmployeeSearch>,
+    fields: web::Query<FieldsQuery>
+) -> HttpResponse {
+    employees_response(&req, &app_state, &jwt_payload, **scope, &query, &fields).await
+}
+
+/// The search of the routes whose partial last name and partial first name are in the 
+/// URL path, i.e. ``/employees/{last_name}/{first_name}``.
+fn path_employee_search(req: &HttpRequest, options: SearchOptions) -> EmployeeSearch {
+    EmployeeSearch {
+        last_name: req.match_info().get("last_name").unwrap().parse::<String>().unwrap(),
+        first_name: req.match_info().get("first_name").unwrap().parse::<String>().unwrap(),
+        options,
+    }
+}
+
+/// Retrieves the requested page of employees, see [`search_employees`].
+/// 
+/// # Return
+/// 
+/// * ``Err(HttpResponse)`` - [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`)
+///   JSON response of invalid filters, sort order or page parameters.
+/// 
+async fn employees_page(
+    app_state: &super::AppState,
+    search: &EmployeeSearch
+) -> Result<(Vec<Employee>, PageInfo), HttpResponse> {
+    let query = search.resolve(app_state.cfg.page_limit_default, app_state.cfg.page_limit_max)
+        .map_err(|message| make_api_status_response(StatusCode::BAD_REQUEST, &message, None))?;
+
+    Ok(search_employees(&app_state.db, &query).await)
+}
+
+/// Responds to the employee searches with the result of [`employees_page`], in the
+/// representation negotiated by the ``Accept`` header. Without a preference, it is the
+/// default of the ``scope``.
+/// 
+/// ``fields`` selects the columns of the JSON, CSV and XML representations, and 
+/// ``include`` embeds histories in the JSON, see [`crate::models::employee_fields`]. 
+/// The HTML page has all columns.
+/// 
+/// Responses are conditional, see [`conditional_response`], by ``ETag`` only: an
+/// employee leaving the page changes no ``updated_at`` of the page. They vary by
+/// ``Accept``, see [`vary_accept`].
+/// 
+/// # Return
 /// 
 /// * Response status code of [OK](`actix_web::http::StatusCode::OK`) is a successful
-/// response. The actual response is a JSON array of serialised [Employee](`crate::models::Employee`).
+///   response, whose ``Link`` header has the next, previous and first pages:
+/// 
+///   - ``application/json``: a serialised [`crate::models::DataResponse`], whose
+///     ``data`` is a JSON array of [`SparseEmployee`], and ``page`` is a [`PageInfo`].
+///   - ``text/html``: the ``templates/employees.html`` page, see [`render_employees_template`].
+///   - ``text/csv``: see [`EmployeeCsvWriter`].
+///   - ``application/xml``: see [`employees_xml`].
+/// 
+/// * Response status code of [NOT_MODIFIED](`actix_web::http::StatusCode::NOT_MODIFIED`)
+///   when the client's copy is current.
+/// 
+/// * Response status codes of [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`)
+///   for invalid search criteria, fields or includes, of
+///   [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`) for ``include=salaries``
+///   without the HR permission, and of 
+///   [NOT_ACCEPTABLE](`actix_web::http::StatusCode::NOT_ACCEPTABLE`) for none of the above
+///   representations, indicate a failure response. The actual response is a JSON serialised 
+///   of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`).
+/// 
+async fn employees_response(
+    req: &HttpRequest,
+    app_state: &super::AppState,
+    jwt_payload: &JWTPayload,
+    scope: ScopeRepresentation,
+    search: &EmployeeSearch,
+    fields_query: &FieldsQuery
+) -> HttpResponse {
+    let Some(representation) = negotiate(req, scope.default) else {
+        return make_not_acceptable_response();
+    };
+
+    let fields = match resolve_fields(app_state, jwt_payload, fields_query).await {
+        Ok(fields) => fields,
+        Err(response) => return response,
+    };
+
+    let (employees, info) = match employees_page(app_state, search).await {
+        Ok(page) => page,
+        Err(response) => return response,
+    };
+
+    let path = format!("{}/employees", scope.path);
+    let query = [search.query_string(), fields_query.query_string()].into_iter()
+        .filter(|query| !query.is_empty()).collect::<Vec<String>>().join("&");
+    let link = link_header(&path, &query, &info);
+
+    let body = match representation {
+        Representation::Json => {
+            let emp_nos: Vec<i32> = employees.iter().map(|employee| employee.emp_no).collect();
+            let relations = EmployeeRelations::select(&app_state.db, &emp_nos, &fields).await;
+            let data: Vec<SparseEmployee> = employees.iter()
+                .map(|employee| relations.sparse(employee, &fields)).collect();
+
+            return conditional_response(req, None, vary_accept(make_page_response(data, info, &link)));
+        }
+        Representation::Html => render_employees_template(&employees, &info, &path, &query),
+        Representation::Csv => {
+            let writer = EmployeeCsvWriter::new(fields.columns.clone());
+            let mut body = writer.header();
+            for employee in &employees {
+                body.extend(writer.record(employee));
+            }
+            String::from_utf8(body).expect("CSV of UTF-8 fields is UTF-8")
+        }
+        Representation::Xml => employees_xml(&employees, &fields.columns, &info),
+    };
+
+    conditional_response(req, None, vary_accept(HttpResponse::Ok()
+        .content_type(representation.content_type())
+        .insert_header((header::LINK, link))
+        .body(body)))
+}
+
+/// Adds ``Vary: Accept`` to a negotiated response: the same URL has several 
+/// representations, which caches must keep apart. A
+/// [NOT_MODIFIED](`actix_web::http::StatusCode::NOT_MODIFIED`) response keeps it, see
+/// [`conditional_response`].
+fn vary_accept(mut response: HttpResponse) -> HttpResponse {
+    response.headers_mut().insert(header::VARY, header::HeaderValue::from_static("accept"));
+    response
+}
+
+/// Parses ``fields`` and ``include``, see [`FieldsQuery::resolve`]. The salaries
+/// require the HR permission, see [`crate::models::permission`].
 /// 
-/// * Response status code of [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`) 
-/// indicates a failure response. The actual response is a JSON serialised of 
-/// [ApiStatus](`crate::bh_libs::api_status::ApiStatus`).
+/// # Return
 /// 
-/// # TO_DO
+/// * ``Err(HttpResponse)`` - [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`)
+///   of unknown or repeated names, or [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`).
 /// 
-/// For successful responses, make [ApiStatus](`crate::bh_libs::api_status::ApiStatus`) 
-/// the header part of the response, the JSON array mentioned is ``data`` field.
+async fn resolve_fields(
+    app_state: &super::AppState,
+    jwt_payload: &JWTPayload,
+    fields_query: &FieldsQuery
+) -> Result<FieldSet, HttpResponse> {
+    let fields = fields_query.resolve()
+        .map_err(|message| make_api_status_response(StatusCode::BAD_REQUEST, &message, None))?;
+
+    if fields.includes(EmployeeInclude::Salaries) {
+        if let Some(response) = forbidden_response(app_state, &jwt_payload.email(), Permission::Hr).await {
+            return Err(response);
+        }
+    }
+
+    Ok(fields)
+}
+
+/// Reads the ``emp_no`` path segment. The routes only match digits, but the value might 
+/// still be out of range.
+fn emp_no_from_path(req: &HttpRequest) -> Option<i32> {
+    req.match_info().get("emp_no").and_then(|emp_no| emp_no.parse::<i32>().ok())
+}
+
+/// The ``404`` response of an unknown ``emp_no``.
+fn employee_not_found_response() -> HttpResponse {
+    make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None)
+}
+
+/// The ``ETag`` of an employee's record: that of its JSON, as [`get_employee`] without
+/// ``fields`` and ``include`` responds, so that the ``ETag`` of that response is the
+/// ``If-Match`` of updates. A change of any field, or of the current title, changes it.
+fn employee_etag(employee: &Employee) -> header::EntityTag {
+    let body = make_data_response(StatusCode::OK, employee).into_body().try_into_bytes().unwrap();
+
+    entity_tag(&body)
+}
+
+/// A JSON response of the employee, with its ``ETag``.
+fn employee_response(status_code: StatusCode, employee: &Employee) -> HttpResponse {
+    let mut response = make_data_response(status_code, employee);
+    response.headers_mut().insert(header::ETAG,
+        header::HeaderValue::from_str(&employee_etag(employee).to_string()).unwrap());
+
+    response
+}
+
+/// The [PRECONDITION_FAILED](`actix_web::http::StatusCode::PRECONDITION_FAILED`) 
+/// response of an ``If-Match`` which is not the employee's ``ETag``: it has the current 
+/// employee, and its ``ETag``, so that the client can redo its changes on it.
+fn employee_modified_response(current: &Employee) -> HttpResponse {
+    let body = DataResponse {
+        api_status: ApiStatus::new(StatusCode::PRECONDITION_FAILED.as_u16()).set_message(EMPLOYEE_MODIFIED_MSG),
+        data: current,
+        page: None,
+    };
+
+    HttpResponse::PreconditionFailed()
+        .content_type(header::ContentType::json())
+        .insert_header((header::ETAG, employee_etag(current)))
+        .body(serde_json::to_string(&body).unwrap())
+}
+
+/// Retrieves a single employee by the employee number.
 /// 
-#[get("/employees/{last_name}/{first_name}")]
-pub async fn employees_json2(
+/// # Usage Example
+/// 
+/// * Route: ``http://localhost:5000/data/employees/10004``
+/// * Route: ``http://localhost:5000/data/employees/10004?fields=emp_no,first_name,last_name&include=titles``
+/// * Method: ``GET``
+/// 
+/// # Return
+/// 
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is the 
+/// [Employee](`crate::models::Employee`), as a [`SparseEmployee`] of ``fields`` and
+/// ``include``, see [`FieldsQuery`]. Response status code is 
+/// [OK](`actix_web::http::StatusCode::OK`), or
+/// [NOT_MODIFIED](`actix_web::http::StatusCode::NOT_MODIFIED`), see [`conditional_response`].
+/// 
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response 
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`),
+/// [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`),
+/// [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`) or 
+/// [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`).
+/// 
+#[get("/employees/{emp_no:\\d+}")]
+pub async fn get_employee(
     req: HttpRequest,
     app_state: web::Data<super::AppState>,
-) -> impl Responder {
-    let last_name: String = req.match_info().get("last_name").unwrap().parse::<String>().unwrap();
-    let first_name: String = req.match_info().get("first_name").unwrap().parse::<String>().unwrap();
+    jwt_payload: web::ReqData<JWTPayload>,
+    fields: web::Query<FieldsQuery>
+) -> HttpResponse {
+    let fields = match resolve_fields(&app_state, &jwt_payload, &fields).await {
+        Ok(fields) => fields,
+        Err(response) => return response,
+    };
 
-    let query_result = get_employees(&app_state.db, &last_name, &first_name).await;
-    web::Json(query_result)
+    let employee = match emp_no_from_path(&req) {
+        Some(emp_no) => select_employee_by_no(&app_state.db, emp_no).await,
+        None => None,
+    };
+
+    match employee {
+        Some(employee) => {
+            let relations = EmployeeRelations::select(&app_state.db, &[employee.emp_no], &fields).await;
+            let last_modified = select_employees_updated_at(&app_state.db, &[employee.emp_no]).await;
+
+            conditional_response(&req, last_modified,
+                make_data_response(StatusCode::OK, relations.sparse(&employee, &fields)))
+        }
+        None => employee_not_found_response(),
+    }
+}
+
+/// Creates a new employee.
+/// 
+/// ``POST /data/employees`` is the employee search, see [`employees_json1`]. So new
+/// employees are posted to ``/data/employees/new``.
+/// 
+/// Creating, updating and deleting employees require the HR permission, see 
+/// [`crate::models::permission`].
+/// 
+/// The submitted employee is validated, see [`crate::models::Employee::validate`].
+/// The optional initial ``password`` is checked as for a password change, see 
+/// [`crate::helper::password_utils::validate_new_password`].
+/// 
+/// # Usage Example
+/// 
+/// * Route: ``http://localhost:5000/data/employees/new``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"emp_no": 600001, "email": "siamak.bernardeschi.600001@gmail.com", "birth_date": "14/12/1955",
+/// "first_name": "Siamak", "last_name": "Bernardeschi", "gender": "M", "hire_date": "26/04/1985"}``
+/// 
+/// # Return
+/// 
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is the new
+/// [Employee](`crate::models::Employee`). Response status code is 
+/// [CREATED](`actix_web::http::StatusCode::CREATED`), the ``Location`` header is the
+/// new employee's URL, and the ``ETag`` header is the ``If-Match`` of its updates.
+/// 
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response 
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for invalid
+/// data, [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or 
+/// [CONFLICT](`actix_web::http::StatusCode::CONFLICT`) when ``emp_no`` or ``email`` is
+/// taken.
+/// 
+#[post("/employees/new")]
+pub async fn create_employee(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    body: web::Json<NewEmployee>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Hr).await {
+        return response;
+    }
+
+    let NewEmployee { mut employee, password } = body.into_inner();
+    // Read only, a new employee has no title yet.
+    employee.title = None;
+    employee.normalize();
+
+    if let Err(message) = employee.validate() {
+        return make_api_status_response(StatusCode::BAD_REQUEST, &message, None);
+    }
+
+    let password = match password {
+        Some(password) => {
+            if let Err(api_status) = validate_new_password(
+                &app_state.cfg.breached_passwords_file, &password) {
+                return api_status.respond_to(&req);
+            }
+            password
+        }
+        None => random_token(32),
+    };
+
+    if let Err(api_status) = insert_employee(&app_state.db, &employee, &hash_password(&password)).await {
+        return api_status.respond_to(&req);
+    }
+
+    tracing::info!("Employee {} created", employee.emp_no);
+
+    app_state.search_index.sync_employees(&app_state.db, &[employee.emp_no]).await;
+
+    let mut response = employee_response(StatusCode::CREATED, &employee);
+    response.headers_mut().insert(header::LOCATION, 
+        header::HeaderValue::from_str(&format!("/data/employees/{}", employee.emp_no)).unwrap());
+
+    response
+}
+
+/// Replaces an existing employee.
+/// 
+/// The body is a complete [Employee](`crate::models::Employee`), validated as for
+/// [`create_employee`]. Its ``emp_no`` must match the URL.
+/// 
+/// The update is conditional: ``If-Match`` must be the employee's ``ETag``, as the
+/// ``GET`` of the employee, or the response of its last update, has it. So two users
+/// editing the same employee can't overwrite each other's changes unknowingly.
+/// 
+/// # Usage Example
+/// 
+/// * Route: ``http://localhost:5000/data/employees/600001``
+/// * Method: ``PUT``
+/// * Header: ``If-Match: "<ETag>"``
+/// * Content Type: ``application/json``
+/// * Body: ``{"emp_no": 600001, "email": "siamak.bernardeschi.600001@gmail.com", "birth_date": "14/12/1955",
+/// "first_name": "Siamak", "last_name": "Bernardeschi", "gender": "M", "hire_date": "26/04/1985"}``
+/// 
+/// # Return
+/// 
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is the updated
+/// [Employee](`crate::models::Employee`). Response status code is 
+/// [OK](`actix_web::http::StatusCode::OK`), the ``ETag`` header is the new one.
+/// 
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response 
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`), 
+/// [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), 
+/// [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`), 
+/// [CONFLICT](`actix_web::http::StatusCode::CONFLICT`) when ``email`` is taken, or
+/// [PRECONDITION_REQUIRED](`actix_web::http::StatusCode::PRECONDITION_REQUIRED`) 
+/// without ``If-Match``.
+/// 
+/// * Failure: [PRECONDITION_FAILED](`actix_web::http::StatusCode::PRECONDITION_FAILED`)
+/// when the employee has been changed since: JSON of [`crate::models::DataResponse`], 
+/// whose ``data`` is the current employee, and the ``ETag`` header is its ``ETag``.
+/// 
+#[put("/employees/{emp_no:\\d+}")]
+pub async fn replace_employee(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    body: web::Json<Employee>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Hr).await {
+        return response;
+    }
+
+    let employee = body.into_inner();
+
+    if emp_no_from_path(&req) != Some(employee.emp_no) {
+        return make_api_status_response(StatusCode::BAD_REQUEST, EMPLOYEE_NO_MISMATCH_MSG, None);
+    }
+
+    // Read only, keeps the current title.
+    save_employee(&req, &app_state, employee.emp_no,
+        |current| Employee { title: current.title, ..employee }).await
+}
+
+/// Updates some fields of an existing employee.
+/// 
+/// The body is an [EmployeePatch](`crate::models::EmployeePatch`). The resulting 
+/// employee is validated as for [`create_employee`].
+/// 
+/// # Usage Example
+/// 
+/// * Route: ``http://localhost:5000/data/employees/600001``
+/// * Method: ``PATCH``
+/// * Header: ``If-Match: "<ETag>"``
+/// * Content Type: ``application/json``
+/// * Body: ``{"last_name": "Koblick"}``
+/// 
+/// # Return
+/// 
+/// * Same as [`replace_employee`].
+/// 
+#[patch("/employees/{emp_no:\\d+}")]
+pub async fn patch_employee(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    body: web::Json<EmployeePatch>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Hr).await {
+        return response;
+    }
+
+    match emp_no_from_path(&req) {
+        Some(emp_no) => {
+            let patch = body.into_inner();
+            save_employee(&req, &app_state, emp_no, |current| patch.apply(current)).await
+        }
+        None => employee_not_found_response(),
+    }
+}
+
+/// Validates and stores an updated employee, for [`replace_employee`] and 
+/// [`patch_employee`].
+/// 
+/// The employee is locked while ``If-Match`` is checked and the employee is updated,
+/// see [`lock_employee_by_no`]: of two concurrent updates of the same ``ETag``, the
+/// second fails.
+/// 
+/// # Arguments
+/// 
+/// * `update` - the updated employee of the current one.
+/// 
+async fn save_employee(
+    req: &HttpRequest,
+    app_state: &super::AppState,
+    emp_no: i32,
+    update: impl FnOnce(Employee) -> Employee
+) -> HttpResponse {
+    let mut transaction = app_state.db.begin().await.unwrap();
+
+    // Dropping the transaction rolls it back, and releases the lock.
+    let Some(current) = lock_employee_by_no(&mut *transaction, emp_no).await else {
+        return employee_not_found_response();
+    };
+
+    match check_if_match(req, &employee_etag(&current)) {
+        Ok(()) => {}
+        Err(StatusCode::PRECONDITION_FAILED) => {
+            tracing::info!("Employee {} has been changed since it was read", emp_no);
+            return employee_modified_response(&current);
+        }
+        Err(status_code) => return make_api_status_response(status_code, EMPLOYEE_IF_MATCH_REQUIRED_MSG, None),
+    }
+
+    let mut employee = update(current);
+    employee.normalize();
+
+    if let Err(message) = employee.validate() {
+        return make_api_status_response(StatusCode::BAD_REQUEST, &message, None);
+    }
+
+    if let Err(api_status) = update_employee(&mut *transaction, &employee).await {
+        return api_status.respond_to(req);
+    }
+
+    transaction.commit().await.unwrap();
+
+    tracing::info!("Employee {} updated", employee.emp_no);
+
+    app_state.search_index.sync_employees(&app_state.db, &[employee.emp_no]).await;
+
+    employee_response(StatusCode::OK, &employee)
+}
+
+/// Deletes an employee: a soft delete, by the logged in user, see 
+/// [`crate::models::delete_employee`]. Admins can restore it, see 
+/// [`crate::deleted_employee_handlers`].
+/// 
+/// # Usage Example
+/// 
+/// * Route: ``http://localhost:5000/data/employees/600001``
+/// * Method: ``DELETE``
+/// 
+/// # Return
+/// 
+/// * JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response status code
+/// is [OK](`actix_web::http::StatusCode::OK`), with message 
+/// [`crate::helper::messages::EMPLOYEE_DELETED_MSG`], 
+/// [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or 
+/// [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`), also when the employee is
+/// already deleted.
+/// 
+#[delete("/employees/{emp_no:\\d+}")]
+pub async fn remove_employee(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Hr).await {
+        return response;
+    }
+
+    let deleted = match emp_no_from_path(&req) {
+        Some(emp_no) => delete_employee(&app_state.db, emp_no, &jwt_payload.email()).await,
+        None => false,
+    };
+
+    match deleted {
+        true => {
+            let emp_no = emp_no_from_path(&req).unwrap();
+            tracing::info!("Employee {} deleted by {}", emp_no, jwt_payload.email());
+
+            app_state.search_index.sync_employees(&app_state.db, &[emp_no]).await;
+            make_api_status_response(StatusCode::OK, EMPLOYEE_DELETED_MSG, None)
+        }
+        false => employee_not_found_response(),
+    }
 }
 
 /// Generates a complete HTML page with retrieved data from the ``employees`` table,
@@ -119,11 +717,17 @@ pub async fn employees_json2(
 /// * `employees` - List of retrieved ``employees`` in JSON format. Column values are
 /// rendered as are.
 /// 
+/// * `info` - the page ``employees`` is.
+/// 
+/// * `path` - the route of the search, e.g. ``/ui/employees``, for the links to other pages.
+/// 
+/// * `query` - the search's URL encoded query string, for the links to other pages.
+/// 
 /// # Return
 /// 
 /// - HTML string.
 /// 
-fn render_employees_template(employees: &Vec<Employee>) -> String {
+fn render_employees_template(employees: &Vec<Employee>, info: &PageInfo, path: &str, query: &str) -> String {
     // Create a new Tera instance and add a template from a string
     let tera = Tera::new("templates/**/*").unwrap();
 
@@ -131,24 +735,33 @@ fn render_employees_template(employees: &Vec<Employee>) -> String {
 
     // Passing data to be rendered to the template engine.
     ctx.insert("employees", employees);
+    ctx.insert("page", info);
+    ctx.insert("next_url", &page_url(path, query, info, PageLink::Next));
+    ctx.insert("prev_url", &page_url(path, query, info, PageLink::Prev));
+    ctx.insert("first_url", &page_url(path, query, info, PageLink::First));
 
     tera.render("employees.html", &ctx).expect("Failed to render template")
 }
 
-/// Attempts to retrieve employee records based on partial last name and partial 
-/// first name, then returns matched records as a complete HTML page. Calls to 
-/// [`get_employees`] method to do database work.
+/// Attempts to retrieve a page of employee records based on partial last name and partial 
+/// first name, then returns matched records as a complete HTML page, or as negotiated by
+/// the ``Accept`` header, see [`employees_response`]. Calls to [`search_employees`] 
+/// method to do database work.
 /// 
 /// # Arguments
 /// 
-/// * `-` - un-used.
+/// * `req` - Submitted request, whose ``Accept`` header chooses the representation.
 /// 
 /// * `app_state` - [Application state](https://actix.rs/docs/application/#state). 
 /// This's where the application MySQL database connection pool is stored.
 /// This MySQL database connection pool is used to query data.
 /// 
+/// * `jwt_payload` - the logged in user, for the HR permission of ``include=salaries``.
+/// 
 /// * `body` - Effectively the submitted ``application/x-www-form-urlencoded; charset=UTF-8`` 
-/// [`actix_web::HttpRequest`] which has been deserialised to struct [`models::EmployeeSearch`].
+/// [`actix_web::HttpRequest`] which has been deserialised to struct [`EmployeeSearch`].
+/// 
+/// * `fields` - ``fields`` and ``include`` in the query string, see [`FieldsQuery`].
 /// 
 /// # Usage Example
 /// 
@@ -162,73 +775,26 @@ fn render_employees_template(employees: &Vec<Employee>) -> String {
 /// * Success: HTML. Failure: JSON.
 /// 
 /// * Response status code: looks for [OK](`actix_web::http::StatusCode::OK`),
-/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) or 
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`),
+/// [NOT_ACCEPTABLE](`actix_web::http::StatusCode::NOT_ACCEPTABLE`) or 
 /// [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`).
 /// 
 /// * Response status code of [OK](`actix_web::http::StatusCode::OK`) is a successful
-/// response. The actual response is a HTML rendered of [Employee](`crate::models::Employee`).
+/// response. The actual response is a HTML rendered of [Employee](`crate::models::Employee`),
+/// with links to the next and previous pages, also in the ``Link`` header.
 /// 
-/// * Response status codes of the later two indicate a failure response. The actual 
+/// * Response status codes of the later three indicate a failure response. The actual 
 /// response is a JSON serialised of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`).
 /// 
 #[post("/employees")]
 pub async fn employees_html1(
-    _: HttpRequest,
-    app_state: web::Data<super::AppState>,
-    body: web::Form<EmployeeSearch>
-) -> impl Responder {
-    let query_result = get_employees(&app_state.db, &body.last_name, &body.first_name).await;
-
-    HttpResponse::Ok()
-        .content_type("text/html; charset=utf-8")
-        .body(render_employees_template(&query_result))    
-}
-
-/// Attempts to retrieve employee records based on partial last name and partial 
-/// first name, then returns matched records as a complete HTML page. Calls to 
-/// [`get_employees`] method to do database work.
-/// 
-/// # Arguments
-/// 
-/// * `req` - Submitted request, where URL contains path information which are partial
-/// last name and partial first name.
-/// 
-/// * `app_state` - [Application state](https://actix.rs/docs/application/#state). 
-/// This's where the application MySQL database connection pool is stored.
-/// This MySQL database connection pool is used to query data.
-/// 
-/// # Usage Example
-/// 
-/// * Route: ``http://localhost:5000/ui/employees/%chi/%ak``
-/// * Method: ``GET``
-/// 
-/// # Return
-/// 
-/// * Success: HTML. Failure: JSON.
-/// 
-/// * Response status code: looks for [OK](`actix_web::http::StatusCode::OK`) 
-/// or [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`).
-/// 
-/// * Response status code of [OK](`actix_web::http::StatusCode::OK`) is a successful
-/// response. The actual response is a HTML rendered of [Employee](`crate::models::Employee`).
-/// 
-/// * Response status code of [UNAUTHORIZED](`actix_web::http::StatusCode::UNAUTHORIZED`) 
-/// indicates a failure response. The actual response is a JSON serialised of 
-/// [ApiStatus](`crate::bh_libs::api_status::ApiStatus`).
-/// 
-#[get("/employees/{last_name}/{first_name}")]
-pub async fn employees_html2(
     req: HttpRequest,
     app_state: web::Data<super::AppState>,
-) -> impl Responder {
-    let last_name: String = req.match_info().get("last_name").unwrap().parse::<String>().unwrap();
-    let first_name: String = req.match_info().get("first_name").unwrap().parse::<String>().unwrap();
-
-    let query_result = get_employees(&app_state.db, &last_name, &first_name).await;
-
-    HttpResponse::Ok()
-        .content_type("text/html; charset=utf-8")
-        .body(render_employees_template(&query_result))
+    jwt_payload: web::ReqData<JWTPayload>,
+    body: web::Form<EmployeeSearch>,
+    fields: web::Query<FieldsQuery>
+) -> HttpResponse {
+    employees_response(&req, &app_state, &jwt_payload, ScopeRepresentation::UI, &body, &fields).await
 }
 
 /// [SayHi](`super::middleware::SayHi`) middleware resource endpoint handler.
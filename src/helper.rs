@@ -13,4 +13,12 @@ pub mod app_utils;
 pub mod endpoint;
 pub mod messages;
 pub mod jwt_utils;
-pub mod app_logger;
\ No newline at end of file
+pub mod app_logger;
+pub mod password_utils;
+pub mod token_binding;
+pub mod login_next;
+pub mod pagination;
+pub mod org_chart_export;
+pub mod csv_utils;
+pub mod content_negotiation;
+pub mod conditional_get;
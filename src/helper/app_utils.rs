@@ -3,19 +3,27 @@
 //! Application helper / utility functions.
 
 use actix_web::{
-    http::{header, StatusCode, header::ContentType}, HttpRequest, cookie::{Cookie, SameSite}, 
+    http::{header, StatusCode, header::ContentType}, HttpRequest, cookie::{Cookie, SameSite, time::Duration}, 
     HttpResponse, Responder, body::BoxBody, //HttpMessage
 };
 
 use crate::helper::constants::{
     REDIRECT_MESSAGE,
-    ORIGINAL_CONTENT_TYPE
+    ORIGINAL_CONTENT_TYPE,
+    PERSISTENT_LOGIN,
+    MAGIC_LINK_BROWSER,
+    TOKEN_BINDING,
+    LOGIN_NEXT
 };
 use crate::bh_libs::api_status::ApiStatus;
 // use crate::helper::html_renderer::render_home_page;
 // use crate::models::LoginSuccessResponse;
 
-use crate::helper::endpoint::serialise_api_status;
+use crate::helper::endpoint::{http_status_code, serialise_api_status};
+use crate::helper::pagination::PageInfo;
+use crate::models::DataResponse;
+use crate::models::permission::{Permission, has_permission};
+use crate::AppState;
 
 /// Creates and returns a cookie.
 /// 
@@ -220,6 +228,138 @@ pub fn remove_original_content_type_cookie<'a>() -> Cookie<'a> {
     build_cookie(ORIGINAL_CONTENT_TYPE, "", true, true)
 }
 
+/// Creates and returns a server-side cookie whose name is [`crate::helper::constants::PERSISTENT_LOGIN`].
+/// 
+/// Unlike other cookies, this one outlives the browser session: it expires after
+/// ``days_valid_for`` days.
+/// 
+/// # Arguments
+/// 
+/// * `value` - ``selector:validator``, see [`crate::models::persistent_login::PersistentLoginToken::cookie_value`].
+/// 
+/// * `days_valid_for` - see [`crate::config::Config::persistent_login_days_valid_for`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn build_persistent_login_cookie(
+    value: &str,
+    days_valid_for: u64
+) -> Cookie<'static> {
+    let mut cookie = build_cookie(PERSISTENT_LOGIN, value, true, false).into_owned();
+    cookie.set_max_age(Duration::days(days_valid_for as i64));
+
+    cookie
+}
+
+/// Creates and returns a server-side cookie to be removed, and whose name is 
+/// [`crate::helper::constants::PERSISTENT_LOGIN`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn remove_persistent_login_cookie<'a>() -> Cookie<'a> {
+    build_cookie(PERSISTENT_LOGIN, "", true, true)
+}
+
+/// Creates and returns a server-side cookie whose name is [`crate::helper::constants::MAGIC_LINK_BROWSER`].
+/// 
+/// It binds sign-in links to the browser which requested them, see
+/// [`crate::models::magic_link`]. It lasts as long as the links do.
+/// 
+/// # Arguments
+/// 
+/// * `browser_secret` - the random secret whose hash is stored with the links.
+/// 
+/// * `mins_valid_for` - see [`crate::config::Config::magic_link_mins_valid_for`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn build_magic_link_browser_cookie(
+    browser_secret: &str,
+    mins_valid_for: u64
+) -> Cookie<'static> {
+    let mut cookie = build_cookie(MAGIC_LINK_BROWSER, browser_secret, true, false).into_owned();
+    cookie.set_max_age(Duration::minutes(mins_valid_for as i64));
+
+    cookie
+}
+
+/// Creates and returns a server-side cookie to be removed, and whose name is 
+/// [`crate::helper::constants::MAGIC_LINK_BROWSER`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn remove_magic_link_browser_cookie<'a>() -> Cookie<'a> {
+    build_cookie(MAGIC_LINK_BROWSER, "", true, true)
+}
+
+/// Creates and returns a server-side cookie whose name is [`crate::helper::constants::TOKEN_BINDING`].
+/// 
+/// It holds the per-session secret which access tokens are bound to, when 
+/// ``TOKEN_BINDING=cookie``. See [`crate::helper::token_binding`].
+/// 
+/// # Arguments
+/// 
+/// * `secret` - the random per-session secret.
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn build_token_binding_cookie(
+    secret: &str
+) -> Cookie<'static> {
+    build_cookie(TOKEN_BINDING, secret, true, false).into_owned()
+}
+
+/// Creates and returns a server-side cookie to be removed, and whose name is 
+/// [`crate::helper::constants::TOKEN_BINDING`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn remove_token_binding_cookie<'a>() -> Cookie<'a> {
+    build_cookie(TOKEN_BINDING, "", true, true)
+}
+
+/// Creates and returns a server-side cookie whose name is [`crate::helper::constants::LOGIN_NEXT`].
+/// 
+/// It holds the path of a protected resource requested while not logged in, which
+/// users are returned to after logging in. See [`crate::helper::login_next`].
+/// 
+/// # Arguments
+/// 
+/// * `next` - an already validated path, see [`crate::helper::login_next::validate_next`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn build_login_next_cookie(
+    next: &str
+) -> Cookie<'static> {
+    build_cookie(LOGIN_NEXT, next, true, false).into_owned()
+}
+
+/// Creates and returns a server-side cookie to be removed, and whose name is 
+/// [`crate::helper::constants::LOGIN_NEXT`].
+/// 
+/// # Return
+/// 
+/// * [`actix_web::cookie::Cookie`].
+/// 
+pub fn remove_login_next_cookie<'a>() -> Cookie<'a> {
+    build_cookie(LOGIN_NEXT, "", true, true)
+}
+
 /// See [Response with custom type](https://actix.rs/docs/handlers#response-with-custom-type).
 /// 
 /// # Note
@@ -264,6 +404,96 @@ pub fn make_api_status_response(
         .body(serialise_api_status(status_code, message, session_id)) 
 }
 
+/// Checks that the logged in user has a permission, see [`crate::models::permission`].
+/// 
+/// # Arguments
+/// 
+/// * `app_state` - the application state, whose database and config grant permissions.
+/// 
+/// * `email` - the logged in email.
+/// 
+/// * `permission` - the required permission.
+/// 
+/// # Return
+/// 
+/// * The [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`) JSON response of 
+///   [`Permission::required_message`] if the user does not have `permission`, 
+///   otherwise ``None``.
+/// 
+pub async fn forbidden_response(
+    app_state: &AppState,
+    email: &str,
+    permission: Permission
+) -> Option<HttpResponse> {
+    if has_permission(&app_state.db, &app_state.cfg, email, permission).await {
+        return None;
+    }
+
+    tracing::info!("{} does not have the {:?} permission", email, permission);
+
+    Some(make_api_status_response(StatusCode::FORBIDDEN, permission.required_message(), None))
+}
+
+/// Creates and returns a serialised [`crate::models::DataResponse`] as [`actix_web::HttpResponse`].
+/// 
+/// # Arguments
+/// 
+/// * `status_code` - the response status code, also the ``code`` field.
+/// 
+/// * `data` - the ``data`` field.
+/// 
+/// # Return
+/// 
+/// * JSON of [`crate::models::DataResponse`] as [`actix_web::HttpResponse`].
+/// 
+pub fn make_data_response<T: serde::Serialize>(
+    status_code: StatusCode,
+    data: T
+) -> HttpResponse {
+    let response = DataResponse {
+        api_status: ApiStatus::new(http_status_code(status_code)),
+        data,
+        page: None,
+    };
+
+    HttpResponse::Ok()
+        .status(status_code)
+        .content_type(ContentType::json())
+        .body(serde_json::to_string(&response).unwrap())
+}
+
+/// Creates and returns a serialised [`crate::models::DataResponse`] of a page of a list
+/// as [`actix_web::HttpResponse`], with status code [`StatusCode::OK`].
+/// 
+/// # Arguments
+/// 
+/// * `data` - the rows of the page, the ``data`` field.
+/// 
+/// * `page` - the ``page`` field.
+/// 
+/// * `link` - the ``Link`` header, see [`crate::helper::pagination::link_header`].
+/// 
+/// # Return
+/// 
+/// * JSON of [`crate::models::DataResponse`] as [`actix_web::HttpResponse`].
+/// 
+pub fn make_page_response<T: serde::Serialize>(
+    data: T,
+    page: PageInfo,
+    link: &str
+) -> HttpResponse {
+    let response = DataResponse {
+        api_status: ApiStatus::new(http_status_code(StatusCode::OK)),
+        data,
+        page: Some(page),
+    };
+
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .insert_header((header::LINK, link))
+        .body(serde_json::to_string(&response).unwrap())
+}
+
 /*
 impl Responder for LoginSuccessResponse {
     type Body = BoxBody;
@@ -0,0 +1,269 @@
+/* Date Created: 18/10/2026. */
+
+//! Conditional ``GET`` requests: ``ETag`` and ``Last-Modified`` response headers, and
+//! [NOT_MODIFIED](`actix_web::http::StatusCode::NOT_MODIFIED`) for ``If-None-Match``
+//! and ``If-Modified-Since`` requests of unchanged responses.
+//!
+//! And conditional updates: ``If-Match`` of ``PUT`` and ``PATCH`` requests must be the
+//! current ``ETag`` of the resource, see [`check_if_match`].
+//!
+//! The ``ETag`` is strong: a hash of the response body, so it differs per
+//! representation, e.g. JSON and HTML of the same employees. ``Last-Modified`` is the
+//! ``updated_at`` of the ``employees`` row of an employee's response. Changes of the
+//! effective dated rows, e.g. ``titles``, only change the ``ETag``: as in RFC 9110,
+//! ``If-Modified-Since`` is ignored when there is ``If-None-Match``. Collections, e.g.
+//! pages of employees, have no ``Last-Modified``: a row leaving a collection changes no
+//! ``updated_at`` of the rows left.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::conditional_get::tests
+//!
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use actix_web::{HttpRequest, HttpResponse};
+use actix_web::body::MessageBody;
+use actix_web::http::{Method, StatusCode};
+use actix_web::http::header::{
+    self, Header, EntityTag, ETag, HttpDate, IfMatch, IfModifiedSince, IfNoneMatch, LastModified
+};
+use sha2::{Digest, Sha256};
+use time::OffsetDateTime;
+
+/// The strong entity tag of a response body: the hex of the first 16 bytes of its
+/// SHA-256.
+pub fn entity_tag(body: &[u8]) -> EntityTag {
+    EntityTag::new_strong(hex::encode(&Sha256::digest(body)[..16]))
+}
+
+/// Checks ``If-Match`` of an update request against the current ``etag`` of the
+/// resource, by strong comparison. ``*`` matches any existing resource.
+///
+/// # Return
+///
+/// * ``Err(StatusCode)`` - [PRECONDITION_REQUIRED](`actix_web::http::StatusCode::PRECONDITION_REQUIRED`)
+///   without ``If-Match``, or [PRECONDITION_FAILED](`actix_web::http::StatusCode::PRECONDITION_FAILED`)
+///   when it doesn't match, i.e. the resource has been changed since the client read it.
+///
+pub fn check_if_match(req: &HttpRequest, etag: &EntityTag) -> Result<(), StatusCode> {
+    if !req.headers().contains_key(header::IF_MATCH) {
+        return Err(StatusCode::PRECONDITION_REQUIRED);
+    }
+
+    match IfMatch::parse(req) {
+        Ok(IfMatch::Any) => Ok(()),
+        Ok(IfMatch::Items(tags)) if tags.iter().any(|tag| tag.strong_eq(etag)) => Ok(()),
+        _ => Err(StatusCode::PRECONDITION_FAILED),
+    }
+}
+
+/// Whether the client's copy is current, i.e. the response can be
+/// [NOT_MODIFIED](`actix_web::http::StatusCode::NOT_MODIFIED`).
+///
+/// # Arguments
+///
+/// * `etag` - of the response.
+///
+/// * `last_modified` - of the response, ``None`` if not known.
+///
+fn is_not_modified(req: &HttpRequest, etag: &EntityTag, last_modified: Option<OffsetDateTime>) -> bool {
+    if req.headers().contains_key(header::IF_NONE_MATCH) {
+        return match IfNoneMatch::parse(req) {
+            Ok(IfNoneMatch::Any) => true,
+            Ok(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(etag)),
+            Err(_) => false,
+        };
+    }
+
+    match (IfModifiedSince::parse(req), last_modified) {
+        (Ok(IfModifiedSince(since)), Some(last_modified)) => {
+            let since = SystemTime::from(since).duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs() as i64);
+
+            // HTTP dates are whole seconds.
+            last_modified.unix_timestamp() <= since
+        }
+        _ => false,
+    }
+}
+
+/// Adds ``ETag`` and ``Last-Modified`` to a successful ``response``, and replaces it
+/// with [NOT_MODIFIED](`actix_web::http::StatusCode::NOT_MODIFIED`) if the client's copy
+/// is current. Only ``GET`` and ``HEAD`` requests are conditional: other requests,
+/// other responses, and streamed bodies, are returned as they are.
+///
+/// # Arguments
+///
+/// * `req` - the request, whose ``If-None-Match`` and ``If-Modified-Since`` are checked.
+///
+/// * `last_modified` - the latest ``updated_at`` of the rows of the response, ``None``
+///   if not known.
+///
+/// * `response` - the complete response.
+///
+pub fn conditional_response(
+    req: &HttpRequest,
+    last_modified: Option<OffsetDateTime>,
+    response: HttpResponse
+) -> HttpResponse {
+    if response.status() != StatusCode::OK || !matches!(*req.method(), Method::GET | Method::HEAD) {
+        return response;
+    }
+
+    let (response, body) = response.into_parts();
+    let body = match body.try_into_bytes() {
+        Ok(body) => body,
+        Err(body) => return response.set_body(body),
+    };
+
+    let etag = entity_tag(&body);
+    let not_modified = is_not_modified(req, &etag, last_modified);
+
+    let mut response = match not_modified {
+        true => {
+            let mut not_modified = HttpResponse::NotModified().finish();
+            // Headers the 200 response would have had, e.g. a refreshed access token.
+            for (name, value) in response.headers().iter()
+                .filter(|(name, _)| *name != header::CONTENT_TYPE && *name != header::CONTENT_LENGTH) {
+                not_modified.headers_mut().append(name.clone(), value.clone());
+            }
+            not_modified
+        }
+        false => response.set_body(body).map_into_boxed_body(),
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(header::ETAG, ETag(etag).to_string().parse().unwrap());
+    if let Some(last_modified) = last_modified {
+        let date = HttpDate::from(SystemTime::from(last_modified));
+        headers.insert(header::LAST_MODIFIED, LastModified(date).to_string().parse().unwrap());
+    }
+
+    response
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::conditional_get::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::conditional_get::tests::test_entity_tag -- --exact
+///    * cargo test helper::conditional_get::tests::test_conditional_response -- --exact
+///    * cargo test helper::conditional_get::tests::test_conditional_response_modified_since -- --exact
+///    * cargo test helper::conditional_get::tests::test_check_if_match -- --exact
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+    use time::macros::datetime;
+    use super::*;
+
+    fn response() -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type("application/json")
+            .insert_header((header::LINK, "</data/departments>; rel=\"first\""))
+            .body("{\"code\":200}")
+    }
+
+    fn header_value(response: &HttpResponse, name: header::HeaderName) -> Option<String> {
+        response.headers().get(name).map(|value| String::from(value.to_str().unwrap()))
+    }
+
+    #[test]
+    fn test_entity_tag() {
+        let etag = entity_tag(b"{\"code\":200}");
+        assert!(!etag.weak);
+        assert_eq!(etag.tag().len(), 32);
+        assert_eq!(etag, entity_tag(b"{\"code\":200}"));
+        assert_ne!(etag, entity_tag(b"{\"code\":201}"));
+    }
+
+    #[test]
+    fn test_conditional_response() {
+        let etag = entity_tag(b"{\"code\":200}").to_string();
+
+        let req = TestRequest::default().to_http_request();
+        let res = conditional_response(&req, None, response());
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(header_value(&res, header::ETAG), Some(etag.clone()));
+        assert_eq!(header_value(&res, header::LAST_MODIFIED), None);
+        assert_eq!(res.into_body().try_into_bytes().unwrap(), "{\"code\":200}");
+
+        for if_none_match in [etag.clone(), format!("\"other\", W/{}", etag), String::from("*")] {
+            let req = TestRequest::default()
+                .insert_header((header::IF_NONE_MATCH, if_none_match))
+                .to_http_request();
+            let res = conditional_response(&req, None, response());
+            assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+            assert_eq!(header_value(&res, header::ETAG), Some(etag.clone()));
+            assert_eq!(header_value(&res, header::LINK), Some(String::from("</data/departments>; rel=\"first\"")));
+            assert_eq!(header_value(&res, header::CONTENT_TYPE), None);
+        }
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, "\"other\""))
+            .to_http_request();
+        assert_eq!(conditional_response(&req, None, response()).status(), StatusCode::OK);
+
+        // Failures are as they are.
+        let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, "*")).to_http_request();
+        let res = conditional_response(&req, None, HttpResponse::NotFound().body("{\"code\":404}"));
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(header_value(&res, header::ETAG), None);
+
+        let req = TestRequest::post().insert_header((header::IF_NONE_MATCH, "*")).to_http_request();
+        let res = conditional_response(&req, None, response());
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(header_value(&res, header::ETAG), None);
+    }
+
+    #[test]
+    fn test_conditional_response_modified_since() {
+        let last_modified = datetime!(2026-10-18 09:30:15.250 UTC);
+
+        let req = TestRequest::default().to_http_request();
+        let res = conditional_response(&req, Some(last_modified), response());
+        assert_eq!(header_value(&res, header::LAST_MODIFIED), Some(String::from("Sun, 18 Oct 2026 09:30:15 GMT")));
+
+        let since = |date: &str| TestRequest::default()
+            .insert_header((header::IF_MODIFIED_SINCE, date)).to_http_request();
+
+        let res = conditional_response(&since("Sun, 18 Oct 2026 09:30:15 GMT"), Some(last_modified), response());
+        assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(header_value(&res, header::LAST_MODIFIED), Some(String::from("Sun, 18 Oct 2026 09:30:15 GMT")));
+
+        let res = conditional_response(&since("Sun, 18 Oct 2026 09:30:14 GMT"), Some(last_modified), response());
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // Not known: modified.
+        let res = conditional_response(&since("Sun, 18 Oct 2026 09:30:15 GMT"), None, response());
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // If-None-Match first.
+        let req = TestRequest::default()
+            .insert_header((header::IF_MODIFIED_SINCE, "Sun, 18 Oct 2026 09:30:15 GMT"))
+            .insert_header((header::IF_NONE_MATCH, "\"other\""))
+            .to_http_request();
+        assert_eq!(conditional_response(&req, Some(last_modified), response()).status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_check_if_match() {
+        let etag = entity_tag(b"{\"code\":200}");
+
+        let if_match = |value: String| TestRequest::put()
+            .insert_header((header::IF_MATCH, value)).to_http_request();
+
+        assert_eq!(check_if_match(&if_match(etag.to_string()), &etag), Ok(()));
+        assert_eq!(check_if_match(&if_match(format!("\"other\", {}", etag)), &etag), Ok(()));
+        assert_eq!(check_if_match(&if_match(String::from("*")), &etag), Ok(()));
+
+        assert_eq!(check_if_match(&if_match(String::from("\"other\"")), &etag), Err(StatusCode::PRECONDITION_FAILED));
+        // Weak tags never match.
+        assert_eq!(check_if_match(&if_match(format!("W/{}", etag)), &etag), Err(StatusCode::PRECONDITION_FAILED));
+
+        let req = TestRequest::put().to_http_request();
+        assert_eq!(check_if_match(&req, &etag), Err(StatusCode::PRECONDITION_REQUIRED));
+    }
+}
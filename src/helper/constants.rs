@@ -4,6 +4,10 @@
 
 pub static REDIRECT_MESSAGE: &str = "redirect-message";
 pub static ORIGINAL_CONTENT_TYPE: &str = "original-content-type";
+pub static PERSISTENT_LOGIN: &str = "persistent-login";
+pub static MAGIC_LINK_BROWSER: &str = "magic-link-browser";
+pub static TOKEN_BINDING: &str = "token-binding";
+pub static LOGIN_NEXT: &str = "login-next";
 
 pub static TOKEN_TYPE: &str = "bearer";
 pub static BEARER_TOKEN: &str = "Bearer.";
\ No newline at end of file
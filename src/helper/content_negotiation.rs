@@ -0,0 +1,184 @@
+/* Date Created: 18/10/2026. */
+
+//! Content negotiation of resources which have several representations, e.g. the
+//! employee search: JSON, HTML, CSV or XML, chosen by the ``Accept`` request header.
+//!
+//! The same resource is routed under both ``/data`` and ``/ui``. A scope registers its
+//! [`ScopeRepresentation`], which is the representation of requests without a
+//! preference, i.e. no ``Accept`` header or ``*/*``: JSON under ``/data`` and HTML
+//! under ``/ui``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::content_negotiation::tests
+//!
+
+use actix_web::{HttpRequest, HttpResponse};
+use actix_web::http::StatusCode;
+use actix_web::http::header::{Accept, Header, Quality};
+use mime::Mime;
+
+use crate::helper::app_utils::make_api_status_response;
+use crate::helper::csv_utils::CSV_CONTENT_TYPE;
+use crate::helper::messages::NOT_ACCEPTABLE_MSG;
+
+/// The representations of a negotiated resource.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Representation {
+    Json,
+    Html,
+    Csv,
+    Xml,
+}
+
+impl Representation {
+    /// The ``Content-Type`` of responses.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Csv => CSV_CONTENT_TYPE,
+            Self::Xml => "application/xml; charset=utf-8",
+        }
+    }
+
+    /// All representations, in the order wildcards prefer them after the default.
+    pub const ALL: [Representation; 4] = [Self::Json, Self::Html, Self::Csv, Self::Xml];
+
+    /// The ``type/subtype`` media type.
+    fn essence(&self) -> &'static str {
+        self.content_type().split(';').next().unwrap()
+    }
+
+    /// Whether a media range of the ``Accept`` header includes the representation.
+    /// ``application/xhtml+xml`` and ``text/xml`` are also accepted as HTML and XML.
+    fn in_range(&self, mime: &Mime) -> bool {
+        match (mime.type_().as_str(), mime.subtype().as_str()) {
+            ("*", "*") => true,
+            ("application", "xhtml+xml") => *self == Self::Html,
+            ("text", "xml") => *self == Self::Xml,
+            (type_, "*") => self.essence().split('/').next() == Some(type_),
+            _ => self.essence() == mime.essence_str(),
+        }
+    }
+}
+
+/// The base path and default representation of a scope, registered as its app data.
+#[derive(Debug, Clone, Copy)]
+pub struct ScopeRepresentation {
+    /// The scope path, e.g. ``/data``, which links to other pages start with.
+    pub path: &'static str,
+    pub default: Representation,
+}
+
+impl ScopeRepresentation {
+    pub const DATA: ScopeRepresentation = ScopeRepresentation { path: "/data", default: Representation::Json };
+    pub const UI: ScopeRepresentation = ScopeRepresentation { path: "/ui", default: Representation::Html };
+}
+
+/// Chooses the representation of the ``Accept`` header: the first, most preferred,
+/// media range which is supported. Ranges of ``q=0`` are not acceptable.
+///
+/// # Return
+///
+/// * ``default`` - no ``Accept`` header, or one which can't be parsed.
+///
+/// * ``None`` - no acceptable representation is supported.
+///
+pub fn negotiate(req: &HttpRequest, default: Representation) -> Option<Representation> {
+    let accept = match Accept::parse(req) {
+        Ok(accept) if !accept.is_empty() => accept,
+        _ => return Some(default),
+    };
+
+    let (refused, ranges): (Vec<Mime>, Vec<Mime>) = accept.ranked().into_iter()
+        .partition(|mime| accept.iter().any(|item| item.item == *mime && item.quality == Quality::ZERO));
+
+    // The default first, so that e.g. ``*/*`` and ``text/*`` are the default when they can be.
+    let candidates: Vec<Representation> = std::iter::once(default)
+        .chain(Representation::ALL.into_iter().filter(|representation| *representation != default))
+        .filter(|representation| !refused.iter().any(|mime| representation.in_range(mime)))
+        .collect();
+
+    ranges.iter().find_map(|mime| candidates.iter().copied()
+        .find(|representation| representation.in_range(mime)))
+}
+
+/// The ``406`` response of [`negotiate`] finding no representation.
+pub fn make_not_acceptable_response() -> HttpResponse {
+    make_api_status_response(StatusCode::NOT_ACCEPTABLE, NOT_ACCEPTABLE_MSG, None)
+}
+
+/// Escapes text and attribute values of XML, and so of HTML and SVG.
+pub fn xml_escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+        .replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::content_negotiation::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::content_negotiation::tests::test_negotiate -- --exact
+///    * cargo test helper::content_negotiation::tests::test_negotiate_wildcards -- --exact
+///    * cargo test helper::content_negotiation::tests::test_xml_escape -- --exact
+#[cfg(test)]
+mod tests {
+    use actix_web::http::header;
+    use actix_web::test::TestRequest;
+    use super::*;
+
+    fn negotiate_accept(accept: &str, default: Representation) -> Option<Representation> {
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, accept))
+            .to_http_request();
+
+        negotiate(&req, default)
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let req = TestRequest::default().to_http_request();
+        assert_eq!(negotiate(&req, Representation::Html), Some(Representation::Html));
+
+        assert_eq!(negotiate_accept("application/json", Representation::Html), Some(Representation::Json));
+        assert_eq!(negotiate_accept("text/csv", Representation::Json), Some(Representation::Csv));
+        assert_eq!(negotiate_accept("text/xml", Representation::Json), Some(Representation::Xml));
+        assert_eq!(negotiate_accept("application/xml;q=0.5, text/csv", Representation::Json), Some(Representation::Csv));
+        assert_eq!(negotiate_accept("image/png, application/xml", Representation::Json), Some(Representation::Xml));
+
+        // A browser.
+        assert_eq!(negotiate_accept("text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8",
+            Representation::Json), Some(Representation::Html));
+
+        assert_eq!(negotiate_accept("image/png", Representation::Json), None);
+        assert_eq!(negotiate_accept("application/pdf, text/plain", Representation::Html), None);
+        assert_eq!(negotiate_accept("application/json;q=0", Representation::Json), None);
+    }
+
+    #[test]
+    fn test_negotiate_wildcards() {
+        assert_eq!(negotiate_accept("*/*", Representation::Json), Some(Representation::Json));
+        assert_eq!(negotiate_accept("*/*", Representation::Html), Some(Representation::Html));
+
+        assert_eq!(negotiate_accept("text/*", Representation::Json), Some(Representation::Html));
+        assert_eq!(negotiate_accept("text/*", Representation::Xml), Some(Representation::Html));
+        assert_eq!(negotiate_accept("text/*", Representation::Csv), Some(Representation::Csv));
+        assert_eq!(negotiate_accept("application/*", Representation::Html), Some(Representation::Json));
+        assert_eq!(negotiate_accept("application/*", Representation::Xml), Some(Representation::Xml));
+
+        // The more specific range comes first.
+        assert_eq!(negotiate_accept("*/*, text/csv", Representation::Json), Some(Representation::Csv));
+        assert_eq!(negotiate_accept("text/html;q=0, */*", Representation::Html), Some(Representation::Json));
+        assert_eq!(negotiate_accept("text/*;q=0, */*", Representation::Html), Some(Representation::Json));
+        assert_eq!(negotiate_accept("*/*;q=0", Representation::Html), None);
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("Research & \"Development\" <R'D>"),
+            "Research &amp; &quot;Development&quot; &lt;R&apos;D&gt;");
+    }
+}
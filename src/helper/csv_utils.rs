@@ -0,0 +1,138 @@
+/* Date Created: 18/10/2026. */
+
+//! CSV responses for report routes, which return either JSON or CSV.
+//!
+//! Rows are serialised by their [`serde::Serialize`] implementation: field names are
+//! the header record, so rows must be flat structs. Dates are as in JSON, e.g.
+//! ``26/06/1986``, and amounts are exact decimals.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::csv_utils::tests
+//!
+
+use std::str::FromStr;
+
+use actix_web::HttpResponse;
+use actix_web::http::{header, StatusCode};
+use serde::Serialize;
+
+use crate::helper::app_utils::make_data_response;
+use crate::helper::messages::REPORT_FORMAT_INVALID_MSG;
+
+/// The content type of CSV responses.
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+/// The representations of a report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            _ => Err(String::from(REPORT_FORMAT_INVALID_MSG)),
+        }
+    }
+}
+
+impl ReportFormat {
+    /// Parses the ``format`` query parameter, [`ReportFormat::Json`] when there is none.
+    pub fn from_query(format: Option<&str>) -> Result<Self, String> {
+        format.map_or(Ok(Self::Json), str::parse)
+    }
+}
+
+/// Serialises ``rows`` as CSV, with a header record. Empty if there are no rows.
+pub fn to_csv<T: Serialize>(rows: &[T]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    for row in rows {
+        writer.serialize(row)?;
+    }
+
+    let bytes = writer.into_inner().map_err(|error| error.into_error())?;
+
+    Ok(String::from_utf8(bytes).expect("CSV of UTF-8 fields is UTF-8"))
+}
+
+/// Creates a CSV attachment response, e.g. ``headcount.csv``.
+pub fn make_csv_response(file_stem: &str, body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(CSV_CONTENT_TYPE)
+        .insert_header((header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}.csv\"", file_stem)))
+        .body(body)
+}
+
+/// Creates the response of a report: a [`crate::models::DataResponse`] JSON whose
+/// ``data`` is ``rows``, or the CSV of ``rows``.
+///
+/// # Panics
+///
+/// If ``rows`` can not be serialised as CSV, i.e. they are not flat structs.
+///
+pub fn make_report_response<T: Serialize>(
+    format: ReportFormat,
+    file_stem: &str,
+    rows: Vec<T>
+) -> HttpResponse {
+    match format {
+        ReportFormat::Json => make_data_response(StatusCode::OK, rows),
+        ReportFormat::Csv => make_csv_response(file_stem,
+            to_csv(&rows).expect("Report rows must be flat structs")),
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::csv_utils::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::csv_utils::tests::test_report_format -- --exact
+///    * cargo test helper::csv_utils::tests::test_to_csv -- --exact
+#[cfg(test)]
+mod tests {
+    use rust_decimal::Decimal;
+    use time::macros::date;
+    use sqlx::types::time::Date;
+    use crate::bh_libs::australian_date::australian_date_format;
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row {
+        name: String,
+        #[serde(with = "australian_date_format")]
+        date: Date,
+        amount: Decimal,
+        count: i64,
+    }
+
+    #[test]
+    fn test_report_format() {
+        assert_eq!(ReportFormat::from_query(None), Ok(ReportFormat::Json));
+        assert_eq!(ReportFormat::from_query(Some(" CSV")), Ok(ReportFormat::Csv));
+        assert_eq!(ReportFormat::from_query(Some("xml")), Err(String::from(REPORT_FORMAT_INVALID_MSG)));
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let rows = vec![
+            Row { name: String::from("Research"), date: date!(1986 - 06 - 26), amount: Decimal::new(6011700, 2), count: 2 },
+            Row { name: String::from("Sales, \"East\""), date: date!(2000 - 01 - 01), amount: Decimal::new(5, 1), count: 0 },
+        ];
+
+        assert_eq!(to_csv(&rows).unwrap(), "name,date,amount,count\n\
+            Research,26/06/1986,60117.00,2\n\
+            \"Sales, \"\"East\"\"\",01/01/2000,0.5,0\n");
+
+        assert_eq!(to_csv::<Row>(&[]).unwrap(), "");
+    }
+}
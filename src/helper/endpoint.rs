@@ -66,19 +66,24 @@ pub fn serialise_api_status(
 /// on subsequent requests to access protected resources. It's the value of 
 /// ``data.access_token``.
 /// 
+/// * `next` - the originally requested page, see [`crate::helper::login_next`]. It's
+///   the value of ``data.next``, which is left out when ``None``.
+/// 
 /// # Return
 /// 
 /// * JSON object as listed above.
 /// 
 pub fn login_success_json_response(
     email: &str, 
-    access_token: &str) -> String {
+    access_token: &str,
+    next: Option<&str>) -> String {
 
     let r = LoginSuccessResponse {
         api_status: ApiStatus::new(http_status_code(StatusCode::OK)),
         data: LoginSuccess { email: String::from(email), 
             access_token: String::from(access_token),
-            token_type: String::from(TOKEN_TYPE)
+            token_type: String::from(TOKEN_TYPE),
+            next: next.map(String::from),
         }
     };
 
@@ -39,6 +39,10 @@ pub struct JWTPayload {
     /// Custom field. The last time this token was used for authentication. It's
     /// seconds since epoch.
     last_active: u64,
+    /// Custom field, optional. Ties this token to the client it was issued to. See
+    /// [`crate::helper::token_binding`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    binding: Option<String>,
 }
 
 /// See [Rust: seconds since epoch -- “1970-01-01 00:00:00 UTC”](https://behai-nguyen.github.io/2023/11/12/rust-12-epoch-time.html) 
@@ -88,6 +92,7 @@ impl JWTPayload {
             iat,
             exp: iat + secs_valid_for,
             last_active: iat,
+            binding: None,
         }
     }
 
@@ -148,6 +153,21 @@ impl JWTPayload {
         self
     }
 
+    /// Sets a [`JWTPayload`] instance binding.
+    /// 
+    /// # Arguments
+    ///
+    /// * `binding` - see [`crate::helper::token_binding`].
+    ///
+    /// # Return 
+    ///
+    /// * [`JWTPayload`] instance with binding set.
+    ///
+    pub fn set_binding(mut self, binding: Option<String>) -> Self {
+        self.binding = binding;
+        self
+    }
+
     /// Gets a [`JWTPayload`] instance email.
     /// 
     /// # Return 
@@ -197,6 +217,16 @@ impl JWTPayload {
     pub fn last_active(&self) -> u64 {
         self.last_active
     }
+
+    /// Gets a [`JWTPayload`] instance binding.
+    /// 
+    /// # Return 
+    ///
+    /// * [`JWTPayload`] instance binding, ``None`` for unbound tokens.
+    ///
+    pub fn binding(&self) -> Option<String> {
+        self.binding.clone()
+    }
 }
 
 /// Create a [jsonwebtoken](https://docs.rs/jsonwebtoken/latest/jsonwebtoken/index.html) token.
@@ -368,6 +398,7 @@ pub fn decode_bearer_token(
 ///    * cargo test helper::jwt_utils::tests::test_decode_bearer_token_valid -- --exact
 ///    * cargo test helper::jwt_utils::tests::test_decode_token_expired -- --exact
 ///    * cargo test helper::jwt_utils::tests::test_decode_token_invalid -- --exact
+///    * cargo test helper::jwt_utils::tests::test_binding_claim -- --exact
 #[cfg(test)]
 mod tests {
     use std;
@@ -569,4 +600,22 @@ mod tests {
         assert_eq!(api_status.get_code(), StatusCode::UNAUTHORIZED.as_u16());
         assert_eq!(api_status.get_message().unwrap(), TOKEN_INVALID_MSG);
     }
+
+    #[test]
+    fn test_binding_claim() {
+        dotenv().ok();
+        let config = Config::init();
+
+        // Unbound tokens don't have the claim at all.
+        let jwt_payload = JWTPayload::new("behai_nguyen@hotmail.com", 45);
+        assert!(!serde_json::to_string(&jwt_payload).unwrap().contains("binding"));
+
+        let token = make_token_from_payload(&jwt_payload, config.jwt_secret_key.as_ref());
+        assert_eq!(decode_token(&token, config.jwt_secret_key.as_ref(), None).unwrap().binding(), None);
+
+        let jwt_payload = jwt_payload.set_binding(Some(String::from("abcd")));
+        let token = make_token_from_payload(&jwt_payload, config.jwt_secret_key.as_ref());
+        assert_eq!(decode_token(&token, config.jwt_secret_key.as_ref(), None).unwrap().binding(),
+            Some(String::from("abcd")));
+    }
 }
@@ -0,0 +1,159 @@
+/* Date Created: 18/10/2026. */
+
+//! Returns users to the page they originally requested after they log in.
+//!
+//! When [`crate::auth_middleware::CheckLogin`] redirects a not logged in ``GET``
+//! request to the login page, it remembers the requested path and query in the
+//! server-side only cookie [`crate::helper::constants::LOGIN_NEXT`]. Clients can also
+//! submit the login with a ``next`` field, e.g. the login page form carries it over
+//! from ``/ui/login?next=...``.
+//!
+//! Since the value ends up in a ``Location`` header, it is only accepted if it is a
+//! same-origin path, see [`validate_next`]. Anything else is ignored, and the login
+//! proceeds as if there was no ``next``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::login_next::tests
+//!
+
+use actix_web::HttpRequest;
+
+use crate::helper::constants::LOGIN_NEXT;
+
+/// Longest accepted ``next`` value.
+const MAX_NEXT_LEN: usize = 2048;
+
+/// Routes which make no sense to return to after logging in.
+const EXCLUDED_ROUTES: [&str; 4] = ["/ui/login", "/api/login", "/api/logout", "/api/magic-link"];
+
+/// Returns ``value`` if it is safe to redirect to after logging in.
+///
+/// A safe ``next`` is an absolute path on this server, with an optional query: it
+/// starts with a single ``/``. Rejected are, among others:
+///
+/// * Absolute and scheme relative URLs, e.g. ``https://evil.com``, ``//evil.com``,
+///   and ``/\evil.com``, which browsers treat as ``//evil.com``.
+///
+/// * Values with control characters or white spaces, e.g. header injection attempts.
+///
+/// * Values with characters which can't be in a cookie value: ``"``, ``,`` and ``;``.
+///
+/// * The login, logout and passwordless sign-in routes.
+///
+/// # Arguments
+///
+/// * `value` - the ``next`` field or cookie value.
+///
+/// # Return
+///
+/// * ``Some(path)`` if safe, ``None`` otherwise.
+///
+pub fn validate_next(value: &str) -> Option<String> {
+    if value.is_empty() || value.len() > MAX_NEXT_LEN {
+        return None;
+    }
+
+    if !value.starts_with('/') || value.starts_with("//") || value.contains('\\') {
+        return None;
+    }
+
+    if value.chars().any(|c| c.is_control() || c.is_whitespace() || matches!(c, '"' | ',' | ';')) {
+        return None;
+    }
+
+    let path = value.split(['?', '#']).next().unwrap_or("");
+    if EXCLUDED_ROUTES.iter().any(|route| path == *route || path.starts_with(&format!("{}/", route))) {
+        return None;
+    }
+
+    Some(String::from(value))
+}
+
+/// Returns where to go after a successful login, if anywhere.
+///
+/// # Arguments
+///
+/// * `request` - the login request, which might have the
+///   [`crate::helper::constants::LOGIN_NEXT`] cookie.
+///
+/// * `submitted` - the submitted ``next`` field, if any. It takes precedence over the
+///   cookie.
+///
+/// # Return
+///
+/// * The first of ``submitted`` and the cookie value which is safe, see [`validate_next`].
+///
+pub fn login_next(request: &HttpRequest, submitted: Option<&str>) -> Option<String> {
+    submitted.and_then(validate_next)
+        .or_else(|| request.cookie(LOGIN_NEXT).and_then(|cookie| validate_next(cookie.value())))
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::login_next::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::login_next::tests::test_validate_next_accepted -- --exact
+///    * cargo test helper::login_next::tests::test_validate_next_rejected -- --exact
+///    * cargo test helper::login_next::tests::test_login_next -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{cookie::Cookie, test::TestRequest};
+
+    #[test]
+    fn test_validate_next_accepted() {
+        for value in [
+            "/",
+            "/ui/home",
+            "/data/employees/%chi/%ak",
+            "/ui/employees?last_name=%25chi&first_name=%25ak",
+            "/ui/login-help",
+        ] {
+            assert_eq!(validate_next(value), Some(String::from(value)), "{} should be accepted", value);
+        }
+    }
+
+    #[test]
+    fn test_validate_next_rejected() {
+        let too_long = format!("/{}", "a".repeat(MAX_NEXT_LEN));
+
+        for value in [
+            "",
+            "ui/home",
+            "https://evil.com/ui/home",
+            "//evil.com",
+            "/\\evil.com",
+            "/ui/home\r\nSet-Cookie: id=1",
+            "/ui/ho me",
+            "/ui/home;id=1",
+            "/ui/login",
+            "/ui/login?next=/ui/home",
+            "/api/logout",
+            "/api/magic-link/0123",
+            too_long.as_str(),
+        ] {
+            assert_eq!(validate_next(value), None, "{} should be rejected", value);
+        }
+    }
+
+    #[test]
+    fn test_login_next() {
+        let request = TestRequest::default()
+            .cookie(Cookie::new(LOGIN_NEXT, "/ui/home"))
+            .to_http_request();
+
+        assert_eq!(login_next(&request, Some("/data/employees/%chi/%ak")), Some(String::from("/data/employees/%chi/%ak")));
+        assert_eq!(login_next(&request, Some("//evil.com")), Some(String::from("/ui/home")));
+        assert_eq!(login_next(&request, None), Some(String::from("/ui/home")));
+
+        let request = TestRequest::default()
+            .cookie(Cookie::new(LOGIN_NEXT, "https://evil.com"))
+            .to_http_request();
+        assert_eq!(login_next(&request, None), None);
+
+        assert_eq!(login_next(&TestRequest::default().to_http_request(), None), None);
+    }
+}
@@ -7,4 +7,78 @@ pub static UNAUTHORISED_ACCESS_MSG: &str = "Please log in first.";
 pub static TOKEN_INVALID_MSG: &str = "Invalid token.";
 pub static TOKEN_EXPIRED_MSG: &str = "Token has expired.";
 pub static TOKEN_OTHER_ERR_MSG: &str = "Token is in error.";
-pub static TOKEN_STR_JWT_MSG: &str = "JWT extension should be a string.";
\ No newline at end of file
+pub static TOKEN_STR_JWT_MSG: &str = "JWT extension should be a string.";
+pub static TOKEN_BINDING_MISMATCH_MSG: &str = "Token is not valid for this client.";
+pub static BREACHED_PASSWORD_MSG: &str = "This password has appeared in a data breach and can't be used. Please choose a different password.";
+pub static BREACHED_PASSWORD_CHECK_FAILURE_MSG: &str = "Unable to check the password against known data breaches. Please try again later.";
+pub static CURRENT_PASSWORD_MISMATCH_MSG: &str = "Current password is incorrect.";
+pub static PASSWORD_CHANGED_MSG: &str = "Password has been changed.";
+pub static PASSWORD_MANAGED_EXTERNALLY_MSG: &str = "Passwords are managed by the directory service, please change it there.";
+pub static MAGIC_LINK_SENT_MSG: &str = "If the email is registered, a sign-in link has been sent to it. Please open it in this browser.";
+pub static MAGIC_LINK_INVALID_MSG: &str = "This sign-in link is invalid or has expired. Please request a new one.";
+pub static MAGIC_LINK_MAIL_SUBJECT: &str = "Your sign-in link";
+pub static SESSION_LIMIT_REACHED_MSG: &str = "You have reached the maximum number of active sessions. Please log out of another session first.";
+pub static SESSION_EVICTED_MSG: &str = "This session was ended by a newer login, the maximum number of active sessions was reached. Please log in again.";
+pub static SESSION_REVOKED_MSG: &str = "This session has ended. Please log in again.";
+pub static ACCOUNT_DISABLED_MSG: &str = "This account has been disabled.";
+pub static ACCOUNT_LOCKED_MSG: &str = "This account is locked. Please contact your administrator.";
+pub static PASSWORD_CHANGE_REQUIRED_MSG: &str = "Please change your password before continuing.";
+pub static EMPLOYEE_NOT_FOUND_MSG: &str = "Employee not found.";
+pub static EMPLOYEE_NO_EXISTS_MSG: &str = "An employee with this employee number already exists.";
+pub static EMPLOYEE_EMAIL_EXISTS_MSG: &str = "An employee with this email already exists.";
+pub static EMPLOYEE_NO_MISMATCH_MSG: &str = "The employee number in the body does not match the one in the URL.";
+pub static EMPLOYEE_DELETED_MSG: &str = "Employee has been deleted.";
+pub static EMPLOYEE_IF_MATCH_REQUIRED_MSG: &str = "If-Match must be the ETag of the employee as last read.";
+pub static EMPLOYEE_RESTORED_MSG: &str = "Employee has been restored.";
+pub static DELETED_EMPLOYEE_NOT_FOUND_MSG: &str = "Deleted employee not found.";
+pub static EMPLOYEE_MODIFIED_MSG: &str = "The employee has been changed since it was read: data is the current employee.";
+pub static EMPLOYEE_NO_INVALID_MSG: &str = "emp_no must be a positive number.";
+pub static EMPLOYEE_EMAIL_INVALID_MSG: &str = "email must be a valid email address of at most 255 characters.";
+pub static EMPLOYEE_FIRST_NAME_INVALID_MSG: &str = "first_name must be 1 to 14 characters.";
+pub static EMPLOYEE_LAST_NAME_INVALID_MSG: &str = "last_name must be 1 to 16 characters.";
+pub static EMPLOYEE_GENDER_INVALID_MSG: &str = "gender must be M or F.";
+pub static EMPLOYEE_DATES_INVALID_MSG: &str = "birth_date must be before hire_date.";
+
+pub static PAGE_LIMIT_INVALID_MSG: &str = "limit must be between 1 and";
+pub static PAGE_CURSOR_INVALID_MSG: &str = "Invalid page cursor.";
+pub static PAGE_CURSOR_OFFSET_MSG: &str = "Use either offset or cursor, not both.";
+
+pub static SEARCH_SORT_INVALID_MSG: &str = "Unknown sort field:";
+pub static SEARCH_SORT_DUPLICATE_MSG: &str = "Duplicate sort field:";
+pub static SEARCH_RANGE_INVALID_MSG: &str = "The _from value must not be after the _to value:";
+pub static SEARCH_EMAIL_INVALID_MSG: &str = "email must be at most 255 characters.";
+pub static SEARCH_TITLE_INVALID_MSG: &str = "title must be at most 50 characters.";
+
+pub static DEPARTMENT_NOT_FOUND_MSG: &str = "Department not found.";
+pub static ORG_CHART_FORMAT_INVALID_MSG: &str = "format must be json, dot or svg.";
+
+pub static HR_PERMISSION_REQUIRED_MSG: &str = "This requires the HR permission.";
+pub static ADMIN_PERMISSION_REQUIRED_MSG: &str = "This requires the admin permission.";
+pub static SALARY_PERCENTILE_INVALID_MSG: &str = "percentiles must be comma separated numbers between 0 and 100:";
+
+pub static REPORT_FORMAT_INVALID_MSG: &str = "format must be json or csv.";
+pub static ANALYTICS_YEARS_INVALID_MSG: &str = "from_year must not be after to_year, and they must be less than 100 years apart:";
+
+pub static EXPORT_COLUMN_INVALID_MSG: &str = "Unknown export column:";
+pub static EXPORT_COLUMN_DUPLICATE_MSG: &str = "Duplicate export column:";
+
+pub static IMPORT_MODE_INVALID_MSG: &str = "mode must be dry-run or commit.";
+pub static IMPORT_CSV_INVALID_MSG: &str = "Invalid CSV:";
+pub static IMPORT_CSV_EMPTY_MSG: &str = "The CSV has no employee records.";
+pub static IMPORT_CSV_TOO_LARGE_MSG: &str = "The CSV must have at most this many employee records:";
+pub static IMPORT_COLUMN_INVALID_MSG: &str = "Unknown import column:";
+pub static IMPORT_COLUMN_DUPLICATE_MSG: &str = "Duplicate import column:";
+pub static IMPORT_COLUMN_MISSING_MSG: &str = "Missing import column:";
+pub static IMPORT_DATE_INVALID_MSG: &str = "Not a dd/mm/yyyy date:";
+pub static IMPORT_EMP_NO_REPEATED_MSG: &str = "emp_no repeats the one on row:";
+pub static IMPORT_EMAIL_REPEATED_MSG: &str = "email repeats the one on row:";
+
+pub static SEARCH_TEXT_REQUIRED_MSG: &str = "q must have at least one word to search for.";
+pub static SEARCH_INDEX_FAILURE_MSG: &str = "The search index could not be read.";
+
+pub static NOT_ACCEPTABLE_MSG: &str = "Acceptable types are application/json, text/html, text/csv and application/xml.";
+
+pub static FIELD_UNKNOWN_MSG: &str = "Unknown field:";
+pub static FIELD_DUPLICATE_MSG: &str = "Duplicate field:";
+pub static INCLUDE_UNKNOWN_MSG: &str = "Unknown include, it must be departments, titles or salaries:";
+pub static INCLUDE_DUPLICATE_MSG: &str = "Duplicate include:";
@@ -0,0 +1,238 @@
+/* Date Created: 18/10/2026. */
+
+//! Exports a management tree, see [`crate::models::org_chart`], as Graphviz DOT or SVG.
+//!
+//! DOT is for Graphviz: e.g. ``dot -Tpdf org-chart.dot -o org-chart.pdf``. The SVG is
+//! made here without Graphviz, as an indented tree: one node per row, each child
+//! indented under its parent. It prints on pages of any width, no matter how many
+//! employees a manager has.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::org_chart_export::tests
+//!
+
+use std::str::FromStr;
+
+use crate::models::org_chart::{OrgNode, OrgNodeKind};
+use crate::helper::messages::ORG_CHART_FORMAT_INVALID_MSG;
+use crate::helper::content_negotiation::xml_escape;
+
+/// The representations of a management tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrgChartFormat {
+    Json,
+    Dot,
+    Svg,
+}
+
+impl FromStr for OrgChartFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "dot" => Ok(Self::Dot),
+            "svg" => Ok(Self::Svg),
+            _ => Err(String::from(ORG_CHART_FORMAT_INVALID_MSG)),
+        }
+    }
+}
+
+impl OrgChartFormat {
+    /// The response content type.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Dot => "text/vnd.graphviz; charset=utf-8",
+            Self::Svg => "image/svg+xml; charset=utf-8",
+        }
+    }
+
+    /// The file name extension.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Dot => "dot",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+/// The text of a node: name, then title or number of employees.
+fn node_label(node: &OrgNode) -> String {
+    match (&node.title, node.employees) {
+        (Some(title), _) => format!("{}, {}", node.name, title),
+        (None, Some(employees)) => format!("{} ({} employees)", node.name, employees),
+        (None, None) => node.name.clone(),
+    }
+}
+
+/// Unique DOT node id, e.g. ``department:d005``. A manager is also an employee, so
+/// the kind is part of the id.
+fn dot_id(node: &OrgNode) -> String {
+    format!("{}:{}", node.kind.as_str(), node.id)
+}
+
+fn dot_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn push_dot_node(dot: &mut String, node: &OrgNode) {
+    let style = match node.kind {
+        OrgNodeKind::Company | OrgNodeKind::Department => ", style=filled, fillcolor=\"#dde8f5\"",
+        OrgNodeKind::Manager => ", style=filled, fillcolor=\"#f5ecd5\"",
+        OrgNodeKind::Employee => "",
+    };
+
+    dot.push_str(&format!("    \"{}\" [label=\"{}\"{}];\n",
+        dot_escape(&dot_id(node)), dot_escape(&node_label(node)), style));
+
+    for child in &node.children {
+        push_dot_node(dot, child);
+        dot.push_str(&format!("    \"{}\" -> \"{}\";\n",
+            dot_escape(&dot_id(node)), dot_escape(&dot_id(child))));
+    }
+}
+
+/// Makes a Graphviz DOT directed graph, top to bottom.
+pub fn to_dot(root: &OrgNode) -> String {
+    let mut dot = String::from("digraph org_chart {\n    rankdir=TB;\n    node [shape=box, fontname=\"sans-serif\"];\n");
+    push_dot_node(&mut dot, root);
+    dot.push_str("}\n");
+
+    dot
+}
+
+const SVG_ROW_HEIGHT: usize = 30;
+const SVG_BOX_HEIGHT: usize = 22;
+const SVG_INDENT: usize = 28;
+const SVG_MARGIN: usize = 10;
+/// Approximate width of a character of the 12px sans-serif font.
+const SVG_CHAR_WIDTH: usize = 7;
+
+/// A laid out node: its row, depth, and the row of its parent.
+struct SvgRow<'a> {
+    node: &'a OrgNode,
+    depth: usize,
+    parent_row: Option<usize>,
+}
+
+fn layout<'a>(node: &'a OrgNode, depth: usize, parent_row: Option<usize>, rows: &mut Vec<SvgRow<'a>>) {
+    let row = rows.len();
+    rows.push(SvgRow { node, depth, parent_row });
+
+    for child in &node.children {
+        layout(child, depth + 1, Some(row), rows);
+    }
+}
+
+/// Makes an SVG image of the tree, see the module documentation.
+pub fn to_svg(root: &OrgNode) -> String {
+    let mut rows: Vec<SvgRow> = Vec::new();
+    layout(root, 0, None, &mut rows);
+
+    let x = |depth: usize| SVG_MARGIN + depth * SVG_INDENT;
+    let y = |row: usize| SVG_MARGIN + row * SVG_ROW_HEIGHT;
+
+    let mut width = 0;
+    let mut body = String::new();
+
+    for (row, svg_row) in rows.iter().enumerate() {
+        let label = node_label(svg_row.node);
+        let box_width = label.chars().count() * SVG_CHAR_WIDTH + 16;
+        let (left, top) = (x(svg_row.depth), y(row));
+        width = width.max(left + box_width);
+
+        // Elbow connector: down from the parent, then right to this node.
+        if let Some(parent_row) = svg_row.parent_row {
+            let line_x = x(svg_row.depth - 1) + SVG_INDENT / 2;
+            let middle = top + SVG_BOX_HEIGHT / 2;
+            body.push_str(&format!("  <path d=\"M{} {} V{} H{}\" fill=\"none\" stroke=\"#888888\"/>\n",
+                line_x, y(parent_row) + SVG_BOX_HEIGHT, middle, left));
+        }
+
+        let fill = match svg_row.node.kind {
+            OrgNodeKind::Company | OrgNodeKind::Department => "#dde8f5",
+            OrgNodeKind::Manager => "#f5ecd5",
+            OrgNodeKind::Employee => "#ffffff",
+        };
+
+        body.push_str(&format!("  <g class=\"{}\">\n    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"3\" fill=\"{}\" stroke=\"#444444\"/>\n",
+            svg_row.node.kind.as_str(), left, top, box_width, SVG_BOX_HEIGHT, fill));
+        body.push_str(&format!("    <text x=\"{}\" y=\"{}\">{}</text>\n  </g>\n",
+            left + 8, top + 15, xml_escape(&label)));
+    }
+
+    format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+        font-family=\"sans-serif\" font-size=\"12\">\n{}</svg>\n",
+        width + SVG_MARGIN, y(rows.len()), body)
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::org_chart_export::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::org_chart_export::tests::test_format_from_str -- --exact
+///    * cargo test helper::org_chart_export::tests::test_to_dot -- --exact
+///    * cargo test helper::org_chart_export::tests::test_to_svg -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(kind: OrgNodeKind, id: &str, name: &str, title: Option<&str>, children: Vec<OrgNode>) -> OrgNode {
+        OrgNode {
+            kind,
+            id: String::from(id),
+            name: String::from(name),
+            title: title.map(String::from),
+            employees: if title.is_none() { Some(2) } else { None },
+            children,
+        }
+    }
+
+    fn department() -> OrgNode {
+        node(OrgNodeKind::Department, "d005", "R&D \"Lab\"", None, vec![
+            node(OrgNodeKind::Manager, "110567", "Leon DasSarma", Some("Manager"), vec![
+                node(OrgNodeKind::Employee, "10001", "Georgi Facello", Some("Senior <Engineer>"), Vec::new()),
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(" SVG ".parse::<OrgChartFormat>(), Ok(OrgChartFormat::Svg));
+        assert_eq!("dot".parse::<OrgChartFormat>(), Ok(OrgChartFormat::Dot));
+        assert_eq!("json".parse::<OrgChartFormat>(), Ok(OrgChartFormat::Json));
+        assert_eq!("png".parse::<OrgChartFormat>(), Err(String::from(ORG_CHART_FORMAT_INVALID_MSG)));
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&department());
+
+        assert!(dot.starts_with("digraph org_chart {\n"));
+        assert!(dot.contains("    \"department:d005\" [label=\"R&D \\\"Lab\\\" (2 employees)\", style=filled"));
+        assert!(dot.contains("    \"manager:110567\" [label=\"Leon DasSarma, Manager\""));
+        assert!(dot.contains("    \"department:d005\" -> \"manager:110567\";\n"));
+        assert!(dot.contains("    \"manager:110567\" -> \"employee:10001\";\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let svg = to_svg(&department());
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("height=\"100\""));
+        assert!(svg.contains(">R&amp;D &quot;Lab&quot; (2 employees)</text>"));
+        assert!(svg.contains(">Georgi Facello, Senior &lt;Engineer&gt;</text>"));
+        assert_eq!(svg.matches("<rect ").count(), 3);
+        assert_eq!(svg.matches("<path ").count(), 2);
+        // The employee row is indented twice, under the manager.
+        assert!(svg.contains("<rect x=\"66\" y=\"70\""));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
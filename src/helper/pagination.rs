@@ -0,0 +1,415 @@
+/* Date Created: 18/10/2026. */
+
+//! Pagination of lists: limit/offset, and keyset (cursor) pagination.
+//!
+//! Lists are in a total order, given by a row key: the values of the sort columns,
+//! which end with a unique column, e.g. ``employees.emp_no``. A page is requested with,
+//! all optional:
+//!
+//! * ``limit`` - the maximum number of rows, defaults to ``PAGE_LIMIT_DEFAULT``, at most
+//!   ``PAGE_LIMIT_MAX``.
+//!
+//! * ``offset`` - the number of rows to skip.
+//!
+//! * ``cursor`` - an opaque value from a previous page's ``next_cursor`` or
+//!   ``prev_cursor``: the rows right after, or right before, that page. Unlike
+//!   ``offset``, it does not skip rows in the database, and does not shift when rows
+//!   are added or deleted.
+//!
+//! The response carries a [`PageInfo`], and an RFC 8288 ``Link`` header with the
+//! ``next``, ``prev`` and ``first`` pages, see [`link_header`].
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::pagination::tests
+//!
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::helper::messages::{
+    PAGE_LIMIT_INVALID_MSG,
+    PAGE_CURSOR_INVALID_MSG,
+    PAGE_CURSOR_OFFSET_MSG
+};
+
+/// Submitted page parameters, in a query string, a form, or a JSON body.
+///
+/// Query strings and forms have only strings: ``limit`` and ``offset`` accept both
+/// numbers and strings, also when flattened into another struct.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct PageParams {
+    #[serde(default, deserialize_with = "number_or_string")]
+    pub limit: Option<u32>,
+    #[serde(default, deserialize_with = "number_or_string")]
+    pub offset: Option<u64>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrString {
+    Number(u64),
+    String(String),
+}
+
+/// Deserialises an optional number which might be a string, e.g. from a query string.
+pub fn number_or_string<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: TryFrom<u64>,
+{
+    let value = match Option::<NumberOrString>::deserialize(deserializer)? {
+        None => return Ok(None),
+        Some(NumberOrString::String(value)) if value.trim().is_empty() => return Ok(None),
+        Some(NumberOrString::Number(value)) => value,
+        Some(NumberOrString::String(value)) => value.trim().parse::<u64>()
+            .map_err(|_| serde::de::Error::custom(format!("invalid number {}", value)))?,
+    };
+
+    T::try_from(value).map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("number {} is out of range", value)))
+}
+
+/// The key of a row: the values of the sort columns, in sort order. Numbers and strings
+/// only, e.g. dates are ``yyyy-mm-dd`` strings.
+pub type RowKey = Vec<Value>;
+
+/// A resolved page request.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Page {
+    /// ``limit`` rows after skipping ``offset`` rows.
+    Offset { limit: u32, offset: u64 },
+    /// ``limit`` rows which come after the row whose key is ``key``.
+    After { limit: u32, key: RowKey },
+    /// ``limit`` rows which come before the row whose key is ``key``, the closest ones.
+    Before { limit: u32, key: RowKey },
+}
+
+impl PageParams {
+    /// Validates the parameters, and resolves them into a [`Page`].
+    ///
+    /// # Arguments
+    ///
+    /// * `default_limit` - ``limit`` when not submitted.
+    ///
+    /// * `max_limit` - the largest allowed ``limit``.
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - the problem, as a message for the client.
+    ///
+    /// The list should check that a cursor's key matches its sort columns.
+    ///
+    pub fn resolve(&self, default_limit: u32, max_limit: u32) -> Result<Page, String> {
+        let limit = self.limit.unwrap_or(default_limit);
+
+        if limit == 0 || limit > max_limit {
+            return Err(format!("{} {}", PAGE_LIMIT_INVALID_MSG, max_limit));
+        }
+
+        let cursor = self.cursor.as_deref().map(str::trim).filter(|cursor| !cursor.is_empty());
+
+        match (cursor, self.offset) {
+            (Some(_), Some(_)) => Err(String::from(PAGE_CURSOR_OFFSET_MSG)),
+            (Some(cursor), None) => decode_cursor(cursor, limit)
+                .ok_or_else(|| String::from(PAGE_CURSOR_INVALID_MSG)),
+            (None, offset) => Ok(Page::Offset { limit, offset: offset.unwrap_or(0) }),
+        }
+    }
+}
+
+impl Page {
+    pub fn limit(&self) -> u32 {
+        match *self {
+            Page::Offset { limit, .. } | Page::After { limit, .. } | Page::Before { limit, .. } => limit,
+        }
+    }
+
+    /// The cursor's row key, ``None`` for [`Page::Offset`].
+    pub fn key(&self) -> Option<&RowKey> {
+        match self {
+            Page::Offset { .. } => None,
+            Page::After { key, .. } | Page::Before { key, .. } => Some(key),
+        }
+    }
+
+    /// Makes the [`PageInfo`] of a retrieved page.
+    ///
+    /// # Arguments
+    ///
+    /// * `total` - the number of rows of the whole list.
+    ///
+    /// * `first_key`, `last_key` - keys of the first and last rows of the page, in list
+    ///   order. ``None`` for an empty page.
+    ///
+    /// * `has_more` - whether there are more rows in the direction the page was read:
+    ///   backward for [`Page::Before`], forward otherwise. Read one row more than
+    ///   ``limit`` to know.
+    ///
+    pub fn info(
+        &self,
+        total: u64,
+        first_key: Option<RowKey>,
+        last_key: Option<RowKey>,
+        has_more: bool
+    ) -> PageInfo {
+        let after = |key: Option<RowKey>| key.map(|key| encode_cursor("a", key));
+        let before = |key: Option<RowKey>| key.map(|key| encode_cursor("b", key));
+
+        let (offset, next_cursor, prev_cursor) = match *self {
+            Page::Offset { offset, .. } => (
+                Some(offset),
+                if has_more { after(last_key) } else { None },
+                if offset > 0 { before(first_key) } else { None },
+            ),
+            // The cursor was made from a row of the previous page.
+            Page::After { .. } => (
+                None,
+                if has_more { after(last_key) } else { None },
+                before(first_key),
+            ),
+            // The cursor was made from a row of the next page.
+            Page::Before { .. } => (
+                None,
+                after(last_key),
+                if has_more { before(first_key) } else { None },
+            ),
+        };
+
+        PageInfo { total, limit: self.limit(), offset, next_cursor, prev_cursor }
+    }
+}
+
+/// Describes a retrieved page, included in responses.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PageInfo {
+    /// The number of rows of the whole list.
+    pub total: u64,
+    pub limit: u32,
+    /// Only present for limit/offset requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// ``cursor`` of the next page, ``null`` on the last page.
+    pub next_cursor: Option<String>,
+    /// ``cursor`` of the previous page, ``null`` on the first page.
+    pub prev_cursor: Option<String>,
+}
+
+/// Cursors are the hex of a JSON array: the direction, ``a`` for after or ``b`` for
+/// before, followed by the row key. Opaque to clients, which should not make their own.
+fn encode_cursor(direction: &str, key: RowKey) -> String {
+    let mut cursor = vec![Value::from(direction)];
+    cursor.extend(key);
+
+    hex::encode(Value::Array(cursor).to_string())
+}
+
+fn decode_cursor(cursor: &str, limit: u32) -> Option<Page> {
+    let mut cursor = match serde_json::from_slice::<Value>(&hex::decode(cursor).ok()?).ok()? {
+        Value::Array(cursor) => cursor,
+        _ => return None,
+    };
+
+    if cursor.len() < 2 {
+        return None;
+    }
+
+    let key: RowKey = cursor.split_off(1);
+    if !key.iter().all(|value| value.is_i64() || value.is_string()) {
+        return None;
+    }
+
+    match cursor[0].as_str()? {
+        "a" => Some(Page::After { limit, key }),
+        "b" => Some(Page::Before { limit, key }),
+        _ => None,
+    }
+}
+
+/// Makes the RFC 8288 ``Link`` header value of a page.
+///
+/// # Arguments
+///
+/// * `path` - the path of the list, e.g. ``/data/employees``. The links are relative
+///   to the application.
+///
+/// * `query` - the list's own URL encoded query string, e.g. search criteria.
+///
+/// * `info` - the retrieved page.
+///
+/// # Return
+///
+/// * E.g. ``</data/employees?last_name=%25chi&limit=50&cursor=5b2261222c31303030345d>; rel="next",
+///   </data/employees?last_name=%25chi&limit=50>; rel="first"``.
+///
+pub fn link_header(path: &str, query: &str, info: &PageInfo) -> String {
+    let mut links: Vec<String> = Vec::new();
+
+    if let Some(url) = page_url(path, query, info, PageLink::Next) {
+        links.push(format!("<{}>; rel=\"next\"", url));
+    }
+    if let Some(url) = page_url(path, query, info, PageLink::Prev) {
+        links.push(format!("<{}>; rel=\"prev\"", url));
+    }
+    if let Some(url) = page_url(path, query, info, PageLink::First) {
+        links.push(format!("<{}>; rel=\"first\"", url));
+    }
+
+    links.join(", ")
+}
+
+/// Pages linked to from a page.
+#[derive(Debug, Clone, Copy)]
+pub enum PageLink {
+    Next,
+    Prev,
+    First,
+}
+
+/// Makes the URL of a page linked to from a retrieved page, see [`link_header`] for the
+/// arguments.
+///
+/// # Return
+///
+/// * ``None`` if there is no such page, e.g. no next page of the last page.
+///
+pub fn page_url(path: &str, query: &str, info: &PageInfo, link: PageLink) -> Option<String> {
+    let cursor = match link {
+        PageLink::Next => Some(info.next_cursor.as_deref()?),
+        PageLink::Prev => Some(info.prev_cursor.as_deref()?),
+        PageLink::First => None,
+    };
+
+    let limit = info.limit.to_string();
+    let mut page_query: Vec<(&str, &str)> = vec![("limit", &limit)];
+    if let Some(cursor) = cursor {
+        page_query.push(("cursor", cursor));
+    }
+    let page_query = serde_urlencoded::to_string(&page_query).unwrap();
+
+    match query.is_empty() {
+        true => Some(format!("{}?{}", path, page_query)),
+        false => Some(format!("{}?{}&{}", path, query, page_query)),
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::pagination::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::pagination::tests::test_page_params_deserialise -- --exact
+///    * cargo test helper::pagination::tests::test_resolve -- --exact
+///    * cargo test helper::pagination::tests::test_page_info -- --exact
+///    * cargo test helper::pagination::tests::test_link_header -- --exact
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    #[test]
+    fn test_page_params_deserialise() {
+        let params: PageParams = serde_urlencoded::from_str("limit=10&offset=20").unwrap();
+        assert_eq!(params.limit, Some(10));
+        assert_eq!(params.offset, Some(20));
+        assert_eq!(params.cursor, None);
+
+        let params: PageParams = serde_json::from_str(r#"{"limit": 10, "cursor": "6131"}"#).unwrap();
+        assert_eq!(params.limit, Some(10));
+        assert_eq!(params.offset, None);
+        assert_eq!(params.cursor.as_deref(), Some("6131"));
+
+        let params: PageParams = serde_urlencoded::from_str("limit=&cursor=").unwrap();
+        assert_eq!(params.limit, None);
+
+        assert!(serde_urlencoded::from_str::<PageParams>("limit=ten").is_err());
+        assert!(serde_json::from_str::<PageParams>(r#"{"limit": 5000000000}"#).is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let params = PageParams::default();
+        assert_eq!(params.resolve(50, 500), Ok(Page::Offset { limit: 50, offset: 0 }));
+
+        let params = PageParams { limit: Some(10), offset: Some(30), cursor: None };
+        assert_eq!(params.resolve(50, 500), Ok(Page::Offset { limit: 10, offset: 30 }));
+
+        let key = vec![json!("Koblick"), json!(10004)];
+
+        let params = PageParams { limit: Some(10), offset: None, cursor: Some(encode_cursor("a", key.clone())) };
+        assert_eq!(params.resolve(50, 500), Ok(Page::After { limit: 10, key: key.clone() }));
+
+        let params = PageParams { limit: None, offset: None, cursor: Some(encode_cursor("b", key.clone())) };
+        let page = params.resolve(50, 500).unwrap();
+        assert_eq!(page, Page::Before { limit: 50, key: key.clone() });
+        assert_eq!(page.key(), Some(&key));
+
+        let params = PageParams { limit: Some(0), offset: None, cursor: None };
+        assert_eq!(params.resolve(50, 500), Err(format!("{} 500", PAGE_LIMIT_INVALID_MSG)));
+
+        let params = PageParams { limit: Some(501), offset: None, cursor: None };
+        assert!(params.resolve(50, 500).is_err());
+
+        let params = PageParams { limit: None, offset: Some(1), cursor: Some(encode_cursor("a", key)) };
+        assert_eq!(params.resolve(50, 500), Err(String::from(PAGE_CURSOR_OFFSET_MSG)));
+
+        for cursor in ["xyz", "5b2261222c315d7a7a", &hex::encode(r#"["c",1]"#), &hex::encode(r#"["a"]"#),
+            &hex::encode(r#"["a",null]"#), &hex::encode(r#"{"a":1}"#)] {
+            let params = PageParams { limit: None, offset: None, cursor: Some(String::from(cursor)) };
+            assert_eq!(params.resolve(50, 500), Err(String::from(PAGE_CURSOR_INVALID_MSG)));
+        }
+    }
+
+    #[test]
+    fn test_page_info() {
+        let key = |emp_no: i32| Some(vec![json!(emp_no)]);
+        let after = |emp_no: i32| Some(encode_cursor("a", key(emp_no).unwrap()));
+        let before = |emp_no: i32| Some(encode_cursor("b", key(emp_no).unwrap()));
+
+        let page = Page::Offset { limit: 2, offset: 0 };
+        let info = page.info(5, key(10), key(20), true);
+        assert_eq!(info.total, 5);
+        assert_eq!(info.offset, Some(0));
+        assert_eq!(info.next_cursor, after(20));
+        assert_eq!(info.prev_cursor, None);
+
+        let page = Page::Offset { limit: 2, offset: 4 };
+        let info = page.info(5, key(50), key(50), false);
+        assert_eq!(info.next_cursor, None);
+        assert_eq!(info.prev_cursor, before(50));
+
+        let page = Page::After { limit: 2, key: key(20).unwrap() };
+        let info = page.info(5, key(30), key(40), true);
+        assert_eq!(info.offset, None);
+        assert_eq!(info.next_cursor, after(40));
+        assert_eq!(info.prev_cursor, before(30));
+
+        let page = Page::Before { limit: 2, key: key(30).unwrap() };
+        let info = page.info(5, key(10), key(20), false);
+        assert_eq!(info.next_cursor, after(20));
+        assert_eq!(info.prev_cursor, None);
+
+        let info = page.info(0, None, None, false);
+        assert_eq!(info.next_cursor, None);
+        assert_eq!(info.prev_cursor, None);
+    }
+
+    #[test]
+    fn test_link_header() {
+        let info = PageInfo {
+            total: 5, limit: 2, offset: None,
+            next_cursor: Some(String::from("6134")), prev_cursor: Some(String::from("6233")),
+        };
+
+        assert_eq!(link_header("/data/employees", "last_name=%25chi", &info),
+            "</data/employees?last_name=%25chi&limit=2&cursor=6134>; rel=\"next\", \
+            </data/employees?last_name=%25chi&limit=2&cursor=6233>; rel=\"prev\", \
+            </data/employees?last_name=%25chi&limit=2>; rel=\"first\"");
+
+        let info = PageInfo { next_cursor: None, prev_cursor: None, ..info };
+        assert_eq!(link_header("/ui/employees", "", &info), "</ui/employees?limit=2>; rel=\"first\"");
+    }
+}
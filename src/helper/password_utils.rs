@@ -0,0 +1,297 @@
+/* Date Created: 18/10/2026. */
+
+//! Password utilities: [Argon2](https://docs.rs/argon2/latest/argon2/) hashing and
+//! verification, an offline breached passwords check, and random tokens for
+//! credentials which are not passwords.
+//!
+//! # Breached Passwords File
+//!
+//! The breached passwords file is a local copy of the
+//! [Pwned Passwords](https://haveibeenpwned.com/Passwords) SHA-1 list, **ordered by hash**.
+//! Each line is an upper case hexadecimal SHA-1 hash, optionally followed by a colon and
+//! the number of times it has been seen in breaches:
+//!
+//! ```text
+//! 5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8:9545824
+//! 7C4A8D09CA3762AF61E59520943DC26494F8941B:37359195
+//! ```
+//!
+//! Since the lines are sorted, the file is searched using a binary search over byte
+//! offsets. It is never loaded into memory, so the full list, which is tens of gigabytes,
+//! works just as well as the small sample file checked into this repository.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::password_utils::tests
+//!
+
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+
+use rand::{rngs::OsRng as RandOsRng, RngCore};
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2
+};
+
+use actix_web::http::StatusCode;
+
+use crate::bh_libs::api_status::ApiStatus;
+use crate::helper::messages::{
+    BREACHED_PASSWORD_MSG,
+    BREACHED_PASSWORD_CHECK_FAILURE_MSG,
+};
+
+/// Hashes a plain text password using [Argon2](https://docs.rs/argon2/latest/argon2/)
+/// with default parameters and a random salt.
+///
+/// # Return
+///
+/// * The PHC string of the hashed password. I.e., ``$argon2id$v=19$m=19456,t=2,p=1$...``.
+///
+pub fn hash_password(password: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .expect("Failed to hash password.")
+        .to_string()
+}
+
+/// Verifies a plain text password against a PHC string of a hashed password.
+///
+/// # Return
+///
+/// * ``true`` if the passwords matched. ``false`` otherwise, including when
+///   ``password_hash`` is not a valid PHC string.
+///
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    match PasswordHash::new(password_hash) {
+        Ok(parsed_hash) => Argon2::default()
+            .verify_password(password.as_bytes(), &parsed_hash).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Returns the upper case hexadecimal SHA-1 hash of a plain text password. This is the
+/// form used in the breached passwords file.
+///
+pub fn sha1_hex(password: &str) -> String {
+    hex::encode_upper(Sha1::digest(password.as_bytes()))
+}
+
+/// Returns `len` random bytes from the operating system, as lower case hexadecimal.
+/// For credentials such as persistent logins and sign-in links.
+///
+pub fn random_token(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    RandOsRng.fill_bytes(&mut bytes);
+
+    hex::encode(bytes)
+}
+
+/// Returns the lower case hexadecimal SHA-256 hash of a [`random_token`]. Random tokens 
+/// are long enough not to need a salted, slow hash: only this hash is stored.
+///
+pub fn sha256_hex(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Returns the offset of the first line which starts at or after `offset`.
+fn next_line_start<R: BufRead + Seek>(
+    reader: &mut R,
+    offset: u64
+) -> std::io::Result<u64> {
+    if offset == 0 {
+        return Ok(0);
+    }
+
+    reader.seek(SeekFrom::Start(offset - 1))?;
+    let mut skipped = Vec::new();
+    let read = reader.read_until(b'\n', &mut skipped)?;
+
+    Ok(offset - 1 + read as u64)
+}
+
+/// Compares the hash part of a breached passwords file line against `hash`.
+fn compare_line(line: &str, hash: &str) -> Ordering {
+    let line_hash = line.split(':').next().unwrap_or("").trim();
+
+    line_hash.to_ascii_uppercase().as_str().cmp(hash)
+}
+
+/// Checks if a plain text password is in the breached passwords file.
+///
+/// See the [module documentation](self#breached-passwords-file) for the file format.
+///
+/// # Arguments
+///
+/// * `file_path` - the breached passwords file. It is
+///   [`crate::config::Config::breached_passwords_file`].
+///
+/// * `password` - the plain text password to check.
+///
+/// # Return
+///
+/// * ``Ok(true)`` if the password has been breached, ``Ok(false)`` if not.
+///
+/// * ``Err(std::io::Error)`` if the file can't be read.
+///
+pub fn is_password_breached(
+    file_path: &str,
+    password: &str
+) -> std::io::Result<bool> {
+    let hash = sha1_hex(password);
+
+    let file = File::open(file_path)?;
+    let file_len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    // If the hash is in the file, it is on a line which starts within [low, high).
+    let mut low: u64 = 0;
+    let mut high: u64 = file_len;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        let line_start = next_line_start(&mut reader, mid)?;
+        if line_start >= high {
+            high = mid;
+            continue;
+        }
+
+        reader.seek(SeekFrom::Start(line_start))?;
+        let mut line = String::new();
+        let read = reader.read_line(&mut line)?;
+
+        match compare_line(&line, &hash) {
+            Ordering::Equal => return Ok(true),
+            Ordering::Less => low = line_start + read as u64,
+            Ordering::Greater => high = mid,
+        }
+    }
+
+    Ok(false)
+}
+
+/// Validates a new password before it gets hashed and stored. Presently, the only
+/// validation is the breached passwords check.
+///
+/// # Arguments
+///
+/// * `breached_passwords_file` - see [`is_password_breached`].
+///
+/// * `password` - the new plain text password.
+///
+/// # Return
+///
+/// * ``Ok(())`` if the new password can be used.
+///
+/// * ``Err(ApiStatus)`` with ``code`` [`actix_web::http::StatusCode::BAD_REQUEST`] and
+///   ``message`` [`crate::helper::messages::BREACHED_PASSWORD_MSG`] if the password has
+///   been breached. If the breached passwords file can't be read, ``code`` is
+///   [`actix_web::http::StatusCode::INTERNAL_SERVER_ERROR`]: the password is not accepted.
+///
+pub fn validate_new_password(
+    breached_passwords_file: &str,
+    password: &str
+) -> Result<(), ApiStatus> {
+    match is_password_breached(breached_passwords_file, password) {
+        Ok(false) => Ok(()),
+
+        Ok(true) => Err(ApiStatus::new(StatusCode::BAD_REQUEST.as_u16())
+            .set_message(BREACHED_PASSWORD_MSG)),
+
+        Err(err) => {
+            tracing::error!("Breached passwords file {}: {}", breached_passwords_file, err);
+
+            Err(ApiStatus::new(StatusCode::INTERNAL_SERVER_ERROR.as_u16())
+                .set_message(BREACHED_PASSWORD_CHECK_FAILURE_MSG))
+        }
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::password_utils::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::password_utils::tests::test_hash_verify_password -- --exact
+///    * cargo test helper::password_utils::tests::test_is_password_breached -- --exact
+///    * cargo test helper::password_utils::tests::test_is_password_breached_not_found -- --exact
+///    * cargo test helper::password_utils::tests::test_validate_new_password -- --exact
+///    * cargo test helper::password_utils::tests::test_random_token -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dotenv::dotenv;
+    use crate::config::Config;
+
+    fn breached_passwords_file() -> String {
+        dotenv().ok();
+        Config::init().breached_passwords_file
+    }
+
+    #[test]
+    fn test_hash_verify_password() {
+        let hash = hash_password("Correct Horse Battery Staple");
+
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(verify_password("Correct Horse Battery Staple", &hash));
+        assert!(!verify_password("correct horse battery staple", &hash));
+        assert!(!verify_password("Correct Horse Battery Staple", "not a PHC string"));
+    }
+
+    #[test]
+    fn test_is_password_breached() {
+        let file_path = breached_passwords_file();
+
+        assert_eq!(sha1_hex("password"), "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8");
+
+        // "1234567" is the first line, "123456789" is the last line of the sample file.
+        for password in ["password", "123456", "qwerty", "1234567", "123456789"] {
+            assert!(is_password_breached(&file_path, password).unwrap(), "{} should be breached", password);
+        }
+    }
+
+    #[test]
+    fn test_is_password_breached_not_found() {
+        let file_path = breached_passwords_file();
+
+        for password in ["", "Password", "Correct Horse Battery Staple", "zzzzzzzzzzzz"] {
+            assert!(!is_password_breached(&file_path, password).unwrap(), "{} should not be breached", password);
+        }
+
+        assert!(is_password_breached("./breached/no-such-file.txt", "password").is_err());
+    }
+
+    #[test]
+    fn test_validate_new_password() {
+        let file_path = breached_passwords_file();
+
+        assert!(validate_new_password(&file_path, "Correct Horse Battery Staple").is_ok());
+
+        let api_status = validate_new_password(&file_path, "letmein").unwrap_err();
+        assert_eq!(api_status.get_code(), StatusCode::BAD_REQUEST.as_u16());
+        assert_eq!(api_status.get_message().unwrap(), BREACHED_PASSWORD_MSG);
+
+        let api_status = validate_new_password("./breached/no-such-file.txt", "letmein").unwrap_err();
+        assert_eq!(api_status.get_code(), StatusCode::INTERNAL_SERVER_ERROR.as_u16());
+    }
+
+    #[test]
+    fn test_random_token() {
+        let token = random_token(32);
+
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        assert_ne!(random_token(32), token);
+
+        assert_eq!(sha256_hex("password"), "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8");
+    }
+}
@@ -0,0 +1,306 @@
+/* Date Created: 18/10/2026. */
+
+//! Binds access tokens to the client they were issued to, so that a token stolen from
+//! the ``authorization`` cookie, or from logs, does not work from another machine.
+//!
+//! A bound token carries the ``binding`` claim, see
+//! [`crate::helper::jwt_utils::JWTPayload::binding`]. How it is computed is set by
+//! ``TOKEN_BINDING`` in the ``.env`` file:
+//!
+//! * ``off`` -- tokens are not bound, and bindings are not checked.
+//!
+//! * ``client`` -- the SHA-256 hash of the ``User-Agent`` header and the client IP
+//!   address. Simple, but a client whose IP address changes, e.g. a mobile one, loses
+//!   its session. The IP address is the peer address: behind a reverse proxy, every
+//!   client has the same one.
+//!
+//! * ``cookie`` -- the SHA-256 hash of a random per-session secret, which is kept in the
+//!   server-side only cookie [`crate::helper::constants::TOKEN_BINDING`]. API-like
+//!   clients must return this cookie along with the token.
+//!
+//! ``TOKEN_BINDING_STRICTNESS`` sets what happens when a token's binding does not match
+//! the request, including when the token is not bound at all:
+//!
+//! * ``report`` -- a security event is logged, the token is accepted.
+//!
+//! * ``enforce`` -- a security event is logged, the token is rejected with
+//!   [`crate::helper::messages::TOKEN_BINDING_MISMATCH_MSG`].
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test helper::token_binding::tests
+//!
+
+use std::str::FromStr;
+
+use actix_web::{cookie::Cookie, http::{header, StatusCode}, HttpMessage, HttpRequest};
+
+use crate::bh_libs::api_status::ApiStatus;
+use crate::config::Config;
+use crate::helper::app_utils::build_token_binding_cookie;
+use crate::helper::constants::TOKEN_BINDING;
+use crate::helper::jwt_utils::{JWTPayload, make_token_from_payload};
+use crate::helper::messages::TOKEN_BINDING_MISMATCH_MSG;
+use crate::helper::password_utils::{random_token, sha256_hex};
+
+/// Number of random bytes in the ``cookie`` mode secret.
+const SECRET_BYTES: usize = 32;
+
+/// ``TOKEN_BINDING`` in the ``.env`` file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenBindingMode {
+    Off,
+    Client,
+    Cookie,
+}
+
+impl FromStr for TokenBindingMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "client" => Ok(Self::Client),
+            "cookie" => Ok(Self::Cookie),
+            _ => Err(format!("Unknown token binding mode {}", value)),
+        }
+    }
+}
+
+/// ``TOKEN_BINDING_STRICTNESS`` in the ``.env`` file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenBindingStrictness {
+    Report,
+    Enforce,
+}
+
+impl FromStr for TokenBindingStrictness {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "report" => Ok(Self::Report),
+            "enforce" => Ok(Self::Enforce),
+            _ => Err(format!("Unknown token binding strictness {}", value)),
+        }
+    }
+}
+
+/// The ``cookie`` mode secret issued while handling the current request. It is set to
+/// the request extension, since the cookie itself only reaches the client with the
+/// response.
+#[derive(Debug, Clone)]
+pub struct TokenBindingSecret(pub String);
+
+/// Returns the ``client`` mode binding of a request.
+pub fn client_fingerprint(request: &HttpRequest) -> String {
+    let user_agent = request.headers().get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+
+    let ip = request.peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default();
+
+    sha256_hex(&format!("{}\n{}", user_agent, ip))
+}
+
+/// Returns the ``cookie`` mode secret of a request, if any.
+fn cookie_secret(request: &HttpRequest) -> Option<String> {
+    if let Some(secret) = request.extensions().get::<TokenBindingSecret>() {
+        return Some(secret.0.clone());
+    }
+
+    request.cookie(TOKEN_BINDING)
+        .map(|cookie| String::from(cookie.value()))
+        .filter(|secret| secret.len() == SECRET_BYTES * 2)
+}
+
+/// Returns the binding a token used with this request should have.
+pub fn request_binding(mode: TokenBindingMode, request: &HttpRequest) -> Option<String> {
+    match mode {
+        TokenBindingMode::Off => None,
+        TokenBindingMode::Client => Some(client_fingerprint(request)),
+        TokenBindingMode::Cookie => cookie_secret(request).map(|secret| sha256_hex(&secret)),
+    }
+}
+
+/// Makes the access token of a successful login from its new payload, bound as
+/// configured. The caller keeps the payload's session Id, e.g. to register the session,
+/// see [`crate::models::session`].
+///
+/// In ``cookie`` mode, the browser's existing secret is reused. If there is none, a new
+/// one is made, and set to the request extension as [`TokenBindingSecret`].
+///
+/// # Return
+///
+/// * The access token, and in ``cookie`` mode, the
+///   [`crate::helper::constants::TOKEN_BINDING`] cookie to send with the response.
+///
+pub fn make_bound_token(
+    payload: JWTPayload,
+    request: &HttpRequest,
+    config: &Config
+) -> (String, Option<Cookie<'static>>) {
+    let mut cookie = None;
+
+    if config.token_binding == TokenBindingMode::Cookie {
+        let secret = cookie_secret(request).unwrap_or_else(|| random_token(SECRET_BYTES));
+
+        request.extensions_mut().insert(TokenBindingSecret(secret.clone()));
+        cookie = Some(build_token_binding_cookie(&secret));
+    }
+
+    let payload = payload.set_binding(request_binding(config.token_binding, request));
+
+    (make_token_from_payload(&payload, config.jwt_secret_key.as_ref()), cookie)
+}
+
+/// Checks a decoded access token's binding against the request.
+///
+/// # Return
+///
+/// * ``Ok(())`` if the binding matches, if ``TOKEN_BINDING=off``, or on mismatch with
+///   ``TOKEN_BINDING_STRICTNESS=report``.
+///
+/// * ``Err(ApiStatus)`` with ``code`` [`actix_web::http::StatusCode::UNAUTHORIZED`] and
+///   ``message`` [`crate::helper::messages::TOKEN_BINDING_MISMATCH_MSG`] on mismatch with
+///   ``TOKEN_BINDING_STRICTNESS=enforce``.
+///
+pub fn verify_binding(
+    payload: &JWTPayload,
+    request: &HttpRequest,
+    config: &Config
+) -> Result<(), ApiStatus> {
+    if config.token_binding == TokenBindingMode::Off {
+        return Ok(());
+    }
+
+    let binding = payload.binding();
+    if binding.is_some() && binding == request_binding(config.token_binding, request) {
+        return Ok(());
+    }
+
+    tracing::warn!("Security: token binding mismatch, {} session {} from {}, {}: {:?}",
+        payload.email(), payload.session_id(),
+        request.peer_addr().map(|addr| addr.to_string()).unwrap_or_default(),
+        if binding.is_some() { "bound to another client" } else { "unbound token" },
+        config.token_binding_strictness);
+
+    match config.token_binding_strictness {
+        TokenBindingStrictness::Report => Ok(()),
+        TokenBindingStrictness::Enforce => Err(ApiStatus::new(StatusCode::UNAUTHORIZED.as_u16())
+            .set_message(TOKEN_BINDING_MISMATCH_MSG)),
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test helper::token_binding::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test helper::token_binding::tests::test_from_str -- --exact
+///    * cargo test helper::token_binding::tests::test_client_binding -- --exact
+///    * cargo test helper::token_binding::tests::test_cookie_binding -- --exact
+///    * cargo test helper::token_binding::tests::test_unbound_token -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use dotenv::dotenv;
+    use crate::helper::jwt_utils::decode_token;
+
+    fn config(mode: TokenBindingMode, strictness: TokenBindingStrictness) -> Config {
+        dotenv().ok();
+
+        let mut config = Config::init();
+        config.token_binding = mode;
+        config.token_binding_strictness = strictness;
+
+        config
+    }
+
+    fn new_payload(config: &Config) -> JWTPayload {
+        JWTPayload::new("behai_nguyen@hotmail.com", config.jwt_mins_valid_for * 60)
+    }
+
+    fn payload(token: &str, config: &Config) -> JWTPayload {
+        decode_token(token, config.jwt_secret_key.as_ref(), None).unwrap()
+    }
+
+    fn client_request(user_agent: &str, ip: &str) -> HttpRequest {
+        TestRequest::default()
+            .insert_header((header::USER_AGENT, user_agent))
+            .peer_addr(format!("{}:443", ip).parse().unwrap())
+            .to_http_request()
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("Client".parse::<TokenBindingMode>(), Ok(TokenBindingMode::Client));
+        assert_eq!(" off ".parse::<TokenBindingMode>(), Ok(TokenBindingMode::Off));
+        assert!("ip".parse::<TokenBindingMode>().is_err());
+
+        assert_eq!("enforce".parse::<TokenBindingStrictness>(), Ok(TokenBindingStrictness::Enforce));
+        assert!("strict".parse::<TokenBindingStrictness>().is_err());
+    }
+
+    #[test]
+    fn test_client_binding() {
+        let config = config(TokenBindingMode::Client, TokenBindingStrictness::Enforce);
+
+        let request = client_request("Mozilla/5.0", "192.168.0.16");
+        let (token, cookie) = make_bound_token(new_payload(&config), &request, &config);
+        assert!(cookie.is_none());
+
+        let payload = payload(&token, &config);
+        assert!(verify_binding(&payload, &client_request("Mozilla/5.0", "192.168.0.16"), &config).is_ok());
+
+        let api_status = verify_binding(&payload, &client_request("curl/8.0", "192.168.0.16"), &config).unwrap_err();
+        assert_eq!(api_status.get_code(), StatusCode::UNAUTHORIZED.as_u16());
+        assert_eq!(api_status.get_message().unwrap(), TOKEN_BINDING_MISMATCH_MSG);
+
+        assert!(verify_binding(&payload, &client_request("Mozilla/5.0", "10.0.0.1"), &config).is_err());
+
+        // Report only.
+        let config = self::config(TokenBindingMode::Client, TokenBindingStrictness::Report);
+        assert!(verify_binding(&payload, &client_request("curl/8.0", "10.0.0.1"), &config).is_ok());
+    }
+
+    #[test]
+    fn test_cookie_binding() {
+        let config = config(TokenBindingMode::Cookie, TokenBindingStrictness::Enforce);
+
+        let request = TestRequest::default().to_http_request();
+        let (token, cookie) = make_bound_token(new_payload(&config), &request, &config);
+        let cookie = cookie.unwrap();
+        assert_eq!(cookie.name(), TOKEN_BINDING);
+
+        let payload = payload(&token, &config);
+
+        // The issuing request already has the secret, in its extension.
+        assert!(verify_binding(&payload, &request, &config).is_ok());
+
+        let request = TestRequest::default().cookie(cookie.clone()).to_http_request();
+        assert!(verify_binding(&payload, &request, &config).is_ok());
+
+        // The existing secret is reused.
+        let (_, reused) = make_bound_token(new_payload(&config), &request, &config);
+        assert_eq!(reused.unwrap().value(), cookie.value());
+
+        assert!(verify_binding(&payload, &TestRequest::default().to_http_request(), &config).is_err());
+    }
+
+    #[test]
+    fn test_unbound_token() {
+        let config = config(TokenBindingMode::Client, TokenBindingStrictness::Enforce);
+        let payload = JWTPayload::new("behai_nguyen@hotmail.com", 45);
+        let request = client_request("Mozilla/5.0", "192.168.0.16");
+
+        assert!(verify_binding(&payload, &request, &config).is_err());
+
+        let config = self::config(TokenBindingMode::Off, TokenBindingStrictness::Enforce);
+        assert!(verify_binding(&payload, &request, &config).is_ok());
+    }
+}
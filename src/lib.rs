@@ -5,6 +5,8 @@
 
 use std::{fs::File, io::Read as _,};
 use std::net::TcpListener;
+use std::sync::Arc;
+use std::path::Path;
 use dotenv::dotenv;
 use sqlx::{Pool, MySql};
 use actix_web::{
@@ -25,19 +27,33 @@ pub mod helper;
 
 pub mod config;
 pub mod database;
+pub mod cli;
 pub mod models;
 pub mod handlers;
 
 pub mod middleware;
 
+pub mod authenticators;
+pub mod mailers;
+pub mod search_index;
 pub mod auth_middleware;
 pub mod auth_handlers;
+pub mod department_handlers;
+pub mod salary_handlers;
+pub mod title_handlers;
+pub mod org_chart_handlers;
+pub mod profile_handlers;
+pub mod analytics_handlers;
+pub mod employee_csv_handlers;
+pub mod search_handlers;
+pub mod deleted_employee_handlers;
 
 use crate::helper::{app_utils::{
     make_api_status_response,
     build_authorization_cookie,    
     remove_login_redirect_cookie,
     remove_original_content_type_cookie},
+    content_negotiation::ScopeRepresentation,
     jwt_utils,
     messages::TOKEN_STR_JWT_MSG,
 };
@@ -45,6 +61,9 @@ use crate::helper::{app_utils::{
 pub struct AppState {
     db: Pool<MySql>,
     cfg: config::Config,
+    authenticators: Arc<authenticators::AuthenticatorChain>,
+    mailer: Arc<dyn mailers::Mailer>,
+    search_index: Arc<search_index::EmployeeIndex>,
 }
 
 /// Configures and returns an actix_cors::Cors.
@@ -52,12 +71,14 @@ pub struct AppState {
 fn cors_config(config: &config::Config) -> Cors {
     Cors::default()
         .allowed_origin(&config.allowed_origin)
-        .allowed_methods(vec!["GET", "POST"])
+        .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE"])
         .allowed_headers(vec![
             header::CONTENT_TYPE,
             header::AUTHORIZATION,
             header::ACCEPT,
+            header::IF_MATCH,
         ])
+        .expose_headers(vec![header::ETAG])
         .max_age(config.max_age)
         .supports_credentials()
 }
@@ -143,6 +164,20 @@ fn form_config() -> web::FormConfig {
         })
 }
 
+/// A global error handler for query string data extractor, [`actix_web::types::query`].
+/// 
+/// Same as [`json_config`], malformed query strings result in a JSON 
+/// [`crate::bh_libs::api_status::ApiStatus`] with ``code`` of ``400``.
+/// 
+fn query_config() -> web::QueryConfig {
+    web::QueryConfig::default()
+        .error_handler(|err, _req| {
+            let err_str = err.to_string();
+            error::InternalError::from_response(err, 
+                make_api_status_response(StatusCode::BAD_REQUEST, &err_str, None)).into()
+        })
+}
+
 /// Attempts to extract session Id from the JWT payload.
 /// If not exists yet, return "[no session Id]" instead.
 /// 
@@ -284,16 +319,32 @@ pub async fn run(listener: TcpListener) -> Result<Server, std::io::Error> {
 
     let (secret_key, redis_store) = config_session_store().await;
 
+    // Fails fast on unknown AUTH_BACKENDS or MAILER, rather than in each worker.
+    let authenticators = Arc::new(authenticators::AuthenticatorChain::from_config(&config, &pool));
+    let mailer: Arc<dyn mailers::Mailer> = Arc::from(mailers::from_config(&config));
+
+    let search_index = Arc::new(search_index::EmployeeIndex::open(Path::new(&config.search_index_dir))
+        .expect("Failed to open the search index"));
+    if search_index.num_docs() == 0 {
+        tracing::warn!("The search index is empty, run: learn_actix_web rebuild-search-index");
+    }
+
     let server = HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(AppState {
                 db: pool.clone(),
-                cfg: config.clone()
+                cfg: config.clone(),
+                authenticators: authenticators.clone(),
+                mailer: mailer.clone(),
+                search_index: search_index.clone(),
             }))
             .app_data(json_config())
             .app_data(form_config())
+            .app_data(query_config())
             .wrap(from_fn(finalise_request))
+            .wrap(from_fn(auth_middleware::check_session))
             .wrap(auth_middleware::CheckLogin)
+            .wrap(from_fn(auth_middleware::restore_persistent_login))
             .wrap(from_fn(log_request_entry))
             .wrap(IdentityMiddleware::default())
             .wrap(SessionMiddleware::builder(
@@ -307,20 +358,60 @@ pub async fn run(listener: TcpListener) -> Result<Server, std::io::Error> {
             .wrap(cors_config(&config))
             .service(
                 web::scope("/data")
+                    .app_data(web::Data::new(ScopeRepresentation::DATA))
+                    .service(department_handlers::departments_json)
+                    .service(department_handlers::department_json)
+                    .service(department_handlers::department_employees_json)
+                    .service(department_handlers::employee_departments_json)
+                    .service(salary_handlers::employee_salaries_json)
+                    .service(salary_handlers::department_salary_stats_json)
+                    .service(salary_handlers::title_salary_stats_json)
+                    .service(title_handlers::titles_json)
+                    .service(title_handlers::employee_titles_json)
+                    .service(org_chart_handlers::company_org_chart)
+                    .service(org_chart_handlers::department_org_chart)
+                    .service(org_chart_handlers::employee_manager_json)
+                    .service(profile_handlers::employee_profile_json)
+                    .service(analytics_handlers::headcount)
+                    .service(analytics_handlers::hires_separations)
+                    .service(analytics_handlers::department_gender)
+                    .service(analytics_handlers::title_gender)
+                    .service(analytics_handlers::department_tenure)
+                    .service(analytics_handlers::title_tenure)
+                    .service(employee_csv_handlers::export_employees_csv)
+                    .service(employee_csv_handlers::import_employees_csv)
+                    .service(search_handlers::search_employees_json)
+                    .service(deleted_employee_handlers::deleted_employees_json)
+                    .service(deleted_employee_handlers::restore_employee_json)
                     .service(handlers::employees_json1)
-                    .service(handlers::employees_json2),
+                    .service(handlers::employees_by_name)
+                    .service(handlers::employees_by_query)
+                    .service(handlers::get_employee)
+                    .service(handlers::create_employee)
+                    .service(handlers::replace_employee)
+                    .service(handlers::patch_employee)
+                    .service(handlers::remove_employee),
             )
             .service(
                 web::scope("/ui")
+                    .app_data(web::Data::new(ScopeRepresentation::UI))
+                    .service(department_handlers::departments_html)
+                    .service(department_handlers::department_html)
+                    .service(department_handlers::employee_departments_html)
+                    .service(profile_handlers::employee_profile_html)
                     .service(handlers::employees_html1)
-                    .service(handlers::employees_html2)
+                    .service(handlers::employees_by_name)
+                    .service(handlers::employees_by_query)
                     .service(auth_handlers::login_page)
                     .service(auth_handlers::home_page),
             )
             .service(
                 web::scope("/api")
                     .service(auth_handlers::login)
-                    .service(auth_handlers::logout),
+                    .service(auth_handlers::request_magic_link)
+                    .service(auth_handlers::magic_link_login)
+                    .service(auth_handlers::logout)
+                    .service(auth_handlers::change_password),
             )
             .service(
                 web::resource("/helloemployee/{last_name}/{first_name}")
@@ -0,0 +1,66 @@
+/* Date Created: 18/10/2026. */
+
+//! Outgoing mail.
+//!
+//! A mailer implements the [`Mailer`] trait: it delivers a [`MailMessage`]. Two
+//! mailers are available:
+//!
+//! * ``file`` -- [`file::FileMailer`], writes each message into a local directory.
+//!   For development and tests.
+//!
+//! * ``smtp`` -- [`smtp::SmtpMailer`], sends each message to an SMTP server using
+//!   [lettre](https://docs.rs/lettre/latest/lettre/index.html).
+//!
+//! The mailer is chosen with the ``MAILER`` entry in the ``.env`` file.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test mailers::
+//!
+
+use futures_util::future::LocalBoxFuture;
+
+use crate::config::Config;
+
+pub mod file;
+pub mod smtp;
+
+/// The name of the [`file::FileMailer`] in ``MAILER``.
+pub static FILE_MAILER: &str = "file";
+/// The name of the [`smtp::SmtpMailer`] in ``MAILER``.
+pub static SMTP_MAILER: &str = "smtp";
+
+/// A plain text email.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// A mail delivery backend.
+pub trait Mailer: Send + Sync {
+    /// The name of the mailer, as used in ``MAILER``.
+    fn name(&self) -> &'static str;
+
+    /// Delivers a message. On failure, the error is the reason, for logging.
+    fn send<'a>(
+        &'a self,
+        message: &'a MailMessage
+    ) -> LocalBoxFuture<'a, Result<(), String>>;
+}
+
+/// Creates the mailer named in [`crate::config::Config::mailer`].
+///
+/// # Panics
+///
+/// On an unknown mailer name, or an invalid ``SMTP_URL``. This is a configuration 
+/// error which should stop the application from starting.
+///
+pub fn from_config(config: &Config) -> Box<dyn Mailer> {
+    match config.mailer.as_str() {
+        "file" => Box::new(file::FileMailer::new(&config.mail_from, &config.mail_file_dir)),
+        "smtp" => Box::new(smtp::SmtpMailer::new(&config.mail_from, &config.smtp_url)),
+        _ => panic!("Unknown mailer {} in MAILER", config.mailer),
+    }
+}
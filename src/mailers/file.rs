@@ -0,0 +1,120 @@
+/* Date Created: 18/10/2026. */
+
+//! The ``file`` mailer: each message is written to ``<MAIL_FILE_DIR>/<recipient>.eml``.
+//! A later message to the same recipient replaces the earlier one, so that tests and
+//! developers can always find the latest message sent to an address.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test mailers::file::tests
+//!
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use futures_util::future::LocalBoxFuture;
+
+use super::{Mailer, MailMessage};
+
+/// Writes messages into a local directory.
+pub struct FileMailer {
+    from: String,
+    dir: PathBuf,
+}
+
+impl FileMailer {
+    /// # Arguments
+    ///
+    /// * `from` - the sender address.
+    ///
+    /// * `dir` - the directory to write messages to. It is created if needed.
+    ///
+    pub fn new(from: &str, dir: &str) -> Self {
+        Self {
+            from: String::from(from),
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    /// Returns the file the latest message to `to` is written to.
+    ///
+    /// Characters other than ASCII alphanumerics, ``@``, ``.``, ``-`` and ``_`` are
+    /// replaced with ``_``, so a recipient can't write outside the directory.
+    ///
+    pub fn message_path(dir: &Path, to: &str) -> PathBuf {
+        let file_name: String = to.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || "@.-_".contains(c) { c } else { '_' })
+            .collect();
+
+        dir.join(format!("{}.eml", file_name.trim_start_matches('.')))
+    }
+
+    fn render(&self, message: &MailMessage) -> String {
+        format!("From: {}\r\nTo: {}\r\nSubject: {}\r\n\r\n{}",
+            self.from, message.to, message.subject, message.body)
+    }
+}
+
+impl Mailer for FileMailer {
+    fn name(&self) -> &'static str {
+        super::FILE_MAILER
+    }
+
+    fn send<'a>(
+        &'a self,
+        message: &'a MailMessage
+    ) -> LocalBoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            fs::create_dir_all(&self.dir).map_err(|err| err.to_string())?;
+
+            let path = Self::message_path(&self.dir, &message.to);
+            fs::write(&path, self.render(message)).map_err(|err| err.to_string())?;
+
+            tracing::debug!("Mail to {} written to {}", message.to, path.display());
+
+            Ok(())
+        })
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test mailers::file::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test mailers::file::tests::test_message_path -- --exact
+///    * cargo test mailers::file::tests::test_send -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_path() {
+        let dir = Path::new("./mail");
+
+        assert_eq!(FileMailer::message_path(dir, "chirstian.koblick.10004@gmail.com"),
+            dir.join("chirstian.koblick.10004@gmail.com.eml"));
+        assert_eq!(FileMailer::message_path(dir, "../../etc/passwd"),
+            dir.join("_.._etc_passwd.eml"));
+    }
+
+    #[actix_web::test]
+    async fn test_send() {
+        let dir = std::env::temp_dir().join(format!("file-mailer-{}", uuid::Uuid::new_v4()));
+        let mailer = FileMailer::new("no-reply@localhost", dir.to_str().unwrap());
+
+        let message = MailMessage {
+            to: String::from("chirstian.koblick.10004@gmail.com"),
+            subject: String::from("Subject"),
+            body: String::from("Body"),
+        };
+
+        assert_eq!(mailer.send(&message).await, Ok(()));
+
+        let content = fs::read_to_string(FileMailer::message_path(&dir, &message.to)).unwrap();
+        assert_eq!(content, "From: no-reply@localhost\r\nTo: chirstian.koblick.10004@gmail.com\r\nSubject: Subject\r\n\r\nBody");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,69 @@
+/* Date Created: 18/10/2026. */
+
+//! The ``smtp`` mailer: messages are sent to the SMTP server at ``SMTP_URL`` using
+//! [lettre](https://docs.rs/lettre/latest/lettre/index.html).
+//!
+//! The lettre transport is blocking, it runs on the 
+//! [actix_web::web::block](https://docs.rs/actix-web/latest/actix_web/web/fn.block.html)
+//! thread pool.
+
+use futures_util::future::LocalBoxFuture;
+use lettre::{Message, SmtpTransport, Transport, message::header::ContentType};
+
+use super::{Mailer, MailMessage};
+
+/// Sends messages to an SMTP server.
+pub struct SmtpMailer {
+    from: String,
+    transport: SmtpTransport,
+}
+
+impl SmtpMailer {
+    /// # Arguments
+    ///
+    /// * `from` - the sender address.
+    ///
+    /// * `url` - see [lettre::SmtpTransport::from_url](https://docs.rs/lettre/latest/lettre/transport/smtp/struct.SmtpTransport.html#method.from_url).
+    ///
+    /// # Panics
+    ///
+    /// If `url` is not a valid SMTP URL.
+    ///
+    pub fn new(from: &str, url: &str) -> Self {
+        Self {
+            from: String::from(from),
+            transport: SmtpTransport::from_url(url)
+                .unwrap_or_else(|err| panic!("Invalid SMTP_URL {}: {}", url, err))
+                .build(),
+        }
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn name(&self) -> &'static str {
+        super::SMTP_MAILER
+    }
+
+    fn send<'a>(
+        &'a self,
+        message: &'a MailMessage
+    ) -> LocalBoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let email = Message::builder()
+                .from(self.from.parse().map_err(|err| format!("MAIL_FROM: {}", err))?)
+                .to(message.to.parse().map_err(|err| format!("To {}: {}", message.to, err))?)
+                .subject(&message.subject)
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.body.clone())
+                .map_err(|err| err.to_string())?;
+
+            let transport = self.transport.clone();
+
+            actix_web::web::block(move || transport.send(&email))
+                .await
+                .map_err(|err| err.to_string())?
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        })
+    }
+}
@@ -1,11 +1,13 @@
 /* Date Created: 11/10/2023. */
 
-//! Web application entry function.
+//! Web application entry function. Runs the HTTP server, or a command line task
+//! when given, see [`learn_actix_web::cli`].
 
 use dotenv::dotenv;
 use std::net::TcpListener;
 use learn_actix_web::helper::app_logger::init_app_logger;
 use learn_actix_web::run;
+use learn_actix_web::cli::Command;
 
 #[actix_web::main]
 async fn main() -> Result<(), std::io::Error> {
@@ -14,6 +16,16 @@ async fn main() -> Result<(), std::io::Error> {
 
     let _guards = init_app_logger();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match Command::parse(&args) {
+        Ok(None) => (),
+        Ok(Some(command)) => std::process::exit(command.run().await),
+        Err(usage) => {
+            eprintln!("{}", usage);
+            std::process::exit(2);
+        }
+    }
+
     let listener = TcpListener::bind("0.0.0.0:5000").expect("Failed to bind port 5000");
     // We retrieve the port assigned to us by the OS
     let port = listener.local_addr().unwrap().port();
@@ -4,6 +4,10 @@
 //! mirrors the database table, other related auxiliary structure(s), and 
 //! associated CRUD method(s).
 //! 
+//! Employees are soft deleted, see [`delete_employee`]: the queries of this module, and
+//! of [`employee_search`], exclude deleted employees. [`deleted_employee`] lists,
+//! restores and purges them.
+//! 
 
 // To run all doc tests:
 // 
@@ -12,19 +16,52 @@
 // Can't run a specific doc test.
 // 
 
-use sqlx::{FromRow, Row, Pool, MySql};
+use sqlx::{FromRow, Row, Pool, MySql, QueryBuilder, Executor};
 
-use sqlx::types::time::Date;
+use sqlx::types::time::{Date, PrimitiveDateTime};
 use serde::{Serialize, Deserialize};
 
+use actix_web::http::StatusCode;
+
 use crate::bh_libs::{
-    australian_date::australian_date_format,
+    australian_date::{australian_date_format, australian_date_option_format},
     api_status::ApiStatus
 };
+use crate::helper::pagination::PageInfo;
+use crate::helper::messages::{
+    EMPLOYEE_NO_INVALID_MSG,
+    EMPLOYEE_EMAIL_INVALID_MSG,
+    EMPLOYEE_FIRST_NAME_INVALID_MSG,
+    EMPLOYEE_LAST_NAME_INVALID_MSG,
+    EMPLOYEE_GENDER_INVALID_MSG,
+    EMPLOYEE_DATES_INVALID_MSG,
+    EMPLOYEE_NO_EXISTS_MSG,
+    EMPLOYEE_EMAIL_EXISTS_MSG
+};
+
+pub mod persistent_login;
+pub mod magic_link;
+pub mod session;
+pub mod account_status;
+pub mod employee_search;
+pub mod department;
+pub mod permission;
+pub mod salary;
+pub mod title;
+pub mod org_chart;
+pub mod profile;
+pub mod analytics;
+pub mod employee_export;
+pub mod employee_import;
+pub mod employee_fields;
+pub mod deleted_employee;
 
 /// Represents the ``employees`` table in the database. Values of [`sqlx::types::time::Date`] 
 /// fields are converted into Australian date format ``dd/mm/yyyy`` before
 /// sending back to the client.
+/// 
+/// ``title`` is the current title from the ``titles`` table, see [`title`]. It is read 
+/// only: ignored when creating or updating an employee.
 #[derive(FromRow, Debug, Deserialize, Serialize)]
 pub struct Employee {
     pub emp_no: i32,
@@ -36,6 +73,144 @@ pub struct Employee {
     pub gender: String,
     #[serde(with = "australian_date_format")]
     pub hire_date: Date,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub title: Option<String>,
+}
+
+/// Maximum lengths of ``employees`` string columns.
+const FIRST_NAME_MAX_LEN: usize = 14;
+const LAST_NAME_MAX_LEN: usize = 16;
+const EMAIL_MAX_LEN: usize = 255;
+
+impl Employee {
+    /// Trims ``first_name`` and ``last_name``. Before [`Employee::validate`], so that the
+    /// employee is as it is stored, and as its ``ETag`` is of.
+    pub fn normalize(&mut self) {
+        self.first_name = self.first_name.trim().to_string();
+        self.last_name = self.last_name.trim().to_string();
+    }
+
+    /// Validates a submitted employee against the ``employees`` table constraints.
+    /// 
+    /// # Return
+    /// 
+    /// * ``Err(String)`` - all problems found, space separated. E.g. 
+    ///   [`crate::helper::messages::EMPLOYEE_GENDER_INVALID_MSG`].
+    /// 
+    pub fn validate(&self) -> Result<(), String> {
+        let errors = self.validation_errors();
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors.join(" ")),
+        }
+    }
+
+    /// As [`Employee::validate`], but each problem found is separate, e.g. for a per row
+    /// report, see [`employee_import`].
+    pub fn validation_errors(&self) -> Vec<&'static str> {
+        let mut errors: Vec<&'static str> = Vec::new();
+
+        if self.emp_no <= 0 {
+            errors.push(EMPLOYEE_NO_INVALID_MSG);
+        }
+
+        if !is_valid_email(&self.email) {
+            errors.push(EMPLOYEE_EMAIL_INVALID_MSG);
+        }
+
+        let first_name_len = self.first_name.trim().chars().count();
+        if first_name_len == 0 || first_name_len > FIRST_NAME_MAX_LEN {
+            errors.push(EMPLOYEE_FIRST_NAME_INVALID_MSG);
+        }
+
+        let last_name_len = self.last_name.trim().chars().count();
+        if last_name_len == 0 || last_name_len > LAST_NAME_MAX_LEN {
+            errors.push(EMPLOYEE_LAST_NAME_INVALID_MSG);
+        }
+
+        if self.gender != "M" && self.gender != "F" {
+            errors.push(EMPLOYEE_GENDER_INVALID_MSG);
+        }
+
+        if self.birth_date >= self.hire_date {
+            errors.push(EMPLOYEE_DATES_INVALID_MSG);
+        }
+
+        errors
+    }
+}
+
+/// A deliberately simple email check: one ``@``, with a non-blank local part, and a
+/// dotted domain. Delivery is the real test.
+fn is_valid_email(email: &str) -> bool {
+    if email.len() > EMAIL_MAX_LEN || email.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    match email.split_once('@') {
+        Some((local, domain)) => !local.is_empty() && !domain.contains('@') && 
+            domain.split('.').count() > 1 && domain.split('.').all(|part| !part.is_empty()),
+        None => false,
+    }
+}
+
+/// Represents a new employee submission: an [`Employee`], and optionally the initial 
+/// password.
+#[derive(Debug, Deserialize)]
+pub struct NewEmployee {
+    #[serde(flatten)]
+    pub employee: Employee,
+    /// User input, optional. Checked and hashed as for a password change. If not given,
+    /// a random password nobody knows is stored: the employee signs in with a 
+    /// passwordless sign-in link, see [`magic_link`].
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// Represents a partial employee update, a ``PATCH`` request body. Only the given fields
+/// are changed. ``emp_no`` can't be changed, it is rejected as an unknown field.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmployeePatch {
+    pub email: Option<String>,
+    #[serde(default, with = "australian_date_option_format")]
+    pub birth_date: Option<Date>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub gender: Option<String>,
+    #[serde(default, with = "australian_date_option_format")]
+    pub hire_date: Option<Date>,
+}
+
+impl EmployeePatch {
+    /// Returns `employee` with the given fields replaced.
+    pub fn apply(self, employee: Employee) -> Employee {
+        Employee {
+            emp_no: employee.emp_no,
+            email: self.email.unwrap_or(employee.email),
+            birth_date: self.birth_date.unwrap_or(employee.birth_date),
+            first_name: self.first_name.unwrap_or(employee.first_name),
+            last_name: self.last_name.unwrap_or(employee.last_name),
+            gender: self.gender.unwrap_or(employee.gender),
+            hire_date: self.hire_date.unwrap_or(employee.hire_date),
+            title: employee.title,
+        }
+    }
+}
+
+/// Represents a JSON response carrying data: the [`ApiStatus`] fields, and the actual
+/// data in ``data``. E.g. ``{"code": 200, "message": null, "session_id": null, "data": {...}}``.
+/// 
+/// A page of a list also has ``page``, see [`crate::helper::pagination`].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DataResponse<T> {
+    #[serde(flatten)]
+    pub api_status: ApiStatus,
+    pub data: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<PageInfo>,
 }
 
 /// Represents a login submission. That is, user login request data are capture into this struct.
@@ -46,6 +221,33 @@ pub struct EmployeeLogin {
     /// User input. The exact plain text password. If there is matched on ``email``, this 
     /// password will be hashed, then compared to the one retrieved from database.
     pub password: String,
+    /// User input, optional. ``true`` to stay logged in after the access token has
+    /// expired. See [`persistent_login`].
+    #[serde(default)]
+    #[sqlx(default)]
+    pub remember_me: bool,
+    /// User input, optional. Where to go after logging in, see 
+    /// [`crate::helper::login_next`].
+    #[serde(default)]
+    #[sqlx(default)]
+    pub next: Option<String>,
+}
+
+/// Represents a passwordless sign-in link request.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MagicLinkRequest {
+    /// User input. The sign-in link is sent to this email, if it matches ``employees.email``.
+    pub email: String,
+}
+
+/// Represents a password change submission.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PasswordChange {
+    /// User input. The current plain text password, it must match the stored password.
+    pub current_password: String,
+    /// User input. The new plain text password. It is checked against the breached 
+    /// passwords file, then hashed before being stored.
+    pub new_password: String,
 }
 
 /// Represents a result data of a successful login request.
@@ -58,6 +260,9 @@ pub struct LoginSuccess {
     /// The login authentication token.
     pub access_token: String,
     pub token_type: String,
+    /// The originally requested page, if any. See [`crate::helper::login_next`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
 }
 
 /// Represents a JSON response of a successful login request.
@@ -70,24 +275,6 @@ pub struct LoginSuccessResponse {
     pub data: LoginSuccess
 }
 
-/// An auxiliary structure which represents: 
-/// 
-/// * a JSON POST request body. E.g.: ``{"last_name": "%chi", "first_name": "%ak"}``.
-/// Request content type is ``application/json``.
-///
-/// * a x-www-form-urlencoded POST request body. E.g.: ``last_name=%chi&first_name=%ak``.
-/// Request content type is ``application/x-www-form-urlencoded; charset=UTF-8``.
-/// 
-/// Both ``last_name`` and ``first_name`` are partial strings. That is,
-/// each should have at least either a leading or a trailing ``%`` character.
-#[derive(Debug, Deserialize)]
-pub struct EmployeeSearch {
-    /// A MySQL partial string compared using LIKE operator.
-    pub last_name: String,
-    /// A MySQL partial string compared using LIKE operator.
-    pub first_name: String,
-}
-
 /// Attempts to retrieve data from the ``employees`` table based on partial
 /// last name and partial first name.
 /// 
@@ -140,18 +327,26 @@ pub async fn get_employees(
 ) -> Vec<Employee> {
     sqlx::query("call get_employees(?, ?)")
     .bind(last_name).bind(first_name)
-    .map(|row: sqlx::mysql::MySqlRow| { 
-        Employee {
+    .try_map(|row: sqlx::mysql::MySqlRow| { 
+        // The stored procedure selects all columns, deleted employees included.
+        let deleted_at: Option<PrimitiveDateTime> = row.try_get("deleted_at")?;
+
+        Ok((deleted_at.is_none(), Employee {
             emp_no: row.get(0),
             email: row.get(1),
             birth_date: row.get(3),
             first_name: row.get(4),
             last_name: row.get(5),
             gender: row.get(6),
-            hire_date: row.get(7)
-        }
+            hire_date: row.get(7),
+            // The stored procedure does not select titles.
+            title: None
+        }))
     })
     .fetch_all(pool).await.unwrap()
+    .into_iter()
+    .filter_map(|(not_deleted, employee)| not_deleted.then_some(employee))
+    .collect()
 }
 
 /// Attempts to retrieve a single record from the ``employees`` table based on 
@@ -164,7 +359,7 @@ pub async fn get_employees(
 /// # Return
 /// 
 /// - [`std::option::Option`]&lt;[`EmployeeLogin`]&gt; - that is, a single row
-/// if found, otherwise nothing.
+///   if found, otherwise nothing. Deleted employees are not found.
 /// 
 /// # Example, in a synchronous function:
 /// 
@@ -209,18 +404,221 @@ pub async fn get_employees(
 pub async fn select_employee(
     pool: &Pool<MySql>,
     email: &str ) -> Option<EmployeeLogin> {
-    let sql = format!("SELECT email, password FROM employees WHERE email = '{}'", email);
-
-    sqlx::query(&sql)
+    sqlx::query("SELECT email, password FROM employees WHERE email = ? AND deleted_at IS NULL")
+    .bind(email)
     .map(|row: sqlx::mysql::MySqlRow| { 
         EmployeeLogin {
             email: row.get(0),
             password: row.get(1),
+            remember_me: false,
+            next: None,
         }
     })
     .fetch_optional(pool).await.unwrap()
 }
 
+/// Replaces the password of an employee identified by the exact email.
+/// 
+/// # Arguments
+/// 
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+/// 
+/// * `email` - the exact email to match against ``employees.email``.
+/// 
+/// * `password_hash` - the already hashed new password. See 
+///   [`crate::helper::password_utils::hash_password`].
+/// 
+/// # Return
+/// 
+/// - ``true`` if a row was updated, ``false`` otherwise.
+/// 
+pub async fn update_employee_password(
+    pool: &Pool<MySql>,
+    email: &str,
+    password_hash: &str ) -> bool {
+    sqlx::query("UPDATE employees SET password = ? WHERE email = ?")
+    .bind(password_hash).bind(email)
+    .execute(pool).await.unwrap()
+    .rows_affected() > 0
+}
+
+/// Attempts to retrieve a single record from the ``employees`` table based on 
+/// the employee number.
+/// 
+/// # Arguments
+/// 
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+/// 
+/// * `emp_no` - the exact ``employees.emp_no``.
+/// 
+/// # Return
+/// 
+/// - [`std::option::Option`]&lt;[`Employee`]&gt; - a single row if found, otherwise nothing.
+/// 
+pub async fn select_employee_by_no(
+    pool: &Pool<MySql>,
+    emp_no: i32 ) -> Option<Employee> {
+    select_employee_by_no_locking(pool, emp_no, false).await
+}
+
+/// As [`select_employee_by_no`], and locks the ``employees`` row until the end of the
+/// transaction: the employee can't be changed by another request before it is updated.
+/// 
+/// # Arguments
+/// 
+/// * `executor` - a transaction, see [`update_employee`].
+/// 
+/// * `emp_no` - the exact ``employees.emp_no``.
+/// 
+pub async fn lock_employee_by_no<'c, E>(
+    executor: E,
+    emp_no: i32 ) -> Option<Employee>
+where
+    E: Executor<'c, Database = MySql> {
+    select_employee_by_no_locking(executor, emp_no, true).await
+}
+
+async fn select_employee_by_no_locking<'c, E>(
+    executor: E,
+    emp_no: i32,
+    for_update: bool ) -> Option<Employee>
+where
+    E: Executor<'c, Database = MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+        "SELECT emp_no, email, birth_date, first_name, last_name, gender, hire_date, ");
+    title::push_current_title(&mut builder);
+    builder.push(" FROM employees WHERE deleted_at IS NULL AND emp_no = ").push_bind(emp_no);
+    if for_update {
+        builder.push(" FOR UPDATE");
+    }
+
+    builder.build()
+    .map(|row: sqlx::mysql::MySqlRow| { 
+        Employee {
+            emp_no: row.get(0),
+            email: row.get(1),
+            birth_date: row.get(2),
+            first_name: row.get(3),
+            last_name: row.get(4),
+            gender: row.get(5),
+            hire_date: row.get(6),
+            title: row.get(7)
+        }
+    })
+    .fetch_optional(executor).await.unwrap()
+}
+
+/// Maps a failed ``employees`` insert or update into a ``409`` [`ApiStatus`] if it
+/// violates ``PRIMARY`` or ``email_unique``. Other database errors are not expected.
+/// 
+/// # Panics
+/// 
+/// On any other database error, as for the other queries in this module.
+/// 
+fn employee_write_error(err: sqlx::Error) -> ApiStatus {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            let message = match db_err.message().contains("PRIMARY") {
+                true => EMPLOYEE_NO_EXISTS_MSG,
+                false => EMPLOYEE_EMAIL_EXISTS_MSG,
+            };
+
+            ApiStatus::new(StatusCode::CONFLICT.as_u16()).set_message(message)
+        }
+        _ => panic!("employees write failed: {}", err),
+    }
+}
+
+/// Inserts a new employee.
+/// 
+/// # Arguments
+/// 
+/// * `executor` - an already established MySQL connection: a [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;,
+///   or a transaction, see [`employee_import`].
+/// 
+/// * `employee` - an already normalized and validated employee, see [`Employee::normalize`].
+/// 
+/// * `password_hash` - the already hashed initial password.
+/// 
+/// # Return
+/// 
+/// - ``Err(ApiStatus)`` with ``code`` ``409`` if ``emp_no`` or ``email`` is taken.
+/// 
+pub async fn insert_employee<'c, E>(
+    executor: E,
+    employee: &Employee,
+    password_hash: &str ) -> Result<(), ApiStatus>
+where
+    E: Executor<'c, Database = MySql> {
+    sqlx::query("INSERT INTO employees 
+            (emp_no, email, password, birth_date, first_name, last_name, gender, hire_date)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)")
+    .bind(employee.emp_no).bind(&employee.email).bind(password_hash)
+    .bind(employee.birth_date).bind(&employee.first_name).bind(&employee.last_name)
+    .bind(&employee.gender).bind(employee.hire_date)
+    .execute(executor).await
+    .map(|_| ())
+    .map_err(employee_write_error)
+}
+
+/// Replaces all fields but ``emp_no`` of an existing employee.
+/// 
+/// # Arguments
+/// 
+/// * `executor` - an already established MySQL connection: a [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;,
+///   or a transaction which has locked the employee, see [`lock_employee_by_no`].
+/// 
+/// * `employee` - an already normalized and validated employee, see [`Employee::normalize`].
+/// 
+/// # Return
+/// 
+/// - ``Err(ApiStatus)`` with ``code`` ``409`` if ``email`` is taken by another employee.
+/// 
+pub async fn update_employee<'c, E>(
+    executor: E,
+    employee: &Employee ) -> Result<(), ApiStatus>
+where
+    E: Executor<'c, Database = MySql> {
+    sqlx::query("UPDATE employees SET 
+            email = ?, birth_date = ?, first_name = ?, last_name = ?, gender = ?, hire_date = ?
+        WHERE emp_no = ?")
+    .bind(&employee.email).bind(employee.birth_date)
+    .bind(&employee.first_name).bind(&employee.last_name)
+    .bind(&employee.gender).bind(employee.hire_date)
+    .bind(employee.emp_no)
+    .execute(executor).await
+    .map(|_| ())
+    .map_err(employee_write_error)
+}
+
+/// Soft deletes an employee: marks it deleted, now and by whom. Its row, and its 
+/// ``salaries``, ``titles``, ``dept_emp`` and ``dept_manager`` history, are kept until 
+/// it is purged, see [`deleted_employee`].
+/// 
+/// # Arguments
+/// 
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+/// 
+/// * `emp_no` - the exact ``employees.emp_no``.
+/// 
+/// * `deleted_by` - the email of the logged in user deleting the employee.
+/// 
+/// # Return
+/// 
+/// - ``true`` if an employee was deleted, ``false`` if there is no such employee, or
+///   it is already deleted.
+/// 
+pub async fn delete_employee(
+    pool: &Pool<MySql>,
+    emp_no: i32,
+    deleted_by: &str ) -> bool {
+    sqlx::query("UPDATE employees SET deleted_at = UTC_TIMESTAMP(), deleted_by = ?
+        WHERE emp_no = ? AND deleted_at IS NULL")
+    .bind(deleted_by).bind(emp_no)
+    .execute(pool).await.unwrap()
+    .rows_affected() > 0
+}
+
 /// To these tests below:
 /// 
 ///    * cargo test models::tests
@@ -230,6 +628,9 @@ pub async fn select_employee(
 ///    * cargo test models::tests::test_employee_serde -- --exact
 ///    * cargo test models::tests::test_employee_serde_failure -- --exact
 ///    * cargo test models::tests::test_login_success_response -- --exact
+///    * cargo test models::tests::test_employee_validate -- --exact
+///    * cargo test models::tests::test_new_employee_serde -- --exact
+///    * cargo test models::tests::test_employee_patch -- --exact
 /// 
 #[cfg(test)]
 mod tests {
@@ -253,9 +654,14 @@ mod tests {
         assert_eq!(emp.birth_date, date!(1955 - 12 - 14));
         assert_eq!(emp.hire_date, date!(1985 - 04 - 26));
     
-        let expected_str = String::from("{\n  \"emp_no\": 67115,\n  \"email\": \"siamak.bernardeschi.67115@gmail.com\",\n  \"birth_date\": \"14/12/1955\",\n  \"first_name\": \"Siamak\",\n  \"last_name\": \"Bernardeschi\",\n  \"gender\": \"M\",\n  \"hire_date\": \"26/04/1985\"\n}");
+        let expected_str = String::from("{\n  \"emp_no\": 67115,\n  \"email\": \"siamak.bernardeschi.67115@gmail.com\",\n  \"birth_date\": \"14/12/1955\",\n  \"first_name\": \"Siamak\",\n  \"last_name\": \"Bernardeschi\",\n  \"gender\": \"M\",\n  \"hire_date\": \"26/04/1985\",\n  \"title\": null\n}");
         let serialized = serde_json::to_string_pretty(&emp).unwrap();
         assert_eq!(serialized, expected_str);
+
+        let emp: Employee = serde_json::from_str(&json_str.replace("\"gender\": \"M\",",
+            "\"gender\": \"M\", \"title\": \"Senior Engineer\",")).unwrap();
+        assert_eq!(emp.title.as_deref(), Some("Senior Engineer"));
+        assert!(serde_json::to_string(&emp).unwrap().ends_with(r#""title":"Senior Engineer"}"#));
     }
     
     #[test]
@@ -285,7 +691,8 @@ mod tests {
         let login_success = LoginSuccess {
             email: String::from("behai_nguyen@hotmail.com"),
             access_token: String::from("abcd-efgh-ijkl-mnop"),
-            token_type: String::from(TOKEN_TYPE)
+            token_type: String::from(TOKEN_TYPE),
+            next: Some(String::from("/ui/home")),
         };
 
         let lsr = LoginSuccessResponse {api_status, data: login_success};
@@ -299,5 +706,87 @@ mod tests {
         assert_eq!(lsr_obj.api_status.get_session_id(), None);
         assert_eq!(lsr_obj.data.email, "behai_nguyen@hotmail.com");
         assert_eq!(lsr_obj.data.access_token, "abcd-efgh-ijkl-mnop");    
+        assert_eq!(lsr_obj.data.next, Some(String::from("/ui/home")));
+    }
+
+    fn siamak() -> Employee {
+        Employee {
+            emp_no: 67115,
+            email: String::from("siamak.bernardeschi.67115@gmail.com"),
+            birth_date: date!(1955 - 12 - 14),
+            first_name: String::from("Siamak"),
+            last_name: String::from("Bernardeschi"),
+            gender: String::from("M"),
+            hire_date: date!(1985 - 04 - 26),
+            title: Some(String::from("Senior Engineer")),
+        }
+    }
+
+    #[test]
+    fn test_employee_validate() {
+        assert!(siamak().validate().is_ok());
+
+        let mut employee = Employee { first_name: String::from(" Siamak "), last_name: String::from("Bernardeschi "), ..siamak() };
+        employee.normalize();
+        assert_eq!(employee.first_name, "Siamak");
+        assert_eq!(employee.last_name, "Bernardeschi");
+
+        let employee = Employee {
+            emp_no: 0,
+            email: String::from("siamak.bernardeschi@gmail"),
+            birth_date: date!(1985 - 04 - 26),
+            first_name: String::from(" "),
+            last_name: String::from("Bernardeschi-Bernardeschi"),
+            gender: String::from("X"),
+            hire_date: date!(1985 - 04 - 26),
+            title: None,
+        };
+
+        let errors = employee.validate().unwrap_err();
+        for message in [EMPLOYEE_NO_INVALID_MSG, EMPLOYEE_EMAIL_INVALID_MSG, EMPLOYEE_FIRST_NAME_INVALID_MSG,
+            EMPLOYEE_LAST_NAME_INVALID_MSG, EMPLOYEE_GENDER_INVALID_MSG, EMPLOYEE_DATES_INVALID_MSG] {
+            assert!(errors.contains(message), "Expect: {}", message);
+        }
+
+        for email in ["siamak", "@gmail.com", "siamak@", "siamak@gmail..com", "si amak@gmail.com", "a@b@gmail.com"] {
+            assert!(!is_valid_email(email), "{} should be invalid", email);
+        }
+    }
+
+    #[test]
+    fn test_new_employee_serde() {
+        let json_str = r#"{
+            "emp_no": 67115,
+            "email": "siamak.bernardeschi.67115@gmail.com",
+            "birth_date": "14/12/1955",
+            "first_name": "Siamak",
+            "last_name": "Bernardeschi",
+            "gender": "M",
+            "hire_date": "26/04/1985",
+            "password": "Correct Horse Battery Staple"
+        }"#;
+
+        let new_employee: NewEmployee = serde_json::from_str(json_str).unwrap();
+        assert_eq!(new_employee.employee.hire_date, date!(1985 - 04 - 26));
+        assert_eq!(new_employee.password.as_deref(), Some("Correct Horse Battery Staple"));
+    }
+
+    #[test]
+    fn test_employee_patch() {
+        let patch: EmployeePatch = serde_json::from_str(
+            r#"{"last_name": "Koblick", "hire_date": "01/02/1990"}"#).unwrap();
+
+        let employee = patch.apply(siamak());
+        assert_eq!(employee.emp_no, 67115);
+        assert_eq!(employee.first_name, "Siamak");
+        assert_eq!(employee.last_name, "Koblick");
+        assert_eq!(employee.birth_date, date!(1955 - 12 - 14));
+        assert_eq!(employee.hire_date, date!(1990 - 02 - 01));
+        assert_eq!(employee.title.as_deref(), Some("Senior Engineer"));
+
+        // emp_no can't be changed.
+        assert!(serde_json::from_str::<EmployeePatch>(r#"{"emp_no": 1}"#).is_err());
+        assert!(serde_json::from_str::<EmployeePatch>(r#"{"hire_date": "30/02/1990"}"#).is_err());
+        assert!(serde_json::from_str::<EmployeePatch>(r#"{"hire_date": null}"#).unwrap().hire_date.is_none());
     }
 }
\ No newline at end of file
@@ -0,0 +1,188 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents ``employees.status`` in the database: whether an account may be used.
+//!
+//! The status is checked when logging in, and on every authenticated request by
+//! [`crate::auth_middleware::CheckLogin`], so that a change takes effect immediately.
+//!
+//! * ``active`` -- no restriction.
+//!
+//! * ``disabled`` -- e.g. the employee has left. Can't log in, existing sessions end.
+//!
+//! * ``locked`` -- temporarily blocked, e.g. by an administrator. Can't log in, existing
+//!   sessions end.
+//!
+//! * ``must_change_password`` -- can log in, but can only change password or log out.
+//!   Changing password makes the account ``active``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::account_status::tests
+//!
+
+use std::str::FromStr;
+
+use actix_web::http::StatusCode;
+use sqlx::{Row, Pool, MySql};
+
+use crate::bh_libs::api_status::ApiStatus;
+use crate::helper::messages::{
+    ACCOUNT_DISABLED_MSG,
+    ACCOUNT_LOCKED_MSG,
+    PASSWORD_CHANGE_REQUIRED_MSG
+};
+
+/// ``employees.status`` values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountStatus {
+    Active,
+    Disabled,
+    Locked,
+    MustChangePassword,
+}
+
+impl FromStr for AccountStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "active" => Ok(Self::Active),
+            "disabled" => Ok(Self::Disabled),
+            "locked" => Ok(Self::Locked),
+            "must_change_password" => Ok(Self::MustChangePassword),
+            _ => Err(format!("Unknown account status {}", value)),
+        }
+    }
+}
+
+impl AccountStatus {
+    /// The ``employees.status`` value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "active",
+            Self::Disabled => "disabled",
+            Self::Locked => "locked",
+            Self::MustChangePassword => "must_change_password",
+        }
+    }
+
+    /// Returns ``true`` if the account can log in.
+    pub fn can_login(&self) -> bool {
+        matches!(self, Self::Active | Self::MustChangePassword)
+    }
+
+    /// Returns the response to a request which this status does not allow.
+    ///
+    /// # Return
+    ///
+    /// * ``None`` for ``active``.
+    ///
+    /// * [`ApiStatus`] whose ``code`` is [`StatusCode::FORBIDDEN`] and ``message`` is
+    ///   [`ACCOUNT_DISABLED_MSG`] for ``disabled``.
+    ///
+    /// * [`ApiStatus`] whose ``code`` is [`StatusCode::LOCKED`] and ``message`` is
+    ///   [`ACCOUNT_LOCKED_MSG`] for ``locked``.
+    ///
+    /// * [`ApiStatus`] whose ``code`` is [`StatusCode::FORBIDDEN`] and ``message`` is
+    ///   [`PASSWORD_CHANGE_REQUIRED_MSG`] for ``must_change_password``.
+    ///
+    pub fn api_status(&self) -> Option<ApiStatus> {
+        let (code, message) = match self {
+            Self::Active => return None,
+            Self::Disabled => (StatusCode::FORBIDDEN, ACCOUNT_DISABLED_MSG),
+            Self::Locked => (StatusCode::LOCKED, ACCOUNT_LOCKED_MSG),
+            Self::MustChangePassword => (StatusCode::FORBIDDEN, PASSWORD_CHANGE_REQUIRED_MSG),
+        };
+
+        Some(ApiStatus::new(code.as_u16()).set_message(message))
+    }
+}
+
+/// Returns the status of an account.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `email` - the account email. Accounts without a matching ``employees`` row, e.g.
+///   some LDAP logins, are ``active``. Accounts of deleted employees are ``disabled``,
+///   see [`crate::models::delete_employee`].
+///
+pub async fn select_account_status(
+    pool: &Pool<MySql>,
+    email: &str ) -> AccountStatus {
+    let status: Option<String> = sqlx::query("SELECT IF(deleted_at IS NULL, status, ?) 
+        FROM employees WHERE email = ?")
+    .bind(AccountStatus::Disabled.as_str()).bind(email)
+    .map(|row: sqlx::mysql::MySqlRow| row.get(0))
+    .fetch_optional(pool).await.unwrap();
+
+    match status {
+        // An unknown value in the database must not unlock the account.
+        Some(status) => status.parse::<AccountStatus>().unwrap_or_else(|err| {
+            tracing::error!("{} of {}, treated as disabled", err, email);
+            AccountStatus::Disabled
+        }),
+        None => AccountStatus::Active,
+    }
+}
+
+/// Sets the status of an account.
+pub async fn update_account_status(
+    pool: &Pool<MySql>,
+    email: &str,
+    status: AccountStatus ) {
+    sqlx::query("UPDATE employees SET status = ? WHERE email = ?")
+    .bind(status.as_str()).bind(email)
+    .execute(pool).await.unwrap();
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::account_status::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::account_status::tests::test_from_str -- --exact
+///    * cargo test models::account_status::tests::test_can_login -- --exact
+///    * cargo test models::account_status::tests::test_api_status -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        for status in [AccountStatus::Active, AccountStatus::Disabled,
+            AccountStatus::Locked, AccountStatus::MustChangePassword] {
+            assert_eq!(status.as_str().parse::<AccountStatus>(), Ok(status));
+        }
+
+        assert_eq!(" Locked ".parse::<AccountStatus>(), Ok(AccountStatus::Locked));
+        assert!("suspended".parse::<AccountStatus>().is_err());
+    }
+
+    #[test]
+    fn test_can_login() {
+        assert!(AccountStatus::Active.can_login());
+        assert!(AccountStatus::MustChangePassword.can_login());
+        assert!(!AccountStatus::Disabled.can_login());
+        assert!(!AccountStatus::Locked.can_login());
+    }
+
+    #[test]
+    fn test_api_status() {
+        assert!(AccountStatus::Active.api_status().is_none());
+
+        let api_status = AccountStatus::Disabled.api_status().unwrap();
+        assert_eq!(api_status.get_code(), StatusCode::FORBIDDEN.as_u16());
+        assert_eq!(api_status.get_message().unwrap(), ACCOUNT_DISABLED_MSG);
+
+        let api_status = AccountStatus::Locked.api_status().unwrap();
+        assert_eq!(api_status.get_code(), StatusCode::LOCKED.as_u16());
+        assert_eq!(api_status.get_message().unwrap(), ACCOUNT_LOCKED_MSG);
+
+        let api_status = AccountStatus::MustChangePassword.api_status().unwrap();
+        assert_eq!(api_status.get_code(), StatusCode::FORBIDDEN.as_u16());
+        assert_eq!(api_status.get_message().unwrap(), PASSWORD_CHANGE_REQUIRED_MSG);
+    }
+}
@@ -0,0 +1,325 @@
+/* Date Created: 18/10/2026. */
+
+//! Workforce analytics: aggregates of the ``employees``, ``dept_emp`` and ``titles``
+//! tables.
+//!
+//! * Headcount of each department at the end of each year: employees with a
+//!   ``dept_emp`` row from on or before 31 December, to after it.
+//! * Hires and separations per year. An employee is hired on ``hire_date``, and
+//!   separated on the last ``to_date`` of their ``dept_emp`` rows, when none of them
+//!   is current.
+//! * Gender distribution and average tenure of current employees, per current
+//!   department or per current title. Tenure is from ``hire_date`` to today, in years
+//!   of 365.25 days.
+//!
+//! Deleted employees are left out of all of them, see [`crate::models::delete_employee`].
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::analytics::tests
+//!
+
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use sqlx::{Row, Pool, MySql, QueryBuilder};
+
+use crate::helper::messages::ANALYTICS_YEARS_INVALID_MSG;
+use crate::models::department::CURRENT_TO_DATE;
+
+/// The most years a yearly report may span.
+pub const MAX_ANALYTICS_YEARS: i32 = 100;
+
+/// What current employee analytics are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalyticsGroup {
+    /// Grouped by current ``dept_emp``: ``key`` is ``dept_no``, ``name`` is ``dept_name``.
+    Department,
+    /// Grouped by current ``titles``: ``key`` and ``name`` are the title.
+    Title,
+}
+
+impl AnalyticsGroup {
+    /// Selects ``group_key`` and ``group_name`` of current employees ``e``, followed by
+    /// the ``FROM`` and ``WHERE`` clauses.
+    fn push_current_members(&self, builder: &mut QueryBuilder<'_, MySql>) {
+        match self {
+            Self::Department => builder.push("de.dept_no AS group_key, d.dept_name AS group_name
+                FROM employees e JOIN dept_emp de ON de.emp_no = e.emp_no AND de.to_date = ")
+                .push_bind(CURRENT_TO_DATE)
+                .push(" JOIN departments d ON d.dept_no = de.dept_no"),
+            Self::Title => builder.push("t.title AS group_key, t.title AS group_name
+                FROM employees e JOIN titles t ON t.emp_no = e.emp_no AND t.to_date = ")
+                .push_bind(CURRENT_TO_DATE),
+        };
+
+        builder.push(" WHERE e.deleted_at IS NULL");
+    }
+}
+
+/// The query string of analytics routes. ``format`` is ``json`` or ``csv``, see
+/// [`crate::helper::csv_utils::ReportFormat`].
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnalyticsQuery {
+    /// Yearly reports only. Defaults to the year of the earliest ``dept_emp`` row.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_year: Option<i32>,
+    /// Yearly reports only. Defaults to the year of the latest ``dept_emp`` change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_year: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl AnalyticsQuery {
+    /// Resolves the years of a yearly report, defaulting to ``data_years``.
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - [`ANALYTICS_YEARS_INVALID_MSG`] followed by the years, if
+    ///   ``from_year`` is after ``to_year``, or they cover more than
+    ///   [`MAX_ANALYTICS_YEARS`] years.
+    ///
+    pub fn resolve_years(&self, data_years: (i32, i32)) -> Result<(i32, i32), String> {
+        let from_year = self.from_year.unwrap_or(data_years.0);
+        let to_year = self.to_year.unwrap_or(data_years.1);
+
+        if from_year > to_year || to_year - from_year >= MAX_ANALYTICS_YEARS {
+            return Err(format!("{} {}-{}", ANALYTICS_YEARS_INVALID_MSG, from_year, to_year));
+        }
+
+        Ok((from_year, to_year))
+    }
+}
+
+/// The headcount of a department at the end of a year.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Headcount {
+    pub year: i32,
+    pub dept_no: String,
+    pub dept_name: String,
+    pub headcount: i64,
+}
+
+/// The numbers of hires and separations in a year.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HiresSeparations {
+    pub year: i32,
+    pub hires: i64,
+    pub separations: i64,
+}
+
+/// The gender distribution of the current employees of a group, see [`AnalyticsGroup`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenderDistribution {
+    pub key: String,
+    pub name: String,
+    pub employees: i64,
+    pub female: i64,
+    pub male: i64,
+}
+
+/// The average tenure of the current employees of a group, see [`AnalyticsGroup`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Tenure {
+    pub key: String,
+    pub name: String,
+    pub employees: i64,
+    /// Rounded to 2 decimal places.
+    pub average_years: Decimal,
+}
+
+/// Starts a query with the ``years`` CTE: ``year`` from ``from_year`` to ``to_year``.
+fn years_builder<'a>(from_year: i32, to_year: i32) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("WITH RECURSIVE years (year) AS (SELECT CAST(");
+    builder.push_bind(from_year)
+        .push(" AS SIGNED) UNION ALL SELECT year + 1 FROM years WHERE year < ")
+        .push_bind(to_year)
+        .push(")");
+
+    builder
+}
+
+fn headcount_builder<'a>(from_year: i32, to_year: i32) -> QueryBuilder<'a, MySql> {
+    let mut builder = years_builder(from_year, to_year);
+    builder.push(" SELECT y.year, d.dept_no, d.dept_name, COUNT(de.emp_no)
+        FROM years y CROSS JOIN departments d
+        LEFT JOIN (dept_emp de JOIN employees e ON e.emp_no = de.emp_no AND e.deleted_at IS NULL)
+        ON de.dept_no = d.dept_no
+        AND de.from_date <= MAKEDATE(y.year + 1, 1) - INTERVAL 1 DAY
+        AND de.to_date > MAKEDATE(y.year + 1, 1) - INTERVAL 1 DAY
+        GROUP BY y.year, d.dept_no, d.dept_name ORDER BY y.year, d.dept_no");
+
+    builder
+}
+
+fn hires_separations_builder<'a>(from_year: i32, to_year: i32) -> QueryBuilder<'a, MySql> {
+    let mut builder = years_builder(from_year, to_year);
+    builder.push(", hires AS (SELECT YEAR(hire_date) AS year, COUNT(*) AS hires
+            FROM employees WHERE deleted_at IS NULL GROUP BY YEAR(hire_date)),
+        separations AS (SELECT YEAR(last_to_date) AS year, COUNT(*) AS separations
+            FROM (SELECT MAX(de.to_date) AS last_to_date FROM dept_emp de
+                JOIN employees e ON e.emp_no = de.emp_no WHERE e.deleted_at IS NULL GROUP BY de.emp_no) last
+            WHERE last_to_date <> ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" GROUP BY YEAR(last_to_date))
+        SELECT y.year, COALESCE(h.hires, 0), COALESCE(s.separations, 0)
+        FROM years y LEFT JOIN hires h ON h.year = y.year LEFT JOIN separations s ON s.year = y.year
+        ORDER BY y.year");
+
+    builder
+}
+
+/// Makes a query of current employee metrics: ``metrics`` columns, then ``group_key``
+/// and ``group_name``.
+fn group_metrics_builder<'a>(metrics: &str, group: AnalyticsGroup) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new(format!("SELECT {}, ", metrics));
+    group.push_current_members(&mut builder);
+    builder.push(" GROUP BY group_key, group_name ORDER BY group_key");
+
+    builder
+}
+
+const GENDER_METRICS: &str = "COUNT(*), CAST(SUM(e.gender = 'F') AS SIGNED), CAST(SUM(e.gender = 'M') AS SIGNED)";
+
+const TENURE_METRICS: &str = "COUNT(*), CAST(AVG(DATEDIFF(CURDATE(), e.hire_date)) / 365.25 AS DECIMAL(8, 2))";
+
+/// Reads the default years of yearly reports: of the earliest ``dept_emp`` row, and of
+/// the latest ``dept_emp`` change, i.e. the latest ``from_date`` or past ``to_date``.
+pub async fn select_data_years(pool: &Pool<MySql>) -> (i32, i32) {
+    sqlx::query("SELECT CAST(YEAR(MIN(from_date)) AS SIGNED),
+        CAST(YEAR(MAX(CASE WHEN to_date = ? THEN from_date ELSE to_date END)) AS SIGNED) FROM dept_emp")
+    .bind(CURRENT_TO_DATE)
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let from_year: Option<i64> = row.get(0);
+        let to_year: Option<i64> = row.get(1);
+        (from_year.unwrap_or(0) as i32, to_year.unwrap_or(0) as i32)
+    })
+    .fetch_one(pool).await.unwrap()
+}
+
+/// Retrieves the headcount of each department at the end of each year, ordered by
+/// ``year`` then ``dept_no``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `years` - from and to years, inclusive, see [`AnalyticsQuery::resolve_years`].
+///
+pub async fn select_headcount(
+    pool: &Pool<MySql>,
+    years: (i32, i32)
+) -> Vec<Headcount> {
+    headcount_builder(years.0, years.1).build()
+    .map(|row: sqlx::mysql::MySqlRow| Headcount {
+        year: row.get::<i64, _>(0) as i32,
+        dept_no: row.get(1),
+        dept_name: row.get(2),
+        headcount: row.get(3),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// Retrieves the numbers of hires and separations of each year, ordered by ``year``.
+/// See [`select_headcount`] for the arguments.
+pub async fn select_hires_separations(
+    pool: &Pool<MySql>,
+    years: (i32, i32)
+) -> Vec<HiresSeparations> {
+    hires_separations_builder(years.0, years.1).build()
+    .map(|row: sqlx::mysql::MySqlRow| HiresSeparations {
+        year: row.get::<i64, _>(0) as i32,
+        hires: row.get(1),
+        separations: row.get(2),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// Retrieves the gender distribution of current employees per group, ordered by ``key``.
+pub async fn select_gender_distribution(
+    pool: &Pool<MySql>,
+    group: AnalyticsGroup
+) -> Vec<GenderDistribution> {
+    group_metrics_builder(GENDER_METRICS, group).build()
+    .map(|row: sqlx::mysql::MySqlRow| GenderDistribution {
+        employees: row.get(0),
+        female: row.get(1),
+        male: row.get(2),
+        key: row.get(3),
+        name: row.get(4),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// Retrieves the average tenure of current employees per group, ordered by ``key``.
+pub async fn select_tenure(
+    pool: &Pool<MySql>,
+    group: AnalyticsGroup
+) -> Vec<Tenure> {
+    group_metrics_builder(TENURE_METRICS, group).build()
+    .map(|row: sqlx::mysql::MySqlRow| Tenure {
+        employees: row.get(0),
+        average_years: row.get(1),
+        key: row.get(2),
+        name: row.get(3),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::analytics::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::analytics::tests::test_resolve_years -- --exact
+///    * cargo test models::analytics::tests::test_yearly_sql -- --exact
+///    * cargo test models::analytics::tests::test_group_metrics_sql -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_years() {
+        let query = AnalyticsQuery::default();
+        assert_eq!(query.resolve_years((1985, 2002)), Ok((1985, 2002)));
+
+        let query = AnalyticsQuery { from_year: Some(1990), ..Default::default() };
+        assert_eq!(query.resolve_years((1985, 2002)), Ok((1990, 2002)));
+
+        let query = AnalyticsQuery { from_year: Some(2000), to_year: Some(2000), format: None };
+        assert_eq!(query.resolve_years((1985, 2002)), Ok((2000, 2000)));
+
+        let query = AnalyticsQuery { from_year: Some(2003), ..Default::default() };
+        assert_eq!(query.resolve_years((1985, 2002)),
+            Err(format!("{} 2003-2002", ANALYTICS_YEARS_INVALID_MSG)));
+
+        let query = AnalyticsQuery { from_year: Some(1900), ..Default::default() };
+        assert_eq!(query.resolve_years((1985, 2002)),
+            Err(format!("{} 1900-2002", ANALYTICS_YEARS_INVALID_MSG)));
+    }
+
+    #[test]
+    fn test_yearly_sql() {
+        let sql = String::from(headcount_builder(1985, 2002).sql());
+        assert!(sql.starts_with("WITH RECURSIVE years (year) AS (SELECT CAST(? AS SIGNED) \
+            UNION ALL SELECT year + 1 FROM years WHERE year < ?) SELECT y.year, d.dept_no"));
+
+        let sql = String::from(hires_separations_builder(1985, 2002).sql());
+        assert_eq!(sql.matches('?').count(), 3);
+        assert!(sql.contains("WHERE last_to_date <> ? GROUP BY YEAR(last_to_date))"));
+    }
+
+    #[test]
+    fn test_group_metrics_sql() {
+        let sql = String::from(group_metrics_builder(GENDER_METRICS, AnalyticsGroup::Title).sql());
+        assert!(sql.starts_with("SELECT COUNT(*), CAST(SUM(e.gender = 'F') AS SIGNED), \
+            CAST(SUM(e.gender = 'M') AS SIGNED), t.title AS group_key, t.title AS group_name"));
+        assert!(sql.ends_with("JOIN titles t ON t.emp_no = e.emp_no AND t.to_date = ? \
+            WHERE e.deleted_at IS NULL GROUP BY group_key, group_name ORDER BY group_key"));
+
+        let sql = String::from(group_metrics_builder(TENURE_METRICS, AnalyticsGroup::Department).sql());
+        assert!(sql.contains("JOIN dept_emp de ON de.emp_no = e.emp_no AND de.to_date = ? \
+            JOIN departments d ON d.dept_no = de.dept_no WHERE e.deleted_at IS NULL GROUP BY"));
+    }
+}
@@ -0,0 +1,199 @@
+/* Date Created: 18/10/2026. */
+
+//! Soft deleted employees: their list, their restore, and their purge.
+//!
+//! [`crate::models::delete_employee`] marks an employee deleted, with ``deleted_at`` and
+//! ``deleted_by``, rather than deleting its row: its ``salaries``, ``titles`` and
+//! ``dept_emp`` history is kept. Deleted employees are excluded from the other queries,
+//! until they are restored. After ``DELETED_EMPLOYEES_DAYS_RETAINED`` days, they are
+//! purged for good, history included, by the command
+//! ``learn_actix_web purge-deleted-employees``, see [`crate::cli`].
+//!
+//! A deleted employee's ``emp_no`` and ``email`` stay taken until it is purged.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::deleted_employee::tests
+//!
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sqlx::{FromRow, Pool, MySql, QueryBuilder};
+use sqlx::types::time::PrimitiveDateTime;
+
+use crate::bh_libs::australian_date::australian_date_time_format;
+use crate::helper::pagination::{Page, PageInfo};
+
+/// A deleted employee, as admins list them.
+#[derive(FromRow, Debug, Serialize, Deserialize)]
+pub struct DeletedEmployee {
+    pub emp_no: i32,
+    pub email: String,
+    pub first_name: String,
+    pub last_name: String,
+    /// UTC.
+    #[serde(with = "australian_date_time_format")]
+    pub deleted_at: PrimitiveDateTime,
+    /// The email of who deleted the employee.
+    pub deleted_by: Option<String>,
+}
+
+/// Whether a cursor was made by [`select_deleted_employees`]: its row key is the
+/// ``emp_no``.
+pub fn is_deleted_employees_page(page: &Page) -> bool {
+    page.key().is_none_or(|key| key.len() == 1 && key[0].is_i64())
+}
+
+/// Makes the query reading a page of deleted employees, and one more row to know if
+/// there are more.
+fn deleted_employees_builder<'a>(page: &Page) -> QueryBuilder<'a, MySql> {
+    let mut builder = QueryBuilder::new("SELECT emp_no, email, first_name, last_name, deleted_at, deleted_by
+        FROM employees WHERE deleted_at IS NOT NULL");
+
+    let limit = page.limit() as u64 + 1;
+
+    match page {
+        Page::Offset { offset, .. } => {
+            builder.push(" ORDER BY emp_no ASC LIMIT ").push_bind(limit)
+                .push(" OFFSET ").push_bind(*offset);
+        }
+        Page::After { key, .. } => {
+            builder.push(" AND emp_no > ").push_bind(key[0].as_i64())
+                .push(" ORDER BY emp_no ASC LIMIT ").push_bind(limit);
+        }
+        Page::Before { key, .. } => {
+            builder.push(" AND emp_no < ").push_bind(key[0].as_i64())
+                .push(" ORDER BY emp_no DESC LIMIT ").push_bind(limit);
+        }
+    }
+
+    builder
+}
+
+/// Retrieves a page of the deleted employees, ordered by ``emp_no``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `page` - the requested page, see [`is_deleted_employees_page`].
+///
+/// # Return
+///
+/// - The rows of the page, and its [`PageInfo`].
+///
+pub async fn select_deleted_employees(
+    pool: &Pool<MySql>,
+    page: &Page
+) -> (Vec<DeletedEmployee>, PageInfo) {
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM employees WHERE deleted_at IS NOT NULL")
+        .fetch_one(pool).await.unwrap();
+
+    let mut employees: Vec<DeletedEmployee> = deleted_employees_builder(page)
+        .build_query_as()
+        .fetch_all(pool).await.unwrap();
+
+    let has_more = employees.len() > page.limit() as usize;
+    employees.truncate(page.limit() as usize);
+
+    if let Page::Before { .. } = page {
+        employees.reverse();
+    }
+
+    let row_key = |employee: &DeletedEmployee| vec![Value::from(employee.emp_no)];
+    let info = page.info(total as u64,
+        employees.first().map(row_key),
+        employees.last().map(row_key),
+        has_more);
+
+    (employees, info)
+}
+
+/// Restores a deleted employee: it is no longer marked deleted.
+///
+/// # Return
+///
+/// - ``true`` if an employee was restored, ``false`` if there is no such deleted
+///   employee.
+///
+pub async fn restore_employee(
+    pool: &Pool<MySql>,
+    emp_no: i32 ) -> bool {
+    sqlx::query("UPDATE employees SET deleted_at = NULL, deleted_by = NULL
+        WHERE emp_no = ? AND deleted_at IS NOT NULL")
+    .bind(emp_no)
+    .execute(pool).await.unwrap()
+    .rows_affected() > 0
+}
+
+/// Deletes for good the employees deleted more than ``days_retained`` days ago. Their
+/// ``salaries``, ``titles``, ``dept_emp`` and ``dept_manager`` rows go with them, by the
+/// schema's cascading foreign keys.
+///
+/// # Return
+///
+/// - The number of employees purged.
+///
+pub async fn purge_deleted_employees(
+    pool: &Pool<MySql>,
+    days_retained: u32 ) -> u64 {
+    sqlx::query("DELETE FROM employees
+        WHERE deleted_at IS NOT NULL AND deleted_at < UTC_TIMESTAMP() - INTERVAL ? DAY")
+    .bind(days_retained)
+    .execute(pool).await.unwrap()
+    .rows_affected()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::deleted_employee::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::deleted_employee::tests::test_is_deleted_employees_page -- --exact
+///    * cargo test models::deleted_employee::tests::test_deleted_employees_sql -- --exact
+///    * cargo test models::deleted_employee::tests::test_deleted_employee_serde -- --exact
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use time::macros::datetime;
+    use super::*;
+
+    #[test]
+    fn test_is_deleted_employees_page() {
+        assert!(is_deleted_employees_page(&Page::Offset { limit: 10, offset: 0 }));
+        assert!(is_deleted_employees_page(&Page::After { limit: 10, key: vec![json!(600001)] }));
+        assert!(!is_deleted_employees_page(&Page::Before { limit: 10, key: vec![json!("Koblick"), json!(10004)] }));
+    }
+
+    #[test]
+    fn test_deleted_employees_sql() {
+        let select = "SELECT emp_no, email, first_name, last_name, deleted_at, deleted_by
+        FROM employees WHERE deleted_at IS NOT NULL";
+
+        assert_eq!(deleted_employees_builder(&Page::Offset { limit: 10, offset: 20 }).sql(),
+            format!("{} ORDER BY emp_no ASC LIMIT ? OFFSET ?", select));
+        assert_eq!(deleted_employees_builder(&Page::After { limit: 10, key: vec![json!(600001)] }).sql(),
+            format!("{} AND emp_no > ? ORDER BY emp_no ASC LIMIT ?", select));
+        assert_eq!(deleted_employees_builder(&Page::Before { limit: 10, key: vec![json!(600001)] }).sql(),
+            format!("{} AND emp_no < ? ORDER BY emp_no DESC LIMIT ?", select));
+    }
+
+    #[test]
+    fn test_deleted_employee_serde() {
+        let employee = DeletedEmployee {
+            emp_no: 600001,
+            email: String::from("siamak.bernardeschi.600001@gmail.com"),
+            first_name: String::from("Siamak"),
+            last_name: String::from("Bernardeschi"),
+            deleted_at: datetime!(2026-10-18 09:05:30),
+            deleted_by: Some(String::from("parto.bamford.10003@gmail.com")),
+        };
+
+        let json = serde_json::to_string(&employee).unwrap();
+        assert_eq!(json, r#"{"emp_no":600001,"email":"siamak.bernardeschi.600001@gmail.com","first_name":"Siamak","last_name":"Bernardeschi","deleted_at":"18/10/2026 09:05:30","deleted_by":"parto.bamford.10003@gmail.com"}"#);
+
+        let employee: DeletedEmployee = serde_json::from_str(&json).unwrap();
+        assert_eq!(employee.deleted_at, datetime!(2026-10-18 09:05:30));
+    }
+}
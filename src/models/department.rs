@@ -0,0 +1,407 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents the ``departments``, ``dept_emp`` and ``dept_manager`` tables in the
+//! database.
+//!
+//! ``dept_emp`` and ``dept_manager`` rows are effective dated: from ``from_date`` to
+//! ``to_date``. The current rows have ``to_date`` of [`CURRENT_TO_DATE`].
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::department::tests
+//!
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sqlx::{Row, Pool, MySql, QueryBuilder};
+use sqlx::types::time::Date;
+use time::macros::date;
+
+use crate::bh_libs::australian_date::australian_date_format;
+use crate::helper::pagination::{Page, PageInfo};
+
+/// ``to_date`` of current ``dept_emp``, ``dept_manager``, ``salaries`` and ``titles`` rows.
+pub const CURRENT_TO_DATE: Date = date!(9999 - 01 - 01);
+
+/// Represents the ``departments`` table.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Department {
+    pub dept_no: String,
+    pub dept_name: String,
+}
+
+/// A department, with its current number of employees and its managers.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepartmentDetail {
+    #[serde(flatten)]
+    pub department: Department,
+    pub current_employees: i64,
+    /// All managers, the current one last.
+    pub managers: Vec<DepartmentEmployee>,
+}
+
+/// An employee's ``dept_emp`` or ``dept_manager`` row in a department.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepartmentEmployee {
+    pub emp_no: i32,
+    pub first_name: String,
+    pub last_name: String,
+    #[serde(with = "australian_date_format")]
+    pub from_date: Date,
+    #[serde(with = "australian_date_format")]
+    pub to_date: Date,
+    pub current: bool,
+}
+
+/// An employee's ``dept_emp`` row, i.e. membership of a department.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DepartmentMembership {
+    pub dept_no: String,
+    pub dept_name: String,
+    #[serde(with = "australian_date_format")]
+    pub from_date: Date,
+    #[serde(with = "australian_date_format")]
+    pub to_date: Date,
+    pub current: bool,
+}
+
+/// The query string of department membership routes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MembershipQuery {
+    /// ``true`` for current memberships only, ``false`` to include past memberships. 
+    /// The default depends on the route.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current: Option<bool>,
+}
+
+/// Retrieves all departments, ordered by ``dept_no``.
+pub async fn select_departments(pool: &Pool<MySql>) -> Vec<Department> {
+    sqlx::query("SELECT dept_no, dept_name FROM departments ORDER BY dept_no")
+    .map(|row: sqlx::mysql::MySqlRow| Department {
+        dept_no: row.get(0),
+        dept_name: row.get(1),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// Retrieves a department with its current number of employees and its managers.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `dept_no` - e.g. ``d005``.
+///
+/// # Return
+///
+/// * ``None`` if there is no such department.
+///
+pub async fn select_department(
+    pool: &Pool<MySql>,
+    dept_no: &str
+) -> Option<DepartmentDetail> {
+    let department = sqlx::query("SELECT dept_no, dept_name FROM departments WHERE dept_no = ?")
+    .bind(dept_no)
+    .map(|row: sqlx::mysql::MySqlRow| Department {
+        dept_no: row.get(0),
+        dept_name: row.get(1),
+    })
+    .fetch_optional(pool).await.unwrap()?;
+
+    let current_employees: i64 = sqlx::query("SELECT COUNT(*) FROM dept_emp de 
+        JOIN employees e ON e.emp_no = de.emp_no 
+        WHERE de.dept_no = ? AND de.to_date = ? AND e.deleted_at IS NULL")
+    .bind(dept_no).bind(CURRENT_TO_DATE)
+    .map(|row: sqlx::mysql::MySqlRow| row.get(0))
+    .fetch_one(pool).await.unwrap();
+
+    let managers = sqlx::query("SELECT dm.emp_no, e.first_name, e.last_name, dm.from_date, dm.to_date
+        FROM dept_manager dm JOIN employees e ON e.emp_no = dm.emp_no
+        WHERE dm.dept_no = ? AND e.deleted_at IS NULL ORDER BY dm.from_date, dm.emp_no")
+    .bind(dept_no)
+    .map(department_employee)
+    .fetch_all(pool).await.unwrap();
+
+    Some(DepartmentDetail { department, current_employees, managers })
+}
+
+fn department_employee(row: sqlx::mysql::MySqlRow) -> DepartmentEmployee {
+    let to_date: Date = row.get(4);
+
+    DepartmentEmployee {
+        emp_no: row.get(0),
+        first_name: row.get(1),
+        last_name: row.get(2),
+        from_date: row.get(3),
+        to_date,
+        current: to_date == CURRENT_TO_DATE,
+    }
+}
+
+/// Retrieves the departments an employee belongs, or has belonged, to, ordered by
+/// ``from_date``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `emp_no` - the employee number.
+///
+/// * `current_only` - ``true`` for the current departments only.
+///
+pub async fn select_employee_departments(
+    pool: &Pool<MySql>,
+    emp_no: i32,
+    current_only: bool
+) -> Vec<DepartmentMembership> {
+    select_employees_departments(pool, &[emp_no], current_only).await
+        .remove(&emp_no).unwrap_or_default()
+}
+
+/// Makes the query reading the department memberships of employees: rows of ``emp_no``,
+/// ``dept_no``, ``dept_name``, ``from_date`` and ``to_date``.
+fn employees_departments_builder<'a>(emp_nos: &[i32], current_only: bool) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT de.emp_no, de.dept_no, d.dept_name,
+        de.from_date, de.to_date FROM dept_emp de JOIN departments d ON d.dept_no = de.dept_no
+        WHERE de.emp_no IN (");
+    let mut separated = builder.separated(", ");
+    for emp_no in emp_nos {
+        separated.push_bind(*emp_no);
+    }
+    separated.push_unseparated(")");
+
+    if current_only {
+        builder.push(" AND de.to_date = ").push_bind(CURRENT_TO_DATE);
+    }
+    builder.push(" ORDER BY de.emp_no, de.from_date, de.dept_no");
+
+    builder
+}
+
+/// Retrieves the departments of employees, each ordered by ``from_date``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `emp_nos` - the employee numbers.
+///
+/// * `current_only` - ``true`` for the current departments only.
+///
+/// # Return
+///
+/// - The memberships by ``emp_no``. Employees without any are not in it.
+///
+pub async fn select_employees_departments(
+    pool: &Pool<MySql>,
+    emp_nos: &[i32],
+    current_only: bool
+) -> HashMap<i32, Vec<DepartmentMembership>> {
+    let mut departments: HashMap<i32, Vec<DepartmentMembership>> = HashMap::new();
+    if emp_nos.is_empty() {
+        return departments;
+    }
+
+    let rows = employees_departments_builder(emp_nos, current_only).build()
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let to_date: Date = row.get(4);
+
+        (row.get::<i32, _>(0), DepartmentMembership {
+            dept_no: row.get(1),
+            dept_name: row.get(2),
+            from_date: row.get(3),
+            to_date,
+            current: to_date == CURRENT_TO_DATE,
+        })
+    })
+    .fetch_all(pool).await.unwrap();
+
+    for (emp_no, membership) in rows {
+        departments.entry(emp_no).or_default().push(membership);
+    }
+    departments
+}
+
+/// Whether a cursor was made by [`select_department_employees`]: its row key is the
+/// ``emp_no``.
+pub fn is_department_employees_page(page: &Page) -> bool {
+    page.key().is_none_or(|key| key.len() == 1 && key[0].is_i64())
+}
+
+/// Makes the query reading a page of the employees of a department, and one more row
+/// to know if there are more.
+fn department_employees_builder<'a>(
+    dept_no: &'a str,
+    current_only: bool,
+    page: &Page
+) -> QueryBuilder<'a, MySql> {
+    let mut builder = QueryBuilder::new("SELECT de.emp_no, e.first_name, e.last_name, de.from_date, de.to_date
+        FROM dept_emp de JOIN employees e ON e.emp_no = de.emp_no WHERE e.deleted_at IS NULL AND de.dept_no = ");
+    builder.push_bind(dept_no);
+
+    if current_only {
+        builder.push(" AND de.to_date = ").push_bind(CURRENT_TO_DATE);
+    }
+
+    let limit = page.limit() as u64 + 1;
+
+    match page {
+        Page::Offset { offset, .. } => {
+            builder.push(" ORDER BY de.emp_no ASC LIMIT ").push_bind(limit)
+                .push(" OFFSET ").push_bind(*offset);
+        }
+        Page::After { key, .. } => {
+            builder.push(" AND de.emp_no > ").push_bind(key[0].as_i64())
+                .push(" ORDER BY de.emp_no ASC LIMIT ").push_bind(limit);
+        }
+        Page::Before { key, .. } => {
+            builder.push(" AND de.emp_no < ").push_bind(key[0].as_i64())
+                .push(" ORDER BY de.emp_no DESC LIMIT ").push_bind(limit);
+        }
+    }
+
+    builder
+}
+
+/// Retrieves a page of the employees of a department, ordered by ``emp_no``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `dept_no` - e.g. ``d005``.
+///
+/// * `current_only` - ``false`` to include former employees of the department.
+///
+/// * `page` - the requested page, see [`is_department_employees_page`].
+///
+/// # Return
+///
+/// - The rows of the page, and its [`PageInfo`].
+///
+pub async fn select_department_employees(
+    pool: &Pool<MySql>,
+    dept_no: &str,
+    current_only: bool,
+    page: &Page
+) -> (Vec<DepartmentEmployee>, PageInfo) {
+    let mut count: QueryBuilder<MySql> = QueryBuilder::new("SELECT COUNT(*) FROM dept_emp de 
+        JOIN employees e ON e.emp_no = de.emp_no WHERE e.deleted_at IS NULL AND de.dept_no = ");
+    count.push_bind(dept_no);
+    if current_only {
+        count.push(" AND de.to_date = ").push_bind(CURRENT_TO_DATE);
+    }
+
+    let total: i64 = count.build_query_scalar().fetch_one(pool).await.unwrap();
+
+    let mut employees = department_employees_builder(dept_no, current_only, page)
+        .build()
+        .map(department_employee)
+        .fetch_all(pool).await.unwrap();
+
+    let has_more = employees.len() > page.limit() as usize;
+    employees.truncate(page.limit() as usize);
+
+    if let Page::Before { .. } = page {
+        employees.reverse();
+    }
+
+    let row_key = |employee: &DepartmentEmployee| vec![Value::from(employee.emp_no)];
+    let info = page.info(total as u64,
+        employees.first().map(row_key),
+        employees.last().map(row_key),
+        has_more);
+
+    (employees, info)
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::department::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::department::tests::test_is_department_employees_page -- --exact
+///    * cargo test models::department::tests::test_department_employees_sql -- --exact
+///    * cargo test models::department::tests::test_employees_departments_sql -- --exact
+///    * cargo test models::department::tests::test_department_detail_serde -- --exact
+///    * cargo test models::department::tests::test_membership_query -- --exact
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    #[test]
+    fn test_is_department_employees_page() {
+        assert!(is_department_employees_page(&Page::Offset { limit: 10, offset: 0 }));
+        assert!(is_department_employees_page(&Page::After { limit: 10, key: vec![json!(10004)] }));
+        assert!(!is_department_employees_page(&Page::After { limit: 10, key: vec![json!("10004")] }));
+        assert!(!is_department_employees_page(&Page::Before { limit: 10, key: vec![json!("Koblick"), json!(10004)] }));
+    }
+
+    #[test]
+    fn test_department_employees_sql() {
+        let builder = department_employees_builder("d005", true, &Page::Offset { limit: 10, offset: 20 });
+        assert_eq!(builder.sql(), "SELECT de.emp_no, e.first_name, e.last_name, de.from_date, de.to_date
+        FROM dept_emp de JOIN employees e ON e.emp_no = de.emp_no WHERE e.deleted_at IS NULL AND de.dept_no = ? \
+        AND de.to_date = ? ORDER BY de.emp_no ASC LIMIT ? OFFSET ?");
+
+        let builder = department_employees_builder("d005", false, &Page::Before { limit: 10, key: vec![json!(10004)] });
+        assert!(builder.sql().ends_with("WHERE e.deleted_at IS NULL AND de.dept_no = ? AND de.emp_no < ? ORDER BY de.emp_no DESC LIMIT ?"));
+    }
+
+    #[test]
+    fn test_employees_departments_sql() {
+        let builder = employees_departments_builder(&[10004, 10005], true);
+        assert!(builder.sql().ends_with("WHERE de.emp_no IN (?, ?) AND de.to_date = ? \
+            ORDER BY de.emp_no, de.from_date, de.dept_no"));
+
+        let builder = employees_departments_builder(&[10004], false);
+        assert!(builder.sql().ends_with("WHERE de.emp_no IN (?) ORDER BY de.emp_no, de.from_date, de.dept_no"));
+    }
+
+    #[test]
+    fn test_department_detail_serde() {
+        let detail = DepartmentDetail {
+            department: Department { dept_no: String::from("d005"), dept_name: String::from("Development") },
+            current_employees: 61386,
+            managers: vec![DepartmentEmployee {
+                emp_no: 110567,
+                first_name: String::from("Leon"),
+                last_name: String::from("DasSarma"),
+                from_date: date!(1992 - 04 - 25),
+                to_date: CURRENT_TO_DATE,
+                current: true,
+            }],
+        };
+
+        assert_eq!(serde_json::to_value(&detail).unwrap(), json!({
+            "dept_no": "d005",
+            "dept_name": "Development",
+            "current_employees": 61386,
+            "managers": [{
+                "emp_no": 110567,
+                "first_name": "Leon",
+                "last_name": "DasSarma",
+                "from_date": "25/04/1992",
+                "to_date": "01/01/9999",
+                "current": true
+            }]
+        }));
+    }
+
+    #[test]
+    fn test_membership_query() {
+        let query: MembershipQuery = serde_urlencoded::from_str("current=false&limit=10").unwrap();
+        assert_eq!(query.current, Some(false));
+        assert_eq!(serde_urlencoded::to_string(&query).unwrap(), "current=false");
+
+        let query: MembershipQuery = serde_urlencoded::from_str("").unwrap();
+        assert_eq!(query.current, None);
+        assert_eq!(serde_urlencoded::to_string(&query).unwrap(), "");
+
+        assert!(serde_urlencoded::from_str::<MembershipQuery>("current=yes").is_err());
+    }
+}
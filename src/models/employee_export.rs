@@ -0,0 +1,279 @@
+/* Date Created: 18/10/2026. */
+
+//! CSV of employees, one record per [`Employee`], and the XML of a page of employees.
+//!
+//! Records are RFC 4180: comma separated, ``CRLF`` terminated, and fields are quoted
+//! only when needed. Dates are formatted as [`australian_date_format`], e.g.
+//! ``26/06/1986``. Records are written one at a time, so that they can be streamed as
+//! the rows are read.
+//!
+//! The XML elements are named as the CSV columns, and the page is attributes of the
+//! root element. E.g. ``<employees total="2" limit="50"><employee><emp_no>10004</emp_no>``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::employee_export::tests
+//!
+
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+
+use crate::bh_libs::australian_date::australian_date_format;
+use crate::helper::content_negotiation::xml_escape;
+use crate::helper::pagination::PageInfo;
+use crate::helper::messages::{EXPORT_COLUMN_INVALID_MSG, EXPORT_COLUMN_DUPLICATE_MSG};
+use super::Employee;
+
+/// Columns of the CSV, named as the [`Employee`] fields.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportColumn {
+    EmpNo,
+    Email,
+    BirthDate,
+    FirstName,
+    LastName,
+    Gender,
+    HireDate,
+    Title,
+}
+
+impl FromStr for ExportColumn {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|column| column.name() == value)
+            .ok_or_else(|| format!("{} {}", EXPORT_COLUMN_INVALID_MSG, value))
+    }
+}
+
+impl ExportColumn {
+    /// All columns, in the default order.
+    pub const ALL: [ExportColumn; 8] = [
+        Self::EmpNo, Self::Email, Self::BirthDate, Self::FirstName,
+        Self::LastName, Self::Gender, Self::HireDate, Self::Title,
+    ];
+
+    /// The column name, in the header record.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EmpNo => "emp_no",
+            Self::Email => "email",
+            Self::BirthDate => "birth_date",
+            Self::FirstName => "first_name",
+            Self::LastName => "last_name",
+            Self::Gender => "gender",
+            Self::HireDate => "hire_date",
+            Self::Title => "title",
+        }
+    }
+
+    /// The field of an employee. Empty for no title.
+    pub fn value(&self, employee: &Employee) -> String {
+        match self {
+            Self::EmpNo => employee.emp_no.to_string(),
+            Self::Email => employee.email.clone(),
+            Self::BirthDate => australian_date_format::format(&employee.birth_date),
+            Self::FirstName => employee.first_name.clone(),
+            Self::LastName => employee.last_name.clone(),
+            Self::Gender => employee.gender.clone(),
+            Self::HireDate => australian_date_format::format(&employee.hire_date),
+            Self::Title => employee.title.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// The query string of CSV exports, besides the search criteria.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ExportOptions {
+    /// Comma separated column names, in order. E.g. ``emp_no,last_name,hire_date``.
+    /// Defaults to [`ExportColumn::ALL`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub columns: Option<String>,
+    /// Whether to write the header record. Defaults to ``true``.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<bool>,
+}
+
+impl ExportOptions {
+    /// Parses ``columns``.
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - [`EXPORT_COLUMN_INVALID_MSG`] or [`EXPORT_COLUMN_DUPLICATE_MSG`]
+    ///   followed by the column name.
+    ///
+    pub fn resolve_columns(&self) -> Result<Vec<ExportColumn>, String> {
+        let mut columns: Vec<ExportColumn> = Vec::new();
+
+        for name in self.columns.as_deref().unwrap_or_default().split(',')
+            .map(str::trim).filter(|name| !name.is_empty()) {
+            let column = name.parse::<ExportColumn>()?;
+
+            if columns.contains(&column) {
+                return Err(format!("{} {}", EXPORT_COLUMN_DUPLICATE_MSG, name));
+            }
+            columns.push(column);
+        }
+
+        match columns.is_empty() {
+            true => Ok(ExportColumn::ALL.to_vec()),
+            false => Ok(columns),
+        }
+    }
+
+    pub fn header(&self) -> bool {
+        self.header.unwrap_or(true)
+    }
+}
+
+/// Writes CSV records of employees, returning the bytes of each record as written.
+pub struct EmployeeCsvWriter {
+    columns: Vec<ExportColumn>,
+    builder: csv::WriterBuilder,
+}
+
+impl EmployeeCsvWriter {
+    pub fn new(columns: Vec<ExportColumn>) -> Self {
+        let mut builder = csv::WriterBuilder::new();
+        builder.terminator(csv::Terminator::CRLF).buffer_capacity(512);
+
+        EmployeeCsvWriter { columns, builder }
+    }
+
+    /// The header record: the column names.
+    pub fn header(&self) -> Vec<u8> {
+        let names: Vec<&str> = self.columns.iter().map(ExportColumn::name).collect();
+        self.write(&names)
+    }
+
+    /// The record of an employee.
+    pub fn record(&self, employee: &Employee) -> Vec<u8> {
+        let values: Vec<String> = self.columns.iter().map(|column| column.value(employee)).collect();
+        self.write(&values)
+    }
+
+    fn write<T: AsRef<[u8]>>(&self, fields: &[T]) -> Vec<u8> {
+        let mut writer = self.builder.from_writer(Vec::new());
+
+        // Writing to memory does not fail.
+        writer.write_record(fields).unwrap();
+        writer.into_inner().unwrap()
+    }
+}
+
+/// The XML document of a page of employees, with an element per column. Empty elements
+/// are written for no title.
+pub fn employees_xml(employees: &[Employee], columns: &[ExportColumn], info: &PageInfo) -> String {
+    let mut attributes = vec![
+        format!("total=\"{}\"", info.total),
+        format!("limit=\"{}\"", info.limit),
+    ];
+    if let Some(offset) = info.offset {
+        attributes.push(format!("offset=\"{}\"", offset));
+    }
+    if let Some(cursor) = &info.next_cursor {
+        attributes.push(format!("next_cursor=\"{}\"", xml_escape(cursor)));
+    }
+    if let Some(cursor) = &info.prev_cursor {
+        attributes.push(format!("prev_cursor=\"{}\"", xml_escape(cursor)));
+    }
+
+    let mut xml = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<employees {}>\n",
+        attributes.join(" "));
+
+    for employee in employees {
+        xml.push_str("  <employee>");
+        for column in columns {
+            xml.push_str(&format!("<{0}>{1}</{0}>", column.name(), xml_escape(&column.value(employee))));
+        }
+        xml.push_str("</employee>\n");
+    }
+
+    xml.push_str("</employees>\n");
+    xml
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::employee_export::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::employee_export::tests::test_resolve_columns -- --exact
+///    * cargo test models::employee_export::tests::test_employee_csv_writer -- --exact
+///    * cargo test models::employee_export::tests::test_employees_xml -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use super::*;
+
+    #[test]
+    fn test_resolve_columns() {
+        assert_eq!(ExportOptions::default().resolve_columns(), Ok(ExportColumn::ALL.to_vec()));
+
+        let options: ExportOptions = serde_urlencoded::from_str("columns=last_name,+emp_no&header=false").unwrap();
+        assert_eq!(options.resolve_columns(), Ok(vec![ExportColumn::LastName, ExportColumn::EmpNo]));
+        assert!(!options.header());
+
+        let options = ExportOptions { columns: Some(String::from("emp_no,salary")), header: None };
+        assert_eq!(options.resolve_columns(), Err(format!("{} salary", EXPORT_COLUMN_INVALID_MSG)));
+        assert!(options.header());
+
+        let options = ExportOptions { columns: Some(String::from("email,email")), header: None };
+        assert_eq!(options.resolve_columns(), Err(format!("{} email", EXPORT_COLUMN_DUPLICATE_MSG)));
+    }
+
+    #[test]
+    fn test_employee_csv_writer() {
+        let employee = Employee {
+            emp_no: 10004,
+            email: String::from("chirstian.koblick.10004@gmail.com"),
+            birth_date: date!(1954 - 05 - 01),
+            first_name: String::from("Chirstian"),
+            last_name: String::from("Koblick, \"Jr\""),
+            gender: String::from("M"),
+            hire_date: date!(1986 - 12 - 01),
+            title: None,
+        };
+
+        let writer = EmployeeCsvWriter::new(ExportColumn::ALL.to_vec());
+        assert_eq!(writer.header(), b"emp_no,email,birth_date,first_name,last_name,gender,hire_date,title\r\n");
+        assert_eq!(String::from_utf8(writer.record(&employee)).unwrap(),
+            "10004,chirstian.koblick.10004@gmail.com,01/05/1954,Chirstian,\"Koblick, \"\"Jr\"\"\",M,01/12/1986,\r\n");
+
+        let writer = EmployeeCsvWriter::new(vec![ExportColumn::HireDate, ExportColumn::EmpNo]);
+        assert_eq!(writer.record(&employee), b"01/12/1986,10004\r\n");
+    }
+
+    #[test]
+    fn test_employees_xml() {
+        let employee = Employee {
+            emp_no: 10004,
+            email: String::from("chirstian.koblick.10004@gmail.com"),
+            birth_date: date!(1954 - 05 - 01),
+            first_name: String::from("Chirstian"),
+            last_name: String::from("Koblick & <Jr>"),
+            gender: String::from("M"),
+            hire_date: date!(1986 - 12 - 01),
+            title: Some(String::from("Engineer")),
+        };
+        let info = PageInfo { total: 2, limit: 1, offset: None,
+            next_cursor: Some(String::from("5b2261222c31303030345d")), prev_cursor: None };
+
+        assert_eq!(employees_xml(std::slice::from_ref(&employee), &ExportColumn::ALL, &info), "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <employees total=\"2\" limit=\"1\" next_cursor=\"5b2261222c31303030345d\">\n  \
+            <employee><emp_no>10004</emp_no><email>chirstian.koblick.10004@gmail.com</email>\
+            <birth_date>01/05/1954</birth_date><first_name>Chirstian</first_name>\
+            <last_name>Koblick &amp; &lt;Jr&gt;</last_name><gender>M</gender>\
+            <hire_date>01/12/1986</hire_date><title>Engineer</title></employee>\n\
+            </employees>\n");
+
+        let xml = employees_xml(&[employee], &[ExportColumn::LastName, ExportColumn::EmpNo], &info);
+        assert!(xml.contains("<employee><last_name>Koblick &amp; &lt;Jr&gt;</last_name><emp_no>10004</emp_no></employee>"));
+
+        let info = PageInfo { total: 0, limit: 50, offset: Some(0), next_cursor: None, prev_cursor: None };
+        assert_eq!(employees_xml(&[], &ExportColumn::ALL, &info), "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+            <employees total=\"0\" limit=\"50\" offset=\"0\">\n</employees>\n");
+    }
+}
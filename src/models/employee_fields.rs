@@ -0,0 +1,291 @@
+/* Date Created: 18/10/2026. */
+
+//! Sparse fieldsets and embedded relations of employee responses.
+//!
+//! ``fields`` selects the [`Employee`] fields serialised, in order, e.g.
+//! ``fields=emp_no,first_name,last_name``. They are named as the CSV columns, see
+//! [`ExportColumn`]. ``include`` embeds the histories of related rows, e.g.
+//! ``include=departments,titles``: each employee gets an array per relation, as the
+//! ``/employees/{emp_no}/departments``, ``titles`` and ``salaries`` routes return.
+//!
+//! The histories of a page of employees are read by one query per relation.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::employee_fields::tests
+//!
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize, Serializer};
+use serde::ser::SerializeMap;
+use sqlx::{Pool, MySql};
+
+use crate::helper::messages::{
+    FIELD_UNKNOWN_MSG, FIELD_DUPLICATE_MSG, INCLUDE_UNKNOWN_MSG, INCLUDE_DUPLICATE_MSG
+};
+use super::Employee;
+use super::employee_export::ExportColumn;
+use super::department::{DepartmentMembership, select_employees_departments};
+use super::salary::{Salary, select_employees_salaries};
+use super::title::{EmployeeTitle, select_employees_titles};
+
+/// Relations which can be embedded in employees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmployeeInclude {
+    Departments,
+    Titles,
+    Salaries,
+}
+
+impl EmployeeInclude {
+    pub const ALL: [EmployeeInclude; 3] = [Self::Departments, Self::Titles, Self::Salaries];
+
+    /// The name in ``include``, and of the embedded array.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Departments => "departments",
+            Self::Titles => "titles",
+            Self::Salaries => "salaries",
+        }
+    }
+}
+
+/// The query string of employee responses, besides the search criteria.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FieldsQuery {
+    /// Comma separated field names, in order. E.g. ``emp_no,first_name,last_name``.
+    /// Defaults to all fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<String>,
+    /// Comma separated relations: ``departments``, ``titles`` or ``salaries``.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<String>,
+}
+
+/// Splits a comma separated list of names, and finds each among ``all`` by its name.
+/// Unknown and repeated names are refused.
+fn parse_list<T: Copy + PartialEq>(
+    value: Option<&str>,
+    all: &[T],
+    name_of: fn(&T) -> &'static str,
+    unknown_msg: &str,
+    duplicate_msg: &str
+) -> Result<Vec<T>, String> {
+    let mut items: Vec<T> = Vec::new();
+
+    for name in value.unwrap_or_default().split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        let item = *all.iter().find(|item| name_of(item) == name)
+            .ok_or_else(|| format!("{} {}", unknown_msg, name))?;
+
+        if items.contains(&item) {
+            return Err(format!("{} {}", duplicate_msg, name));
+        }
+        items.push(item);
+    }
+
+    Ok(items)
+}
+
+impl FieldsQuery {
+    /// Parses ``fields`` and ``include``.
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - [`FIELD_UNKNOWN_MSG`], [`FIELD_DUPLICATE_MSG`],
+    ///   [`INCLUDE_UNKNOWN_MSG`] or [`INCLUDE_DUPLICATE_MSG`] followed by the name.
+    ///
+    pub fn resolve(&self) -> Result<FieldSet, String> {
+        let columns = parse_list(self.fields.as_deref(), &ExportColumn::ALL, ExportColumn::name,
+            FIELD_UNKNOWN_MSG, FIELD_DUPLICATE_MSG)?;
+        let includes = parse_list(self.include.as_deref(), &EmployeeInclude::ALL, EmployeeInclude::name,
+            INCLUDE_UNKNOWN_MSG, INCLUDE_DUPLICATE_MSG)?;
+
+        Ok(FieldSet {
+            columns: match columns.is_empty() {
+                true => ExportColumn::ALL.to_vec(),
+                false => columns,
+            },
+            includes,
+        })
+    }
+
+    /// The URL encoded query string, for the links to other pages.
+    pub fn query_string(&self) -> String {
+        serde_urlencoded::to_string(self).unwrap()
+    }
+}
+
+/// The resolved [`FieldsQuery`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSet {
+    pub columns: Vec<ExportColumn>,
+    pub includes: Vec<EmployeeInclude>,
+}
+
+impl FieldSet {
+    pub fn includes(&self, include: EmployeeInclude) -> bool {
+        self.includes.contains(&include)
+    }
+}
+
+/// The histories of the relations of a [`FieldSet`], by ``emp_no``.
+#[derive(Debug, Default)]
+pub struct EmployeeRelations {
+    departments: Option<HashMap<i32, Vec<DepartmentMembership>>>,
+    titles: Option<HashMap<i32, Vec<EmployeeTitle>>>,
+    salaries: Option<HashMap<i32, Vec<Salary>>>,
+}
+
+impl EmployeeRelations {
+    /// Reads the included histories of employees. Nothing is read without ``includes``.
+    pub async fn select(pool: &Pool<MySql>, emp_nos: &[i32], fields: &FieldSet) -> Self {
+        let mut relations = EmployeeRelations::default();
+
+        if fields.includes(EmployeeInclude::Departments) {
+            relations.departments = Some(select_employees_departments(pool, emp_nos, false).await);
+        }
+        if fields.includes(EmployeeInclude::Titles) {
+            relations.titles = Some(select_employees_titles(pool, emp_nos).await);
+        }
+        if fields.includes(EmployeeInclude::Salaries) {
+            relations.salaries = Some(select_employees_salaries(pool, emp_nos).await);
+        }
+
+        relations
+    }
+
+    /// The sparse employee, with its included histories.
+    pub fn sparse<'a>(&'a self, employee: &'a Employee, fields: &'a FieldSet) -> SparseEmployee<'a> {
+        let emp_no = employee.emp_no;
+
+        SparseEmployee {
+            employee,
+            columns: &fields.columns,
+            departments: self.departments.as_ref()
+                .map(|departments| departments.get(&emp_no).map_or(&[][..], Vec::as_slice)),
+            titles: self.titles.as_ref()
+                .map(|titles| titles.get(&emp_no).map_or(&[][..], Vec::as_slice)),
+            salaries: self.salaries.as_ref()
+                .map(|salaries| salaries.get(&emp_no).map_or(&[][..], Vec::as_slice)),
+        }
+    }
+}
+
+/// An employee serialised with only the selected fields, in their order, followed by
+/// the included histories. Fields are serialised as [`Employee`] serialises them.
+#[derive(Debug)]
+pub struct SparseEmployee<'a> {
+    employee: &'a Employee,
+    columns: &'a [ExportColumn],
+    departments: Option<&'a [DepartmentMembership]>,
+    titles: Option<&'a [EmployeeTitle]>,
+    salaries: Option<&'a [Salary]>,
+}
+
+impl Serialize for SparseEmployee<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+
+        for column in self.columns {
+            match column {
+                ExportColumn::EmpNo => map.serialize_entry(column.name(), &self.employee.emp_no)?,
+                ExportColumn::Title => map.serialize_entry(column.name(), &self.employee.title)?,
+                _ => map.serialize_entry(column.name(), &column.value(self.employee))?,
+            }
+        }
+
+        if let Some(departments) = self.departments {
+            map.serialize_entry(EmployeeInclude::Departments.name(), departments)?;
+        }
+        if let Some(titles) = self.titles {
+            map.serialize_entry(EmployeeInclude::Titles.name(), titles)?;
+        }
+        if let Some(salaries) = self.salaries {
+            map.serialize_entry(EmployeeInclude::Salaries.name(), salaries)?;
+        }
+
+        map.end()
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::employee_fields::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::employee_fields::tests::test_fields_query_resolve -- --exact
+///    * cargo test models::employee_fields::tests::test_sparse_employee_serde -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use crate::models::department::CURRENT_TO_DATE;
+    use super::*;
+
+    fn employee() -> Employee {
+        Employee {
+            emp_no: 10004,
+            email: String::from("chirstian.koblick.10004@gmail.com"),
+            birth_date: date!(1954 - 05 - 01),
+            first_name: String::from("Chirstian"),
+            last_name: String::from("Koblick"),
+            gender: String::from("M"),
+            hire_date: date!(1986 - 12 - 01),
+            title: None,
+        }
+    }
+
+    #[test]
+    fn test_fields_query_resolve() {
+        let fields = FieldsQuery::default().resolve().unwrap();
+        assert_eq!(fields.columns, ExportColumn::ALL.to_vec());
+        assert!(fields.includes.is_empty());
+
+        let query: FieldsQuery = serde_urlencoded::from_str("fields=last_name,+emp_no&include=titles").unwrap();
+        let fields = query.resolve().unwrap();
+        assert_eq!(fields.columns, vec![ExportColumn::LastName, ExportColumn::EmpNo]);
+        assert_eq!(fields.includes, vec![EmployeeInclude::Titles]);
+        assert!(!fields.includes(EmployeeInclude::Salaries));
+        assert_eq!(query.query_string(), "fields=last_name%2C+emp_no&include=titles");
+
+        let query = FieldsQuery { fields: Some(String::from("emp_no,salary")), include: None };
+        assert_eq!(query.resolve(), Err(format!("{} salary", FIELD_UNKNOWN_MSG)));
+
+        let query = FieldsQuery { fields: Some(String::from("email,email")), include: None };
+        assert_eq!(query.resolve(), Err(format!("{} email", FIELD_DUPLICATE_MSG)));
+
+        let query = FieldsQuery { fields: None, include: Some(String::from("manager")) };
+        assert_eq!(query.resolve(), Err(format!("{} manager", INCLUDE_UNKNOWN_MSG)));
+
+        let query = FieldsQuery { fields: None, include: Some(String::from("titles,titles")) };
+        assert_eq!(query.resolve(), Err(format!("{} titles", INCLUDE_DUPLICATE_MSG)));
+    }
+
+    #[test]
+    fn test_sparse_employee_serde() {
+        let employee = employee();
+
+        // All fields: as the employee.
+        let fields = FieldsQuery::default().resolve().unwrap();
+        let relations = EmployeeRelations::default();
+        assert_eq!(serde_json::to_string(&relations.sparse(&employee, &fields)).unwrap(),
+            serde_json::to_string(&employee).unwrap());
+
+        let fields = FieldSet {
+            columns: vec![ExportColumn::EmpNo, ExportColumn::HireDate, ExportColumn::Title],
+            includes: vec![EmployeeInclude::Titles, EmployeeInclude::Salaries],
+        };
+        let mut titles = HashMap::new();
+        titles.insert(10004, vec![EmployeeTitle {
+            title: String::from("Engineer"),
+            from_date: date!(1986 - 12 - 01),
+            to_date: CURRENT_TO_DATE,
+            current: true,
+        }]);
+        let relations = EmployeeRelations { departments: None, titles: Some(titles), salaries: Some(HashMap::new()) };
+
+        assert_eq!(serde_json::to_string(&relations.sparse(&employee, &fields)).unwrap(),
+            r#"{"emp_no":10004,"hire_date":"01/12/1986","title":null,"titles":[{"title":"Engineer","from_date":"01/12/1986","to_date":"01/01/9999","current":true}],"salaries":[]}"#);
+    }
+}
@@ -0,0 +1,511 @@
+/* Date Created: 18/10/2026. */
+
+//! Bulk import of employees from CSV: the upload
+//! [`crate::employee_csv_handlers::import_employees_csv`], and the ``import-employees``
+//! command, see [`crate::cli`].
+//!
+//! The CSV is as exported, see [`super::employee_export`]: a header record of column
+//! names, in any order. All [`ExportColumn`]s but ``title`` are required. ``title`` is
+//! read only, it is ignored, so that an export can be imported. Dates are parsed by
+//! [`australian_date_format::parse`].
+//!
+//! Every row is checked, and every problem found is reported against its row: fields
+//! as for [`Employee::validate`], ``emp_no`` and ``email`` repeated within the CSV,
+//! and ``emp_no`` and ``email`` already taken, that is ``PRIMARY`` and ``email_unique``.
+//!
+//! A dry run only checks. A commit inserts all rows in one transaction, and only if
+//! every row is valid: an import is never partially written.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::employee_import::tests
+//!
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::str::FromStr;
+
+use sqlx::{Pool, MySql, QueryBuilder};
+use serde::{Serialize, Deserialize};
+
+use crate::bh_libs::australian_date::australian_date_format;
+use crate::helper::password_utils::{hash_password, random_token};
+use crate::helper::messages::{
+    IMPORT_MODE_INVALID_MSG,
+    IMPORT_CSV_INVALID_MSG,
+    IMPORT_CSV_EMPTY_MSG,
+    IMPORT_CSV_TOO_LARGE_MSG,
+    IMPORT_COLUMN_INVALID_MSG,
+    IMPORT_COLUMN_DUPLICATE_MSG,
+    IMPORT_COLUMN_MISSING_MSG,
+    IMPORT_DATE_INVALID_MSG,
+    IMPORT_EMP_NO_REPEATED_MSG,
+    IMPORT_EMAIL_REPEATED_MSG,
+    EMPLOYEE_NO_EXISTS_MSG,
+    EMPLOYEE_EMAIL_EXISTS_MSG
+};
+use super::employee_export::ExportColumn;
+use super::{Employee, insert_employee};
+
+/// The maximum number of employee records in an import.
+pub const MAX_IMPORT_ROWS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportMode {
+    /// Only checks, nothing is written.
+    DryRun,
+    /// Checks, then inserts all rows in one transaction if every row is valid.
+    Commit,
+}
+
+impl FromStr for ImportMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "dry-run" => Ok(Self::DryRun),
+            "commit" => Ok(Self::Commit),
+            _ => Err(String::from(IMPORT_MODE_INVALID_MSG)),
+        }
+    }
+}
+
+impl ImportMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::DryRun => "dry-run",
+            Self::Commit => "commit",
+        }
+    }
+}
+
+/// The query string of an import upload.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportQuery {
+    /// ``dry-run`` or ``commit``. Defaults to ``dry-run``.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+impl ImportQuery {
+    pub fn mode(&self) -> Result<ImportMode, String> {
+        self.mode.as_deref().map_or(Ok(ImportMode::DryRun), str::parse)
+    }
+}
+
+/// The problems found in a CSV record.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImportRowError {
+    /// The number of the record in the CSV: the first after the header record is 1.
+    /// Blank lines are not counted.
+    pub row: u64,
+    /// ``None`` if the record has no valid ``emp_no``.
+    pub emp_no: Option<i32>,
+    pub errors: Vec<String>,
+}
+
+/// The result of an import.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// ``dry-run`` or ``commit``.
+    pub mode: String,
+    /// The number of employee records in the CSV.
+    pub rows: usize,
+    /// The number of employees inserted: ``0`` for a dry run, or when there are errors.
+    pub imported: usize,
+    /// The ``emp_no`` of each employee inserted.
+    pub emp_nos: Vec<i32>,
+    /// Ordered by ``row``. Empty if every record is valid.
+    pub errors: Vec<ImportRowError>,
+}
+
+/// A CSV record, and the problems found so far. ``employee`` is ``None`` if the record
+/// could not be read at all.
+#[derive(Debug)]
+struct ImportRow {
+    row: u64,
+    employee: Option<Employee>,
+    errors: Vec<String>,
+}
+
+impl ImportRow {
+    fn error(&self) -> Option<ImportRowError> {
+        match self.errors.is_empty() {
+            true => None,
+            false => Some(ImportRowError {
+                row: self.row,
+                emp_no: self.employee.as_ref().map(|employee| employee.emp_no).filter(|emp_no| *emp_no > 0),
+                errors: self.errors.clone(),
+            }),
+        }
+    }
+}
+
+/// The columns of the CSV, in order, from the header record.
+struct ImportColumns(Vec<ExportColumn>);
+
+impl ImportColumns {
+    fn from_header(header: &csv::StringRecord) -> Result<Self, String> {
+        let mut columns: Vec<ExportColumn> = Vec::new();
+
+        for name in header.iter() {
+            let column = name.parse::<ExportColumn>()
+                .map_err(|_| format!("{} {}", IMPORT_COLUMN_INVALID_MSG, name))?;
+
+            if columns.contains(&column) {
+                return Err(format!("{} {}", IMPORT_COLUMN_DUPLICATE_MSG, name));
+            }
+            columns.push(column);
+        }
+
+        match ExportColumn::ALL.into_iter()
+            .find(|column| *column != ExportColumn::Title && !columns.contains(column)) {
+            Some(column) => Err(format!("{} {}", IMPORT_COLUMN_MISSING_MSG, column.name())),
+            None => Ok(ImportColumns(columns)),
+        }
+    }
+
+    /// The field of a required column.
+    fn field<'r>(&self, record: &'r csv::StringRecord, column: ExportColumn) -> &'r str {
+        let index = self.0.iter().position(|c| *c == column).unwrap();
+        record.get(index).unwrap_or_default()
+    }
+
+    fn date_field(&self, record: &csv::StringRecord, column: ExportColumn,
+        errors: &mut Vec<String>) -> Option<sqlx::types::time::Date> {
+        let date = australian_date_format::parse(self.field(record, column));
+
+        if date.is_none() {
+            errors.push(format!("{} {}", IMPORT_DATE_INVALID_MSG, column.name()));
+        }
+        date
+    }
+
+    fn row(&self, record: &csv::StringRecord) -> ImportRow {
+        let mut errors: Vec<String> = Vec::new();
+
+        let birth_date = self.date_field(record, ExportColumn::BirthDate, &mut errors);
+        let hire_date = self.date_field(record, ExportColumn::HireDate, &mut errors);
+
+        let mut employee = Employee {
+            // Not a number is reported as not positive, by validation.
+            emp_no: self.field(record, ExportColumn::EmpNo).parse().unwrap_or_default(),
+            email: self.field(record, ExportColumn::Email).to_string(),
+            // An invalid date is reported as such, not also as out of order.
+            birth_date: birth_date.unwrap_or(sqlx::types::time::Date::MIN),
+            first_name: self.field(record, ExportColumn::FirstName).to_string(),
+            last_name: self.field(record, ExportColumn::LastName).to_string(),
+            gender: self.field(record, ExportColumn::Gender).to_string(),
+            hire_date: hire_date.unwrap_or(sqlx::types::time::Date::MAX),
+            title: None,
+        };
+        employee.normalize();
+
+        errors.extend(employee.validation_errors().into_iter().map(String::from));
+
+        ImportRow {
+            row: record.position().map_or(0, csv::Position::record),
+            employee: Some(employee),
+            errors,
+        }
+    }
+}
+
+/// Reads the CSV, and checks each record on its own.
+///
+/// # Return
+///
+/// * ``Err(String)`` - the CSV as a whole can't be imported: no or invalid header
+///   record, no records, or more than [`MAX_IMPORT_ROWS`]. E.g.
+///   [`IMPORT_COLUMN_MISSING_MSG`] followed by the column name.
+///
+fn parse_employees(data: &[u8]) -> Result<Vec<ImportRow>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data);
+
+    let header = reader.headers().map_err(|err| format!("{} {}", IMPORT_CSV_INVALID_MSG, err))?;
+    if header.is_empty() {
+        return Err(String::from(IMPORT_CSV_EMPTY_MSG));
+    }
+
+    let columns = ImportColumns::from_header(header)?;
+
+    let mut rows: Vec<ImportRow> = Vec::new();
+
+    for record in reader.records() {
+        if rows.len() == MAX_IMPORT_ROWS {
+            return Err(format!("{} {}", IMPORT_CSV_TOO_LARGE_MSG, MAX_IMPORT_ROWS));
+        }
+
+        rows.push(match record {
+            Ok(record) => columns.row(&record),
+            Err(err) => ImportRow {
+                row: err.position().map_or(0, csv::Position::record),
+                employee: None,
+                errors: vec![format!("{} {}", IMPORT_CSV_INVALID_MSG, err)],
+            },
+        });
+    }
+
+    match rows.is_empty() {
+        true => Err(String::from(IMPORT_CSV_EMPTY_MSG)),
+        false => Ok(rows),
+    }
+}
+
+/// Reports an ``emp_no`` or ``email`` which repeats that of an earlier record. Emails
+/// are compared case insensitively, as ``email_unique`` does. An ``emp_no`` which is
+/// not positive, e.g. not a number, is already reported as invalid, and is skipped.
+fn check_repeats(rows: &mut [ImportRow]) {
+    let mut emp_nos: HashMap<i32, u64> = HashMap::new();
+    let mut emails: HashMap<String, u64> = HashMap::new();
+
+    for row in rows.iter_mut() {
+        let Some(employee) = &row.employee else { continue };
+
+        if employee.emp_no > 0 {
+            match emp_nos.entry(employee.emp_no) {
+                Entry::Occupied(entry) => row.errors.push(format!("{} {}", IMPORT_EMP_NO_REPEATED_MSG, entry.get())),
+                Entry::Vacant(entry) => { entry.insert(row.row); }
+            }
+        }
+
+        match emails.entry(employee.email.to_lowercase()) {
+            Entry::Occupied(entry) => row.errors.push(format!("{} {}", IMPORT_EMAIL_REPEATED_MSG, entry.get())),
+            Entry::Vacant(entry) => { entry.insert(row.row); }
+        }
+    }
+}
+
+/// Reports an ``emp_no`` or ``email`` already in the ``employees`` table.
+async fn check_taken(pool: &Pool<MySql>, rows: &mut [ImportRow]) {
+    let employees: Vec<&Employee> = rows.iter().filter_map(|row| row.employee.as_ref()).collect();
+
+    if employees.is_empty() {
+        return;
+    }
+
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT emp_no, email FROM employees WHERE emp_no IN (");
+    {
+        let mut separated = builder.separated(", ");
+        for employee in &employees {
+            separated.push_bind(employee.emp_no);
+        }
+    }
+    builder.push(") OR email IN (");
+    {
+        let mut separated = builder.separated(", ");
+        for employee in &employees {
+            separated.push_bind(&employee.email);
+        }
+    }
+    builder.push(")");
+
+    let taken: Vec<(i32, String)> = builder.build_query_as().fetch_all(pool).await.unwrap();
+
+    let taken_emp_nos: Vec<i32> = taken.iter().map(|(emp_no, _)| *emp_no).collect();
+    let taken_emails: Vec<String> = taken.iter().map(|(_, email)| email.to_lowercase()).collect();
+
+    for row in rows.iter_mut() {
+        let Some(employee) = &row.employee else { continue };
+
+        if taken_emp_nos.contains(&employee.emp_no) {
+            row.errors.push(String::from(EMPLOYEE_NO_EXISTS_MSG));
+        }
+        if taken_emails.contains(&employee.email.to_lowercase()) {
+            row.errors.push(String::from(EMPLOYEE_EMAIL_EXISTS_MSG));
+        }
+    }
+}
+
+/// Inserts all rows in one transaction. Should another request take an ``emp_no`` or
+/// ``email`` since they were checked, the transaction is rolled back, and the row is
+/// reported.
+async fn insert_employees(pool: &Pool<MySql>, rows: &[ImportRow]) -> Result<(), ImportRowError> {
+    let mut transaction = pool.begin().await.unwrap();
+
+    for row in rows {
+        let Some(employee) = &row.employee else { continue };
+
+        // As for a new employee without a password: a random one nobody knows, hashed
+        // per row so no two imported employees share a hash.
+        let password_hash = hash_password(&random_token(32));
+
+        if let Err(api_status) = insert_employee(&mut *transaction, employee, &password_hash).await {
+            // Dropping the transaction rolls it back.
+            return Err(ImportRowError {
+                row: row.row,
+                emp_no: Some(employee.emp_no),
+                errors: vec![api_status.get_message().unwrap_or_default()],
+            });
+        }
+    }
+
+    transaction.commit().await.unwrap();
+
+    Ok(())
+}
+
+/// Imports employees from CSV.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `data` - the CSV, with a header record.
+///
+/// * `mode` - see [`ImportMode`].
+///
+/// # Return
+///
+/// * ``Ok(ImportReport)`` - the per row errors, if any, and the number of employees
+///   inserted.
+///
+/// * ``Err(String)`` - the CSV as a whole can't be imported, e.g.
+///   [`IMPORT_COLUMN_INVALID_MSG`] followed by the column name.
+///
+pub async fn import_employees(
+    pool: &Pool<MySql>,
+    data: &[u8],
+    mode: ImportMode
+) -> Result<ImportReport, String> {
+    let mut rows = parse_employees(data)?;
+
+    check_repeats(&mut rows);
+    check_taken(pool, &mut rows).await;
+
+    let mut report = ImportReport {
+        mode: mode.name().to_string(),
+        rows: rows.len(),
+        imported: 0,
+        emp_nos: Vec::new(),
+        errors: rows.iter().filter_map(ImportRow::error).collect(),
+    };
+
+    if mode == ImportMode::Commit && report.errors.is_empty() {
+        match insert_employees(pool, &rows).await {
+            Ok(()) => {
+                report.imported = rows.len();
+                report.emp_nos = rows.iter().filter_map(|row| row.employee.as_ref())
+                    .map(|employee| employee.emp_no).collect();
+            }
+            Err(error) => report.errors.push(error),
+        }
+    }
+
+    Ok(report)
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::employee_import::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::employee_import::tests::test_import_mode -- --exact
+///    * cargo test models::employee_import::tests::test_parse_employees -- --exact
+///    * cargo test models::employee_import::tests::test_parse_employees_failure -- --exact
+///    * cargo test models::employee_import::tests::test_check_repeats -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use crate::helper::messages::{
+        EMPLOYEE_NO_INVALID_MSG, EMPLOYEE_GENDER_INVALID_MSG, EMPLOYEE_DATES_INVALID_MSG
+    };
+    use super::*;
+
+    const HEADER: &str = "emp_no,email,birth_date,first_name,last_name,gender,hire_date";
+
+    #[test]
+    fn test_import_mode() {
+        assert_eq!(ImportQuery::default().mode(), Ok(ImportMode::DryRun));
+
+        let query: ImportQuery = serde_urlencoded::from_str("mode=commit").unwrap();
+        assert_eq!(query.mode(), Ok(ImportMode::Commit));
+        assert_eq!(query.mode().unwrap().name(), "commit");
+
+        let query = ImportQuery { mode: Some(String::from("force")) };
+        assert_eq!(query.mode(), Err(String::from(IMPORT_MODE_INVALID_MSG)));
+    }
+
+    #[test]
+    fn test_parse_employees() {
+        let csv = "\u{feff}hire_date, emp_no,email,birth_date,first_name,last_name,gender,title\r\n\
+            26/04/1985,600001, siamak.bernardeschi.600001@gmail.com ,14/12/1955,Siamak,Bernardeschi,M,Engineer\r\n\
+            \r\n\
+            31/02/1985,x,not-an-email,14/12/1955,Siamak,Bernardeschi,X,\r\n\
+            26/04/1985,600003,siamak@gmail.com,26/04/1985,Siamak,Bernardeschi,M,\r\n\
+            26/04/1985,600004\r\n";
+
+        let rows = parse_employees(csv.as_bytes()).unwrap();
+        assert_eq!(rows.len(), 4);
+
+        // Fields are trimmed, title is ignored.
+        assert_eq!(rows[0].row, 1);
+        assert!(rows[0].errors.is_empty());
+        let employee = rows[0].employee.as_ref().unwrap();
+        assert_eq!(employee.emp_no, 600001);
+        assert_eq!(employee.email, "siamak.bernardeschi.600001@gmail.com");
+        assert_eq!(employee.birth_date, date!(1955 - 12 - 14));
+        assert_eq!(employee.hire_date, date!(1985 - 04 - 26));
+        assert_eq!(employee.title, None);
+        assert!(rows[0].error().is_none());
+
+        // Blank lines are skipped.
+        assert_eq!(rows[1].row, 2);
+        assert_eq!(rows[1].errors.len(), 4);
+        assert_eq!(rows[1].errors[0], format!("{} hire_date", IMPORT_DATE_INVALID_MSG));
+        assert!(rows[1].errors.contains(&String::from(EMPLOYEE_NO_INVALID_MSG)));
+        assert!(rows[1].errors.contains(&String::from(EMPLOYEE_GENDER_INVALID_MSG)));
+        assert_eq!(rows[1].error().unwrap().emp_no, None);
+
+        assert_eq!(rows[2].errors, vec![String::from(EMPLOYEE_DATES_INVALID_MSG)]);
+        assert_eq!(rows[2].error().unwrap(), ImportRowError {
+            row: 3, emp_no: Some(600003), errors: vec![String::from(EMPLOYEE_DATES_INVALID_MSG)]
+        });
+
+        assert_eq!(rows[3].row, 4);
+        assert!(rows[3].employee.is_none());
+        assert!(rows[3].errors[0].starts_with(IMPORT_CSV_INVALID_MSG));
+    }
+
+    #[test]
+    fn test_parse_employees_failure() {
+        let message = |csv: &str| parse_employees(csv.as_bytes()).unwrap_err();
+
+        assert_eq!(message(""), IMPORT_CSV_EMPTY_MSG);
+        assert_eq!(message(HEADER), IMPORT_CSV_EMPTY_MSG);
+        assert_eq!(message(&format!("{},salary\n", HEADER)), format!("{} salary", IMPORT_COLUMN_INVALID_MSG));
+        assert_eq!(message(&format!("{},email\n", HEADER)), format!("{} email", IMPORT_COLUMN_DUPLICATE_MSG));
+        assert_eq!(message("emp_no,email,birth_date,first_name,last_name,gender\n"),
+            format!("{} hire_date", IMPORT_COLUMN_MISSING_MSG));
+
+        let record = "10001,georgi.facello.10001@gmail.com,02/09/1953,Georgi,Facello,M,26/06/1986\n";
+        assert_eq!(message(&format!("{}\n{}", HEADER, record.repeat(MAX_IMPORT_ROWS + 1))),
+            format!("{} {}", IMPORT_CSV_TOO_LARGE_MSG, MAX_IMPORT_ROWS));
+        assert_eq!(parse_employees(format!("{}\n{}", HEADER, record.repeat(MAX_IMPORT_ROWS)).as_bytes())
+            .unwrap().len(), MAX_IMPORT_ROWS);
+    }
+
+    #[test]
+    fn test_check_repeats() {
+        let csv = format!("{}\n\
+            600001,siamak.bernardeschi.600001@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n\
+            600002,Siamak.Bernardeschi.600001@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n\
+            600001,siamak.bernardeschi.600003@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n\
+            x,siamak.bernardeschi.600004@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n\
+            y,siamak.bernardeschi.600005@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n", HEADER);
+
+        let mut rows = parse_employees(csv.as_bytes()).unwrap();
+        let invalid_errors = rows[4].errors.clone();
+        check_repeats(&mut rows);
+
+        assert!(rows[0].errors.is_empty());
+        assert_eq!(rows[1].errors, vec![format!("{} 1", IMPORT_EMAIL_REPEATED_MSG)]);
+        assert_eq!(rows[2].errors, vec![format!("{} 1", IMPORT_EMP_NO_REPEATED_MSG)]);
+        // Invalid emp_no values don't repeat each other.
+        assert!(!invalid_errors.is_empty());
+        assert_eq!(rows[4].errors, invalid_errors);
+    }
+}
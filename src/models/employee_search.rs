@@ -0,0 +1,744 @@
+/* Date Created: 18/10/2026. */
+
+//! Employee searches: partial names, optional filters, sort order and page.
+//!
+//! Searches are built into SQL with [`sqlx::QueryBuilder`]: submitted values are
+//! always bound as parameters, never written into the SQL text. Column names in the
+//! SQL text come from [`SortColumn`] only.
+//!
+//! All parameters can be submitted in a JSON body, a form, or a query string. E.g.
+//! ``?last_name=%25chi&first_name=%25ak&gender=F&hire_date_from=01/01/1990&sort=-hire_date,last_name``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::employee_search::tests
+//!
+
+use std::str::FromStr;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use sqlx::{Pool, MySql, QueryBuilder};
+use sqlx::types::time::{Date, OffsetDateTime};
+
+use crate::bh_libs::australian_date::australian_date_option_format;
+use crate::helper::pagination::{number_or_string, Page, PageInfo, PageParams, RowKey};
+use crate::helper::messages::{
+    EMPLOYEE_GENDER_INVALID_MSG,
+    PAGE_CURSOR_INVALID_MSG,
+    SEARCH_SORT_INVALID_MSG,
+    SEARCH_SORT_DUPLICATE_MSG,
+    SEARCH_RANGE_INVALID_MSG,
+    SEARCH_EMAIL_INVALID_MSG,
+    SEARCH_TITLE_INVALID_MSG
+};
+
+use super::{Employee, EMAIL_MAX_LEN};
+use super::title::{TITLE_MAX_LEN, push_current_title, push_current_title_like};
+
+/// An auxiliary structure which represents:
+///
+/// * a JSON POST request body. E.g.: ``{"last_name": "%chi", "first_name": "%ak"}``.
+///   Request content type is ``application/json``.
+///
+/// * a x-www-form-urlencoded POST request body. E.g.: ``last_name=%chi&first_name=%ak``.
+///   Request content type is ``application/x-www-form-urlencoded; charset=UTF-8``.
+///
+/// * a query string. E.g.: ``?last_name=%25chi&first_name=%25ak&limit=10``.
+///
+/// Both ``last_name`` and ``first_name`` are partial strings. That is,
+/// each should have at least either a leading or a trailing ``%`` character.
+///
+/// The optional filters, sort order and page are in [`SearchOptions`].
+#[derive(Debug, Deserialize)]
+pub struct EmployeeSearch {
+    /// A MySQL partial string compared using LIKE operator.
+    pub last_name: String,
+    /// A MySQL partial string compared using LIKE operator.
+    pub first_name: String,
+    #[serde(flatten)]
+    pub options: SearchOptions,
+}
+
+/// The optional parts of an [`EmployeeSearch`]. On their own, they are the query
+/// string of searches whose partial names are in the URL path.
+#[derive(Debug, Default, Deserialize)]
+pub struct SearchOptions {
+    #[serde(flatten)]
+    pub filter: EmployeeFilter,
+    /// Comma separated employee fields, each optionally prefixed with ``-`` for
+    /// descending. E.g. ``-hire_date,last_name``. Defaults to ``emp_no``.
+    #[serde(default)]
+    pub sort: Option<String>,
+    /// See [`crate::helper::pagination`].
+    #[serde(flatten)]
+    pub page: PageParams,
+}
+
+/// Optional filters of an [`EmployeeSearch`]. Ranges are inclusive, and may be open
+/// ended. Dates are in Australian date format ``dd/mm/yyyy``.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmployeeFilter {
+    /// ``M`` or ``F``.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gender: Option<String>,
+    /// A MySQL partial string compared using LIKE operator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// The current title, a MySQL partial string compared using LIKE operator. E.g.
+    /// ``Senior Engineer``, or ``Senior%``.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, deserialize_with = "number_or_string", skip_serializing_if = "Option::is_none")]
+    pub emp_no_from: Option<i32>,
+    #[serde(default, deserialize_with = "number_or_string", skip_serializing_if = "Option::is_none")]
+    pub emp_no_to: Option<i32>,
+    #[serde(default, with = "australian_date_option_format", skip_serializing_if = "Option::is_none")]
+    pub birth_date_from: Option<Date>,
+    #[serde(default, with = "australian_date_option_format", skip_serializing_if = "Option::is_none")]
+    pub birth_date_to: Option<Date>,
+    #[serde(default, with = "australian_date_option_format", skip_serializing_if = "Option::is_none")]
+    pub hire_date_from: Option<Date>,
+    #[serde(default, with = "australian_date_option_format", skip_serializing_if = "Option::is_none")]
+    pub hire_date_to: Option<Date>,
+}
+
+impl EmployeeFilter {
+    /// Validates the filters.
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - all problems found, space separated.
+    ///
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors: Vec<String> = Vec::new();
+
+        if let Some(gender) = &self.gender {
+            if gender != "M" && gender != "F" {
+                errors.push(String::from(EMPLOYEE_GENDER_INVALID_MSG));
+            }
+        }
+
+        if let Some(email) = &self.email {
+            if email.chars().count() > EMAIL_MAX_LEN {
+                errors.push(String::from(SEARCH_EMAIL_INVALID_MSG));
+            }
+        }
+
+        if let Some(title) = &self.title {
+            if title.chars().count() > TITLE_MAX_LEN {
+                errors.push(String::from(SEARCH_TITLE_INVALID_MSG));
+            }
+        }
+
+        if let (Some(from), Some(to)) = (self.emp_no_from, self.emp_no_to) {
+            if from > to {
+                errors.push(format!("{} emp_no.", SEARCH_RANGE_INVALID_MSG));
+            }
+        }
+
+        for (name, from, to) in [
+            ("birth_date", self.birth_date_from, self.birth_date_to),
+            ("hire_date", self.hire_date_from, self.hire_date_to),
+        ] {
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    errors.push(format!("{} {}.", SEARCH_RANGE_INVALID_MSG, name));
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors.join(" ")),
+        }
+    }
+}
+
+/// Columns an employee search can be sorted on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortColumn {
+    EmpNo,
+    Email,
+    BirthDate,
+    FirstName,
+    LastName,
+    Gender,
+    HireDate,
+}
+
+impl FromStr for SortColumn {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "emp_no" => Ok(Self::EmpNo),
+            "email" => Ok(Self::Email),
+            "birth_date" => Ok(Self::BirthDate),
+            "first_name" => Ok(Self::FirstName),
+            "last_name" => Ok(Self::LastName),
+            "gender" => Ok(Self::Gender),
+            "hire_date" => Ok(Self::HireDate),
+            _ => Err(format!("{} {}", SEARCH_SORT_INVALID_MSG, value)),
+        }
+    }
+}
+
+impl SortColumn {
+    /// The SQL expression of the column.
+    fn sql(&self) -> &'static str {
+        match self {
+            Self::EmpNo => "emp_no",
+            Self::Email => "email",
+            Self::BirthDate => "birth_date",
+            Self::FirstName => "first_name",
+            Self::LastName => "last_name",
+            // ENUM sorts by index, but compares as a string: sort it as a string too.
+            Self::Gender => "CAST(gender AS CHAR)",
+            Self::HireDate => "hire_date",
+        }
+    }
+
+    /// The column value of an employee, as a [`RowKey`] value.
+    fn key(&self, employee: &Employee) -> Value {
+        match self {
+            Self::EmpNo => Value::from(employee.emp_no),
+            Self::Email => Value::from(employee.email.as_str()),
+            Self::BirthDate => Value::from(employee.birth_date.to_string()),
+            Self::FirstName => Value::from(employee.first_name.as_str()),
+            Self::LastName => Value::from(employee.last_name.as_str()),
+            Self::Gender => Value::from(employee.gender.as_str()),
+            Self::HireDate => Value::from(employee.hire_date.to_string()),
+        }
+    }
+}
+
+/// A column of the sort order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortKey {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+/// Parses a ``sort`` parameter, e.g. ``-hire_date,last_name``.
+///
+/// ``emp_no`` is unique: it is added as the last column if not already there, so that
+/// the order is total. Keyset pagination relies on it.
+///
+pub fn parse_sort(sort: Option<&str>) -> Result<Vec<SortKey>, String> {
+    let mut keys: Vec<SortKey> = Vec::new();
+
+    for field in sort.unwrap_or_default().split(',').map(str::trim).filter(|field| !field.is_empty()) {
+        let (name, descending) = match field.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (field, false),
+        };
+
+        let column = name.parse::<SortColumn>()?;
+
+        if keys.iter().any(|key| key.column == column) {
+            return Err(format!("{} {}", SEARCH_SORT_DUPLICATE_MSG, name));
+        }
+
+        keys.push(SortKey { column, descending });
+    }
+
+    if !keys.iter().any(|key| key.column == SortColumn::EmpNo) {
+        keys.push(SortKey { column: SortColumn::EmpNo, descending: false });
+    }
+
+    Ok(keys)
+}
+
+impl EmployeeSearch {
+    /// Validates the search, and resolves it into an [`EmployeeQuery`].
+    ///
+    /// # Arguments
+    ///
+    /// * `default_limit`, `max_limit` - see [`PageParams::resolve`].
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - the problem, as a message for the client.
+    ///
+    pub fn resolve(&self, default_limit: u32, max_limit: u32) -> Result<EmployeeQuery<'_>, String> {
+        let sort = self.resolve_sort()?;
+        let page = self.options.page.resolve(default_limit, max_limit)?;
+
+        // The cursor must have been made with the same sort order.
+        if let Some(key) = page.key() {
+            let matched = key.len() == sort.len() && key.iter().zip(sort.iter())
+                .all(|(value, sort_key)| match sort_key.column {
+                    SortColumn::EmpNo => value.is_i64(),
+                    _ => value.is_string(),
+                });
+
+            if !matched {
+                return Err(String::from(PAGE_CURSOR_INVALID_MSG));
+            }
+        }
+
+        Ok(EmployeeQuery { search: self, sort, page })
+    }
+
+    /// The URL encoded query string of the search, without the page. For the links to
+    /// other pages.
+    pub fn query_string(&self) -> String {
+        let mut params: Vec<(&str, &str)> = vec![
+            ("last_name", &self.last_name), ("first_name", &self.first_name)];
+
+        if let Some(sort) = &self.options.sort {
+            params.push(("sort", sort));
+        }
+
+        let query = serde_urlencoded::to_string(params).unwrap();
+        let filter = serde_urlencoded::to_string(&self.options.filter).unwrap();
+
+        match filter.is_empty() {
+            true => query,
+            false => format!("{}&{}", query, filter),
+        }
+    }
+
+    /// Validates the filters, and parses the sort order. For searches which are not
+    /// paged, see [`EmployeeSearch::export_builder`].
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - the problem, as a message for the client.
+    ///
+    pub fn resolve_sort(&self) -> Result<Vec<SortKey>, String> {
+        self.options.filter.validate()?;
+
+        parse_sort(self.options.sort.as_deref())
+    }
+
+    /// Makes the query reading all matched rows, in ``sort`` order: not paged, e.g.
+    /// for exports.
+    pub fn export_builder(&self, sort: &[SortKey]) -> QueryBuilder<'_, MySql> {
+        let mut builder = employees_select_builder();
+        self.push_conditions(&mut builder);
+        push_order_by(&mut builder, sort, false);
+
+        builder
+    }
+
+    /// Appends the conditions of the search criteria, to the ``WHERE`` clause excluding
+    /// deleted employees.
+    fn push_conditions(&self, builder: &mut QueryBuilder<'_, MySql>) {
+        let filter = &self.options.filter;
+
+        builder.push(" AND last_name LIKE ").push_bind(self.last_name.clone());
+        builder.push(" AND first_name LIKE ").push_bind(self.first_name.clone());
+
+        if let Some(gender) = &filter.gender {
+            builder.push(" AND gender = ").push_bind(gender.clone());
+        }
+        if let Some(email) = &filter.email {
+            builder.push(" AND email LIKE ").push_bind(email.clone());
+        }
+        if let Some(title) = &filter.title {
+            builder.push(" AND ");
+            push_current_title_like(builder, title);
+        }
+        if let Some(emp_no) = filter.emp_no_from {
+            builder.push(" AND emp_no >= ").push_bind(emp_no);
+        }
+        if let Some(emp_no) = filter.emp_no_to {
+            builder.push(" AND emp_no <= ").push_bind(emp_no);
+        }
+
+        for (column, from, to) in [
+            ("birth_date", filter.birth_date_from, filter.birth_date_to),
+            ("hire_date", filter.hire_date_from, filter.hire_date_to),
+        ] {
+            if let Some(date) = from {
+                builder.push(format!(" AND {} >= ", column)).push_bind(date);
+            }
+            if let Some(date) = to {
+                builder.push(format!(" AND {} <= ", column)).push_bind(date);
+            }
+        }
+    }
+}
+
+/// Starts the query reading ``employees`` columns and the current title, of employees
+/// who are not deleted. Conditions follow as ``AND ...``.
+pub fn employees_select_builder<'a>() -> QueryBuilder<'a, MySql> {
+    let mut builder = QueryBuilder::new(
+        "SELECT emp_no, email, birth_date, first_name, last_name, gender, hire_date, ");
+    push_current_title(&mut builder);
+    builder.push(" AS title FROM employees WHERE deleted_at IS NULL");
+
+    builder
+}
+
+/// Appends the ``ORDER BY`` clause, reversed to read backward.
+fn push_order_by(builder: &mut QueryBuilder<'_, MySql>, sort: &[SortKey], reverse: bool) {
+    let columns: Vec<String> = sort.iter()
+        .map(|key| format!("{} {}", key.column.sql(),
+            if key.descending != reverse { "DESC" } else { "ASC" }))
+        .collect();
+
+    builder.push(format!(" ORDER BY {}", columns.join(", ")));
+}
+
+/// A validated [`EmployeeSearch`].
+#[derive(Debug)]
+pub struct EmployeeQuery<'a> {
+    pub search: &'a EmployeeSearch,
+    pub sort: Vec<SortKey>,
+    pub page: Page,
+}
+
+/// Binds a [`RowKey`] value: only numbers and strings, see [`EmployeeSearch::resolve`].
+fn push_bind_key(builder: &mut QueryBuilder<'_, MySql>, value: &Value) {
+    match value {
+        Value::Number(number) => builder.push_bind(number.as_i64()),
+        _ => builder.push_bind(value.as_str().map(String::from)),
+    };
+}
+
+impl EmployeeQuery<'_> {
+    /// Appends the condition of rows which come after, or before, the row whose key
+    /// is ``key``. E.g. sorting on ``-hire_date,emp_no``, the rows after are
+    /// ``(hire_date < ?) OR (hire_date = ? AND emp_no > ?)``.
+    fn push_keyset(&self, builder: &mut QueryBuilder<'_, MySql>, key: &RowKey, after: bool) {
+        builder.push(" AND (");
+
+        for (index, sort_key) in self.sort.iter().enumerate() {
+            if index > 0 {
+                builder.push(" OR ");
+            }
+            builder.push("(");
+
+            for (equal_key, value) in self.sort.iter().zip(key.iter()).take(index) {
+                builder.push(format!("{} = ", equal_key.column.sql()));
+                push_bind_key(builder, value);
+                builder.push(" AND ");
+            }
+
+            let operator = if after != sort_key.descending { " > " } else { " < " };
+            builder.push(format!("{}{}", sort_key.column.sql(), operator));
+            push_bind_key(builder, &key[index]);
+
+            builder.push(")");
+        }
+
+        builder.push(")");
+    }
+
+    /// The query counting all matched rows.
+    fn count_builder(&self) -> QueryBuilder<'_, MySql> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM employees WHERE deleted_at IS NULL");
+        self.search.push_conditions(&mut builder);
+
+        builder
+    }
+
+    /// The query reading the page, and one more row to know if there are more.
+    fn select_builder(&self) -> QueryBuilder<'_, MySql> {
+        let mut builder = employees_select_builder();
+        self.search.push_conditions(&mut builder);
+
+        let limit = self.page.limit() as u64 + 1;
+
+        match &self.page {
+            Page::Offset { offset, .. } => {
+                push_order_by(&mut builder, &self.sort, false);
+                builder.push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(*offset);
+            }
+            Page::After { key, .. } => {
+                self.push_keyset(&mut builder, key, true);
+                push_order_by(&mut builder, &self.sort, false);
+                builder.push(" LIMIT ").push_bind(limit);
+            }
+            Page::Before { key, .. } => {
+                self.push_keyset(&mut builder, key, false);
+                push_order_by(&mut builder, &self.sort, true);
+                builder.push(" LIMIT ").push_bind(limit);
+            }
+        }
+
+        builder
+    }
+
+    /// The [`RowKey`] of an employee.
+    fn row_key(&self, employee: &Employee) -> RowKey {
+        self.sort.iter().map(|key| key.column.key(employee)).collect()
+    }
+}
+
+/// Attempts to retrieve a page of the ``employees`` table rows which match a search.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `query` - the validated search, see [`EmployeeSearch::resolve`].
+///
+/// # Return
+///
+/// - The rows of the page, and its [`PageInfo`].
+///
+pub async fn search_employees(
+    pool: &Pool<MySql>,
+    query: &EmployeeQuery<'_>
+) -> (Vec<Employee>, PageInfo) {
+    let total: i64 = query.count_builder()
+        .build_query_scalar()
+        .fetch_one(pool).await.unwrap();
+
+    let mut employees: Vec<Employee> = query.select_builder()
+        .build_query_as()
+        .fetch_all(pool).await.unwrap();
+
+    let has_more = employees.len() > query.page.limit() as usize;
+    employees.truncate(query.page.limit() as usize);
+
+    if let Page::Before { .. } = query.page {
+        employees.reverse();
+    }
+
+    let info = query.page.info(total as u64,
+        employees.first().map(|employee| query.row_key(employee)),
+        employees.last().map(|employee| query.row_key(employee)),
+        has_more);
+
+    (employees, info)
+}
+
+/// Retrieves employees, with their current title, by ``emp_no``. E.g. the results of
+/// a full-text search, see [`crate::search_index`].
+///
+/// # Return
+///
+/// - The employees found, in the order of `emp_nos`.
+///
+pub async fn select_employees_by_nos(
+    pool: &Pool<MySql>,
+    emp_nos: &[i32]
+) -> Vec<Employee> {
+    if emp_nos.is_empty() {
+        return Vec::new();
+    }
+
+    let mut builder = employees_select_builder();
+    builder.push(" AND emp_no IN (");
+    let mut separated = builder.separated(", ");
+    for emp_no in emp_nos {
+        separated.push_bind(*emp_no);
+    }
+    separated.push_unseparated(")");
+
+    let mut employees: Vec<Employee> = builder.build_query_as().fetch_all(pool).await.unwrap();
+    employees.sort_by_key(|employee| emp_nos.iter().position(|emp_no| *emp_no == employee.emp_no));
+
+    employees
+}
+
+/// The latest ``updated_at`` of employees, the ``Last-Modified`` of an employee's response.
+///
+/// # Return
+///
+/// - ``None`` if there are no such employees.
+///
+pub async fn select_employees_updated_at(
+    pool: &Pool<MySql>,
+    emp_nos: &[i32]
+) -> Option<OffsetDateTime> {
+    if emp_nos.is_empty() {
+        return None;
+    }
+
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT MAX(updated_at) FROM employees WHERE emp_no IN (");
+    let mut separated = builder.separated(", ");
+    for emp_no in emp_nos {
+        separated.push_bind(*emp_no);
+    }
+    separated.push_unseparated(")");
+
+    builder.build_query_scalar().fetch_one(pool).await.unwrap()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::employee_search::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::employee_search::tests::test_employee_search_deserialise -- --exact
+///    * cargo test models::employee_search::tests::test_employee_filter_validate -- --exact
+///    * cargo test models::employee_search::tests::test_parse_sort -- --exact
+///    * cargo test models::employee_search::tests::test_resolve_cursor -- --exact
+///    * cargo test models::employee_search::tests::test_query_string -- --exact
+///    * cargo test models::employee_search::tests::test_select_sql -- --exact
+///    * cargo test models::employee_search::tests::test_export_sql -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use super::*;
+
+    fn search(query: &str) -> EmployeeSearch {
+        serde_urlencoded::from_str(query).unwrap()
+    }
+
+    #[test]
+    fn test_employee_search_deserialise() {
+        let employee_search = search("last_name=%25chi&first_name=%25ak&gender=F&emp_no_from=10010\
+            &hire_date_to=31/12/1990&sort=-hire_date&limit=10");
+
+        assert_eq!(employee_search.last_name, "%chi");
+        assert_eq!(employee_search.options.filter.gender.as_deref(), Some("F"));
+        assert_eq!(employee_search.options.filter.emp_no_from, Some(10010));
+        assert_eq!(employee_search.options.filter.hire_date_to, Some(date!(1990 - 12 - 31)));
+        assert_eq!(employee_search.options.filter.hire_date_from, None);
+        assert_eq!(employee_search.options.sort.as_deref(), Some("-hire_date"));
+        assert_eq!(employee_search.options.page.limit, Some(10));
+
+        let employee_search: EmployeeSearch = serde_json::from_str(r#"{"last_name": "%chi",
+            "first_name": "%ak", "emp_no_to": 20000, "birth_date_from": "01/01/1955", "offset": 5}"#).unwrap();
+
+        assert_eq!(employee_search.options.filter.emp_no_to, Some(20000));
+        assert_eq!(employee_search.options.filter.birth_date_from, Some(date!(1955 - 01 - 01)));
+        assert_eq!(employee_search.options.page.offset, Some(5));
+
+        let options: SearchOptions = serde_urlencoded::from_str("email=%25gmail.com&title=Senior+Staff&cursor=6131").unwrap();
+        assert_eq!(options.filter.email.as_deref(), Some("%gmail.com"));
+        assert_eq!(options.filter.title.as_deref(), Some("Senior Staff"));
+        assert_eq!(options.page.cursor.as_deref(), Some("6131"));
+
+        assert!(serde_urlencoded::from_str::<EmployeeSearch>("last_name=%25chi&first_name=%25ak\
+            &hire_date_from=1990-01-01").is_err());
+        assert!(serde_json::from_str::<EmployeeSearch>(r#"{"first_name": "%ak"}"#).unwrap_err()
+            .to_string().contains("missing field `last_name`"));
+    }
+
+    #[test]
+    fn test_employee_filter_validate() {
+        assert_eq!(EmployeeFilter::default().validate(), Ok(()));
+
+        let filter = EmployeeFilter {
+            gender: Some(String::from("X")),
+            emp_no_from: Some(20000),
+            emp_no_to: Some(10000),
+            hire_date_from: Some(date!(1990 - 01 - 01)),
+            hire_date_to: Some(date!(1989 - 01 - 01)),
+            ..EmployeeFilter::default()
+        };
+
+        assert_eq!(filter.validate(), Err(format!("{} {} emp_no. {} hire_date.",
+            EMPLOYEE_GENDER_INVALID_MSG, SEARCH_RANGE_INVALID_MSG, SEARCH_RANGE_INVALID_MSG)));
+
+        let filter = EmployeeFilter { email: Some("a".repeat(256)), ..EmployeeFilter::default() };
+        assert_eq!(filter.validate(), Err(String::from(SEARCH_EMAIL_INVALID_MSG)));
+
+        let filter = EmployeeFilter { title: Some("a".repeat(51)), ..EmployeeFilter::default() };
+        assert_eq!(filter.validate(), Err(String::from(SEARCH_TITLE_INVALID_MSG)));
+    }
+
+    #[test]
+    fn test_parse_sort() {
+        let emp_no = SortKey { column: SortColumn::EmpNo, descending: false };
+
+        assert_eq!(parse_sort(None), Ok(vec![emp_no]));
+        assert_eq!(parse_sort(Some("")), Ok(vec![emp_no]));
+
+        assert_eq!(parse_sort(Some("-hire_date, last_name")), Ok(vec![
+            SortKey { column: SortColumn::HireDate, descending: true },
+            SortKey { column: SortColumn::LastName, descending: false },
+            emp_no,
+        ]));
+
+        assert_eq!(parse_sort(Some("-emp_no,gender")), Ok(vec![
+            SortKey { column: SortColumn::EmpNo, descending: true },
+            SortKey { column: SortColumn::Gender, descending: false },
+        ]));
+
+        assert_eq!(parse_sort(Some("salary")), Err(format!("{} salary", SEARCH_SORT_INVALID_MSG)));
+        assert_eq!(parse_sort(Some("emp_no; DROP TABLE employees")),
+            Err(format!("{} emp_no; DROP TABLE employees", SEARCH_SORT_INVALID_MSG)));
+        assert_eq!(parse_sort(Some("last_name,-last_name")),
+            Err(format!("{} last_name", SEARCH_SORT_DUPLICATE_MSG)));
+    }
+
+    #[test]
+    fn test_resolve_cursor() {
+        let cursor = |key: &str| hex::encode(format!("[\"a\",{}]", key));
+
+        let employee_search = search(&format!("last_name=%25&first_name=%25&sort=last_name&cursor={}",
+            cursor("\"Koblick\",10004")));
+        let query = employee_search.resolve(50, 500).unwrap();
+        assert_eq!(query.page.key().unwrap().len(), 2);
+
+        // Not made with the same sort order.
+        for key in ["10004", "10004,\"Koblick\"", "\"Koblick\",\"10004\""] {
+            let employee_search = search(&format!("last_name=%25&first_name=%25&sort=last_name&cursor={}",
+                cursor(key)));
+            assert_eq!(employee_search.resolve(50, 500).unwrap_err(), PAGE_CURSOR_INVALID_MSG);
+        }
+
+        let employee_search = search("last_name=%25&first_name=%25&gender=X");
+        assert_eq!(employee_search.resolve(50, 500).unwrap_err(), EMPLOYEE_GENDER_INVALID_MSG);
+    }
+
+    #[test]
+    fn test_query_string() {
+        let employee_search = search("last_name=%25chi&first_name=%25ak&sort=-hire_date\
+            &hire_date_from=01/01/1990&limit=10&cursor=6131");
+
+        assert_eq!(employee_search.query_string(),
+            "last_name=%25chi&first_name=%25ak&sort=-hire_date&hire_date_from=01%2F01%2F1990");
+
+        assert_eq!(search("last_name=%25chi&first_name=%25ak").query_string(),
+            "last_name=%25chi&first_name=%25ak");
+    }
+
+    const CURRENT_TITLE_SQL: &str = "(SELECT t.title FROM titles t WHERE t.emp_no = employees.emp_no \
+        AND t.to_date = ? ORDER BY t.from_date DESC LIMIT 1)";
+
+    #[test]
+    fn test_select_sql() {
+        let employee_search = search("last_name=%25&first_name=%25&gender=F&title=Engineer&emp_no_from=10010\
+            &hire_date_to=31/12/1990&sort=-hire_date,gender&offset=10");
+        let query = employee_search.resolve(50, 500).unwrap();
+
+        let title_condition = "EXISTS (SELECT 1 FROM titles t WHERE t.emp_no = employees.emp_no \
+            AND t.to_date = ? AND t.title LIKE ?)";
+
+        assert_eq!(query.count_builder().sql(), format!("SELECT COUNT(*) FROM employees \
+            WHERE deleted_at IS NULL AND last_name LIKE ? AND first_name LIKE ? AND gender = ? AND {} AND emp_no >= ? \
+            AND hire_date <= ?", title_condition));
+
+        assert_eq!(query.select_builder().sql(), format!("SELECT emp_no, email, birth_date, first_name, last_name, \
+            gender, hire_date, {} AS title FROM employees \
+            WHERE deleted_at IS NULL AND last_name LIKE ? AND first_name LIKE ? AND gender = ? AND {} AND emp_no >= ? AND hire_date <= ? \
+            ORDER BY hire_date DESC, CAST(gender AS CHAR) ASC, emp_no ASC LIMIT ? OFFSET ?",
+            CURRENT_TITLE_SQL, title_condition));
+
+        let cursor = hex::encode(r#"["b","1990-01-01",10004]"#);
+        let employee_search = search(&format!("last_name=%25&first_name=%25&sort=-hire_date&cursor={}", cursor));
+        let query = employee_search.resolve(50, 500).unwrap();
+
+        assert_eq!(query.select_builder().sql(), format!("SELECT emp_no, email, birth_date, first_name, last_name, \
+            gender, hire_date, {} AS title FROM employees \
+            WHERE deleted_at IS NULL AND last_name LIKE ? AND first_name LIKE ? \
+            AND ((hire_date > ?) OR (hire_date = ? AND emp_no < ?)) \
+            ORDER BY hire_date ASC, emp_no DESC LIMIT ?", CURRENT_TITLE_SQL));
+    }
+
+    #[test]
+    fn test_export_sql() {
+        // Page parameters are not part of exports.
+        let employee_search = search("last_name=%25chi&first_name=%25&hire_date_from=01/01/1990&sort=-hire_date&limit=10");
+        let sort = employee_search.resolve_sort().unwrap();
+
+        assert_eq!(employee_search.export_builder(&sort).sql(), format!("SELECT emp_no, email, birth_date, \
+            first_name, last_name, gender, hire_date, {} AS title FROM employees \
+            WHERE deleted_at IS NULL AND last_name LIKE ? AND first_name LIKE ? AND hire_date >= ? \
+            ORDER BY hire_date DESC, emp_no ASC", CURRENT_TITLE_SQL));
+
+        let employee_search = search("last_name=%25&first_name=%25&sort=salary");
+        assert_eq!(employee_search.resolve_sort(), Err(format!("{} salary", SEARCH_SORT_INVALID_MSG)));
+    }
+}
@@ -0,0 +1,105 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents the ``magic_links`` table in the database: passwordless sign-in links.
+//!
+//! A sign-in link carries a random token. It is bound to the browser which requested
+//! it by a second random secret, which is set in the server-side only cookie
+//! [`crate::helper::constants::MAGIC_LINK_BROWSER`]. A link is accepted only once,
+//! before it expires, and only together with that cookie: a forwarded email can't be
+//! used from another browser.
+//!
+//! Only the SHA-256 hashes of the token and of the browser secret are stored.
+
+use sqlx::{Row, Pool, MySql};
+
+use crate::helper::password_utils::sha256_hex;
+
+/// Number of random bytes in the link token, and in the browser secret.
+pub const MAGIC_LINK_TOKEN_BYTES: usize = 32;
+
+/// Why a sign-in link was not accepted. The reasons are for logging only: clients
+/// always get the same message.
+#[derive(Debug, PartialEq)]
+pub enum MagicLinkRejection {
+    /// Unknown, already used, or expired.
+    Invalid,
+    /// The link was followed from a browser other than the one which requested it.
+    WrongBrowser,
+}
+
+/// Stores a new sign-in link.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `email` - the email the link signs in as.
+///
+/// * `token` - the link token, see [`crate::helper::password_utils::random_token`].
+///
+/// * `browser_secret` - the secret in the requesting browser's cookie.
+///
+/// * `mins_valid_for` - see [`crate::config::Config::magic_link_mins_valid_for`].
+///
+pub async fn insert_magic_link(
+    pool: &Pool<MySql>,
+    email: &str,
+    token: &str,
+    browser_secret: &str,
+    mins_valid_for: u64 ) {
+    sqlx::query("INSERT INTO magic_links
+            (token_hash, browser_hash, email, created_at, expires_at)
+        VALUES (?, ?, ?, UTC_TIMESTAMP(), UTC_TIMESTAMP() + INTERVAL ? MINUTE)")
+    .bind(sha256_hex(token)).bind(sha256_hex(browser_secret)).bind(email).bind(mins_valid_for)
+    .execute(pool).await.unwrap();
+}
+
+/// Accepts a sign-in link: marks it used, so that it can't be accepted again.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `token` - the link token from the URL.
+///
+/// * `browser_secret` - the secret in the browser's cookie, if any.
+///
+/// # Return
+///
+/// * ``Ok(email)`` - the email to sign in as.
+///
+/// * ``Err(MagicLinkRejection)`` - the link is not accepted.
+///
+pub async fn consume_magic_link(
+    pool: &Pool<MySql>,
+    token: &str,
+    browser_secret: Option<&str> ) -> Result<String, MagicLinkRejection> {
+    let token_hash = sha256_hex(token);
+
+    let (email, browser_hash) = sqlx::query(
+        "SELECT email, browser_hash FROM magic_links
+        WHERE token_hash = ? AND used_at IS NULL AND expires_at > UTC_TIMESTAMP()")
+    .bind(&token_hash)
+    .map(|row: sqlx::mysql::MySqlRow| -> (String, String) {
+        (row.get(0), row.get(1))
+    })
+    .fetch_optional(pool).await.unwrap()
+    .ok_or(MagicLinkRejection::Invalid)?;
+
+    // The link stays valid for the requesting browser.
+    if browser_secret.map(sha256_hex) != Some(browser_hash) {
+        return Err(MagicLinkRejection::WrongBrowser);
+    }
+
+    // Of two concurrent requests, only one marks the link used.
+    let rows_affected = sqlx::query(
+        "UPDATE magic_links SET used_at = UTC_TIMESTAMP() WHERE token_hash = ? AND used_at IS NULL")
+    .bind(&token_hash)
+    .execute(pool).await.unwrap()
+    .rows_affected();
+
+    match rows_affected {
+        0 => Err(MagicLinkRejection::Invalid),
+        _ => Ok(email),
+    }
+}
@@ -0,0 +1,306 @@
+/* Date Created: 18/10/2026. */
+
+//! The management hierarchy, from the ``dept_manager`` and ``dept_emp`` tables.
+//!
+//! The employees database has no management above departments. So the hierarchy of
+//! the whole company is: the company, its departments, and the current managers of
+//! each department. The hierarchy of a department is the department, its current
+//! managers, and optionally the other current employees of the department, who report
+//! to the managers.
+//!
+//! See [`crate::helper::org_chart_export`] for the Graphviz DOT and SVG exports.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::org_chart::tests
+//!
+
+use serde::{Serialize, Deserialize};
+use sqlx::{Row, Pool, MySql, QueryBuilder};
+
+use crate::models::department::CURRENT_TO_DATE;
+use crate::models::title::push_current_title;
+
+/// The kind of an [`OrgNode`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgNodeKind {
+    Company,
+    Department,
+    Manager,
+    Employee,
+}
+
+impl OrgNodeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Company => "company",
+            Self::Department => "department",
+            Self::Manager => "manager",
+            Self::Employee => "employee",
+        }
+    }
+}
+
+/// A node of the management tree.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrgNode {
+    pub kind: OrgNodeKind,
+    /// ``dept_no`` of a department, ``emp_no`` of a manager or an employee.
+    pub id: String,
+    /// Department name, or employee first and last names.
+    pub name: String,
+    /// The current title of a manager or an employee.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The current number of employees of the company or a department.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub employees: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OrgNode>,
+}
+
+impl OrgNode {
+    fn person(kind: OrgNodeKind, row: &sqlx::mysql::MySqlRow, offset: usize) -> Self {
+        let emp_no: i32 = row.get(offset);
+        let first_name: String = row.get(offset + 1);
+        let last_name: String = row.get(offset + 2);
+
+        OrgNode {
+            kind,
+            id: emp_no.to_string(),
+            name: format!("{} {}", first_name, last_name),
+            title: row.get(offset + 3),
+            employees: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+/// A current manager of an employee: the current manager of a current department of
+/// the employee.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReportingLine {
+    pub dept_no: String,
+    pub dept_name: String,
+    pub manager_emp_no: i32,
+    pub manager_first_name: String,
+    pub manager_last_name: String,
+    pub manager_title: Option<String>,
+}
+
+/// Makes the query reading current department managers: ``dept_no``, then ``emp_no``,
+/// ``first_name``, ``last_name`` and current title.
+fn current_managers_builder<'a>(dept_no: Option<&'a str>) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT dm.dept_no, employees.emp_no,
+        employees.first_name, employees.last_name, ");
+    push_current_title(&mut builder);
+    builder.push(" FROM dept_manager dm JOIN employees ON employees.emp_no = dm.emp_no \
+            WHERE employees.deleted_at IS NULL AND dm.to_date = ")
+        .push_bind(CURRENT_TO_DATE);
+
+    if let Some(dept_no) = dept_no {
+        builder.push(" AND dm.dept_no = ").push_bind(dept_no);
+    }
+    builder.push(" ORDER BY dm.dept_no, dm.from_date, dm.emp_no");
+
+    builder
+}
+
+/// Makes the query reading current employees of a department who are not its current
+/// managers: ``emp_no``, ``first_name``, ``last_name`` and current title.
+fn department_reports_builder(dept_no: &str, limit: u32) -> QueryBuilder<'_, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT employees.emp_no,
+        employees.first_name, employees.last_name, ");
+    push_current_title(&mut builder);
+    builder.push(" FROM dept_emp de JOIN employees ON employees.emp_no = de.emp_no \
+            WHERE employees.deleted_at IS NULL AND de.dept_no = ")
+        .push_bind(dept_no)
+        .push(" AND de.to_date = ").push_bind(CURRENT_TO_DATE)
+        .push(" AND de.emp_no NOT IN (SELECT emp_no FROM dept_manager WHERE dept_no = ")
+        .push_bind(dept_no)
+        .push(" AND to_date = ").push_bind(CURRENT_TO_DATE)
+        .push(") ORDER BY de.emp_no LIMIT ").push_bind(limit);
+
+    builder
+}
+
+/// Reads departments, with their current number of employees. All departments when
+/// ``dept_no`` is ``None``.
+async fn select_department_nodes(
+    pool: &Pool<MySql>,
+    dept_no: Option<&str>
+) -> Vec<OrgNode> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT d.dept_no, d.dept_name,
+        (SELECT COUNT(*) FROM dept_emp de JOIN employees e ON e.emp_no = de.emp_no 
+            WHERE e.deleted_at IS NULL AND de.dept_no = d.dept_no AND de.to_date = ");
+    builder.push_bind(CURRENT_TO_DATE).push(") FROM departments d");
+
+    if let Some(dept_no) = dept_no {
+        builder.push(" WHERE d.dept_no = ").push_bind(dept_no);
+    }
+    builder.push(" ORDER BY d.dept_no");
+
+    let mut departments = builder.build()
+    .map(|row: sqlx::mysql::MySqlRow| OrgNode {
+        kind: OrgNodeKind::Department,
+        id: row.get(0),
+        name: row.get(1),
+        title: None,
+        employees: Some(row.get(2)),
+        children: Vec::new(),
+    })
+    .fetch_all(pool).await.unwrap();
+
+    let managers = current_managers_builder(dept_no).build()
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let dept_no: String = row.get(0);
+        (dept_no, OrgNode::person(OrgNodeKind::Manager, &row, 1))
+    })
+    .fetch_all(pool).await.unwrap();
+
+    for (dept_no, manager) in managers {
+        if let Some(department) = departments.iter_mut().find(|department| department.id == dept_no) {
+            department.children.push(manager);
+        }
+    }
+
+    departments
+}
+
+/// Retrieves the management tree of the whole company: the company, departments, and
+/// their current managers.
+pub async fn select_company_org_chart(pool: &Pool<MySql>) -> OrgNode {
+    let employees: i64 = sqlx::query("SELECT COUNT(DISTINCT de.emp_no) FROM dept_emp de 
+        JOIN employees e ON e.emp_no = de.emp_no WHERE e.deleted_at IS NULL AND de.to_date = ?")
+    .bind(CURRENT_TO_DATE)
+    .map(|row: sqlx::mysql::MySqlRow| row.get(0))
+    .fetch_one(pool).await.unwrap();
+
+    OrgNode {
+        kind: OrgNodeKind::Company,
+        id: String::from("company"),
+        name: String::from("Company"),
+        title: None,
+        employees: Some(employees),
+        children: select_department_nodes(pool, None).await,
+    }
+}
+
+/// Retrieves the management tree of a department.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `dept_no` - e.g. ``d005``.
+///
+/// * `reports` - the maximum number of other current employees, ordered by ``emp_no``.
+///   They are under the first current manager, or under the department if it has no
+///   current manager. ``0`` for managers only.
+///
+/// # Return
+///
+/// * ``None`` if there is no such department.
+///
+pub async fn select_department_org_chart(
+    pool: &Pool<MySql>,
+    dept_no: &str,
+    reports: u32
+) -> Option<OrgNode> {
+    let mut department = select_department_nodes(pool, Some(dept_no)).await.pop()?;
+
+    if reports > 0 {
+        let employees = department_reports_builder(dept_no, reports).build()
+        .map(|row: sqlx::mysql::MySqlRow| OrgNode::person(OrgNodeKind::Employee, &row, 0))
+        .fetch_all(pool).await.unwrap();
+
+        match department.children.first_mut() {
+            Some(manager) => manager.children = employees,
+            None => department.children = employees,
+        }
+    }
+
+    Some(department)
+}
+
+/// Retrieves the current managers of an employee, ordered by ``dept_no``. Empty if the
+/// employee has no current department, or manages all of their current departments.
+pub async fn select_employee_managers(
+    pool: &Pool<MySql>,
+    emp_no: i32
+) -> Vec<ReportingLine> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT de.dept_no, d.dept_name,
+        employees.emp_no, employees.first_name, employees.last_name, ");
+    push_current_title(&mut builder);
+    builder.push(" FROM dept_emp de JOIN departments d ON d.dept_no = de.dept_no
+        JOIN dept_manager dm ON dm.dept_no = de.dept_no AND dm.to_date = ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" JOIN employees ON employees.emp_no = dm.emp_no WHERE employees.deleted_at IS NULL \
+            AND de.emp_no = ")
+        .push_bind(emp_no)
+        .push(" AND de.to_date = ").push_bind(CURRENT_TO_DATE)
+        .push(" AND dm.emp_no <> de.emp_no ORDER BY de.dept_no, dm.from_date");
+
+    builder.build()
+    .map(|row: sqlx::mysql::MySqlRow| ReportingLine {
+        dept_no: row.get(0),
+        dept_name: row.get(1),
+        manager_emp_no: row.get(2),
+        manager_first_name: row.get(3),
+        manager_last_name: row.get(4),
+        manager_title: row.get(5),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::org_chart::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::org_chart::tests::test_org_node_serde -- --exact
+///    * cargo test models::org_chart::tests::test_department_reports_sql -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_org_node_serde() {
+        let node = OrgNode {
+            kind: OrgNodeKind::Department,
+            id: String::from("d005"),
+            name: String::from("Development"),
+            title: None,
+            employees: Some(61386),
+            children: vec![OrgNode {
+                kind: OrgNodeKind::Manager,
+                id: String::from("110567"),
+                name: String::from("Leon DasSarma"),
+                title: Some(String::from("Manager")),
+                employees: None,
+                children: Vec::new(),
+            }],
+        };
+
+        let json = serde_json::to_string(&node).unwrap();
+        assert_eq!(json, r#"{"kind":"department","id":"d005","name":"Development","employees":61386,"children":[{"kind":"manager","id":"110567","name":"Leon DasSarma","title":"Manager"}]}"#);
+
+        let node: OrgNode = serde_json::from_str(&json).unwrap();
+        assert_eq!(node.children[0].kind, OrgNodeKind::Manager);
+        assert!(node.children[0].children.is_empty());
+    }
+
+    #[test]
+    fn test_department_reports_sql() {
+        let sql = String::from(department_reports_builder("d005", 10).sql());
+
+        assert!(sql.contains("FROM dept_emp de JOIN employees ON employees.emp_no = de.emp_no \
+            WHERE employees.deleted_at IS NULL AND de.dept_no = ? AND de.to_date = ? AND de.emp_no NOT IN (SELECT emp_no FROM dept_manager \
+            WHERE dept_no = ? AND to_date = ?) ORDER BY de.emp_no LIMIT ?"));
+
+        let sql = String::from(current_managers_builder(Some("d005")).sql());
+        assert!(sql.ends_with("WHERE employees.deleted_at IS NULL AND dm.to_date = ? AND dm.dept_no = ? ORDER BY dm.dept_no, dm.from_date, dm.emp_no"));
+    }
+}
@@ -0,0 +1,113 @@
+/* Date Created: 18/10/2026. */
+
+//! Permissions, granted by ``employees.role``.
+//!
+//! * ``hr`` -- read salaries; create, import, update and delete employees. Granted to the roles listed in ``HR_ROLES``, e.g.
+//!   ``hr,admin``.
+//!
+//! * ``admin`` -- list and restore deleted employees. Granted to the roles listed in
+//!   ``ADMIN_ROLES``, e.g. ``admin``.
+//!
+//! Logins without a matching ``employees`` row, e.g. some LDAP logins, have no
+//! permission.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::permission::tests
+//!
+
+use sqlx::{Row, Pool, MySql};
+
+use crate::config::Config;
+use crate::helper::messages::{HR_PERMISSION_REQUIRED_MSG, ADMIN_PERMISSION_REQUIRED_MSG};
+
+/// A permission, see the module documentation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Permission {
+    Hr,
+    Admin,
+}
+
+impl Permission {
+    /// Returns ``true`` if ``role`` has this permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - where ``HR_ROLES`` and ``ADMIN_ROLES`` are.
+    ///
+    /// * `role` - an ``employees.role`` value, compared case insensitively.
+    ///
+    pub fn granted_to(&self, config: &Config, role: &str) -> bool {
+        let role = role.trim().to_lowercase();
+
+        match self {
+            Self::Hr => config.hr_roles.contains(&role),
+            Self::Admin => config.admin_roles.contains(&role),
+        }
+    }
+
+    /// The message of the response to a request without this permission.
+    pub fn required_message(&self) -> &'static str {
+        match self {
+            Self::Hr => HR_PERMISSION_REQUIRED_MSG,
+            Self::Admin => ADMIN_PERMISSION_REQUIRED_MSG,
+        }
+    }
+}
+
+/// Returns the role of an employee, ``None`` if there is no such employee, or it is
+/// deleted.
+pub async fn select_employee_role(
+    pool: &Pool<MySql>,
+    email: &str ) -> Option<String> {
+    sqlx::query("SELECT role FROM employees WHERE email = ? AND deleted_at IS NULL")
+    .bind(email)
+    .map(|row: sqlx::mysql::MySqlRow| row.get(0))
+    .fetch_optional(pool).await.unwrap()
+}
+
+/// Returns ``true`` if the logged in ``email`` has ``permission``.
+pub async fn has_permission(
+    pool: &Pool<MySql>,
+    config: &Config,
+    email: &str,
+    permission: Permission ) -> bool {
+    match select_employee_role(pool, email).await {
+        Some(role) => permission.granted_to(config, &role),
+        None => false,
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::permission::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::permission::tests::test_granted_to -- --exact
+///    * cargo test models::permission::tests::test_required_message -- --exact
+#[cfg(test)]
+mod tests {
+    use dotenv::dotenv;
+    use super::*;
+
+    #[test]
+    fn test_granted_to() {
+        dotenv().ok();
+        let config = Config::init();
+
+        assert!(Permission::Hr.granted_to(&config, "hr"));
+        assert!(Permission::Hr.granted_to(&config, " Admin "));
+        assert!(!Permission::Hr.granted_to(&config, "employee"));
+        assert!(!Permission::Hr.granted_to(&config, ""));
+
+        assert!(Permission::Admin.granted_to(&config, "admin"));
+        assert!(!Permission::Admin.granted_to(&config, "hr"));
+    }
+
+    #[test]
+    fn test_required_message() {
+        assert_eq!(Permission::Hr.required_message(), HR_PERMISSION_REQUIRED_MSG);
+        assert_eq!(Permission::Admin.required_message(), ADMIN_PERMISSION_REQUIRED_MSG);
+    }
+}
@@ -0,0 +1,282 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents the ``persistent_logins`` table in the database: "remember me" logins
+//! which outlive the access token.
+//!
+//! The credential is a [`PersistentLoginToken`], which is sent to the client in the
+//! server-side only cookie [`crate::helper::constants::PERSISTENT_LOGIN`] as
+//! ``selector:validator``. The ``selector`` identifies the row, only the SHA-256 hash
+//! of the ``validator`` is stored. So a leaked ``persistent_logins`` table can't be
+//! used to log in.
+//!
+//! Each time the credential is used, the ``validator`` is replaced: see
+//! [`rotate_persistent_login`]. Concurrent requests sending the same cookie, e.g. two
+//! tabs, present the ``validator`` just replaced: it is accepted for
+//! [`ROTATION_GRACE_SECS`] seconds after the rotation. If any other old ``validator`` is
+//! presented for a known ``selector``, the credential must have been copied, all
+//! persistent logins of the employee are revoked.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::persistent_login::tests
+//!
+
+use sqlx::{Row, Pool, MySql};
+
+use crate::helper::password_utils::{random_token, sha256_hex};
+
+/// Number of random bytes in the selector. It is 24 hexadecimal characters.
+const SELECTOR_BYTES: usize = 12;
+/// Number of random bytes in the validator. It is 64 hexadecimal characters.
+const VALIDATOR_BYTES: usize = 32;
+/// Number of seconds the validator replaced by the last rotation is still accepted.
+pub const ROTATION_GRACE_SECS: u64 = 60;
+
+/// A "remember me" credential.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistentLoginToken {
+    /// Public, hexadecimal. Looks up the ``persistent_logins`` row.
+    pub selector: String,
+    /// Secret, hexadecimal. Never stored, only its hash is.
+    pub validator: String,
+}
+
+fn is_hex_of_len(value: &str, len: usize) -> bool {
+    value.len() == len * 2 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl PersistentLoginToken {
+    /// Creates a new credential with a random selector and validator.
+    pub fn generate() -> Self {
+        Self {
+            selector: random_token(SELECTOR_BYTES),
+            validator: random_token(VALIDATOR_BYTES),
+        }
+    }
+
+    /// Creates a new credential with the same selector, but a new random validator.
+    pub fn rotate(&self) -> Self {
+        Self {
+            selector: self.selector.clone(),
+            validator: random_token(VALIDATOR_BYTES),
+        }
+    }
+
+    /// Parses a cookie value in the form ``selector:validator``.
+    ///
+    /// # Return
+    ///
+    /// * ``None`` if the value is not well formed.
+    ///
+    pub fn parse(value: &str) -> Option<Self> {
+        let (selector, validator) = value.split_once(':')?;
+
+        if !is_hex_of_len(selector, SELECTOR_BYTES) || !is_hex_of_len(validator, VALIDATOR_BYTES) {
+            return None;
+        }
+
+        Some(Self {
+            selector: selector.to_ascii_lowercase(),
+            validator: validator.to_ascii_lowercase(),
+        })
+    }
+
+    /// Returns the cookie value, ``selector:validator``.
+    pub fn cookie_value(&self) -> String {
+        format!("{}:{}", self.selector, self.validator)
+    }
+
+    /// Returns the hexadecimal SHA-256 hash of the validator, as stored in
+    /// ``persistent_logins.validator_hash``.
+    pub fn validator_hash(&self) -> String {
+        sha256_hex(&self.validator)
+    }
+}
+
+/// Stores a new persistent login.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `email` - the logged in email.
+///
+/// * `token` - the new credential, see [`PersistentLoginToken::generate`].
+///
+/// * `days_valid_for` - see [`crate::config::Config::persistent_login_days_valid_for`].
+///
+pub async fn insert_persistent_login(
+    pool: &Pool<MySql>,
+    email: &str,
+    token: &PersistentLoginToken,
+    days_valid_for: u64 ) {
+    sqlx::query("INSERT INTO persistent_logins
+            (selector, validator_hash, email, created_at, last_used_at, expires_at)
+        VALUES (?, ?, ?, UTC_TIMESTAMP(), UTC_TIMESTAMP(), UTC_TIMESTAMP() + INTERVAL ? DAY)")
+    .bind(&token.selector).bind(token.validator_hash()).bind(email).bind(days_valid_for)
+    .execute(pool).await.unwrap();
+}
+
+/// Validates a presented persistent login, and if valid, replaces its validator and
+/// extends its expiry.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `token` - the credential from the client cookie.
+///
+/// * `days_valid_for` - see [`crate::config::Config::persistent_login_days_valid_for`].
+///
+/// # Return
+///
+/// * ``Some((email, Some(rotated_token)))`` - the credential is valid. The client must
+///   be given ``rotated_token``, the presented one only works for another
+///   [`ROTATION_GRACE_SECS`] seconds.
+///
+/// * ``Some((email, None))`` - the credential has just been rotated by a concurrent
+///   request, whose response gives the client the current one. The client keeps its
+///   cookie as it is.
+///
+/// * ``None`` - unknown selector, expired, or validator mismatch. On validator mismatch
+///   all persistent logins of the employee are deleted.
+///
+pub async fn rotate_persistent_login(
+    pool: &Pool<MySql>,
+    token: &PersistentLoginToken,
+    days_valid_for: u64 ) -> Option<(String, Option<PersistentLoginToken>)> {
+    let (email, validator_hash, just_rotated, expired) = sqlx::query(
+        "SELECT email, validator_hash,
+            IFNULL(previous_validator_hash = ? AND rotated_at > UTC_TIMESTAMP() - INTERVAL ? SECOND, FALSE),
+            expires_at <= UTC_TIMESTAMP()
+        FROM persistent_logins WHERE selector = ?")
+    .bind(token.validator_hash()).bind(ROTATION_GRACE_SECS)
+    .bind(&token.selector)
+    .map(|row: sqlx::mysql::MySqlRow| -> (String, String, bool, bool) {
+        (row.get(0), row.get(1), row.get(2), row.get(3))
+    })
+    .fetch_optional(pool).await.unwrap()?;
+
+    if just_rotated && !expired {
+        return Some((email, None));
+    }
+
+    // Hashes are compared, so timing reveals nothing about the stored validator.
+    if validator_hash != token.validator_hash() {
+        tracing::warn!("Persistent login {} presented an old validator, revoking all persistent logins of {}",
+            token.selector, email);
+        delete_persistent_logins(pool, &email).await;
+        return None;
+    }
+
+    if expired {
+        delete_persistent_login(pool, &token.selector).await;
+        return None;
+    }
+
+    let rotated = token.rotate();
+
+    // Guarded by the old hash: of two concurrent rotations, only one succeeds. The
+    // other request presented the validator just replaced, which is accepted.
+    let rows_affected = sqlx::query("UPDATE persistent_logins SET
+            previous_validator_hash = validator_hash, validator_hash = ?,
+            last_used_at = UTC_TIMESTAMP(), rotated_at = UTC_TIMESTAMP(),
+            expires_at = UTC_TIMESTAMP() + INTERVAL ? DAY
+        WHERE selector = ? AND validator_hash = ?")
+    .bind(rotated.validator_hash()).bind(days_valid_for)
+    .bind(&token.selector).bind(&validator_hash)
+    .execute(pool).await.unwrap()
+    .rows_affected();
+
+    match rows_affected {
+        0 => Some((email, None)),
+        _ => Some((email, Some(rotated))),
+    }
+}
+
+/// Deletes a single persistent login, e.g. on logout.
+pub async fn delete_persistent_login(
+    pool: &Pool<MySql>,
+    selector: &str ) {
+    sqlx::query("DELETE FROM persistent_logins WHERE selector = ?")
+    .bind(selector)
+    .execute(pool).await.unwrap();
+}
+
+/// Deletes all persistent logins of an employee, e.g. on password change.
+///
+/// # Return
+///
+/// - The number of persistent logins deleted.
+///
+pub async fn delete_persistent_logins(
+    pool: &Pool<MySql>,
+    email: &str ) -> u64 {
+    sqlx::query("DELETE FROM persistent_logins WHERE email = ?")
+    .bind(email)
+    .execute(pool).await.unwrap()
+    .rows_affected()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::persistent_login::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::persistent_login::tests::test_generate_parse -- --exact
+///    * cargo test models::persistent_login::tests::test_parse_malformed -- --exact
+///    * cargo test models::persistent_login::tests::test_rotate -- --exact
+///    * cargo test models::persistent_login::tests::test_validator_hash -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_parse() {
+        let token = PersistentLoginToken::generate();
+
+        assert_eq!(token.selector.len(), 24);
+        assert_eq!(token.validator.len(), 64);
+        assert_eq!(PersistentLoginToken::parse(&token.cookie_value()), Some(token.clone()));
+
+        assert_ne!(PersistentLoginToken::generate(), token);
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let token = PersistentLoginToken::generate();
+
+        for value in [
+            String::from(""),
+            token.selector.clone(),
+            format!("{}{}", token.selector, token.validator),
+            format!("{}:{}", token.selector, &token.validator[1..]),
+            format!("{}:{}", &token.selector[1..], token.validator),
+            format!("{}:{}z", token.selector, &token.validator[1..]),
+            format!("{}:{}:", token.selector, token.validator),
+        ] {
+            assert_eq!(PersistentLoginToken::parse(&value), None, "{} should not parse", value);
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        let token = PersistentLoginToken::generate();
+        let rotated = token.rotate();
+
+        assert_eq!(rotated.selector, token.selector);
+        assert_ne!(rotated.validator, token.validator);
+        assert_ne!(rotated.validator_hash(), token.validator_hash());
+    }
+
+    #[test]
+    fn test_validator_hash() {
+        let token = PersistentLoginToken {
+            selector: String::from("000102030405060708090a0b"),
+            validator: String::from("password"),
+        };
+
+        assert_eq!(token.validator_hash(), "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8");
+    }
+}
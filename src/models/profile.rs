@@ -0,0 +1,200 @@
+/* Date Created: 18/10/2026. */
+
+//! The profile of an employee: the ``employees`` row, with its current title, and the
+//! current department, salary and manager. All read by one query.
+//!
+//! An employee with more than one current department has the one joined last as the
+//! current department. The manager is the current manager of that department, the one
+//! appointed last; ``None`` for the manager of the department.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::profile::tests
+//!
+
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use sqlx::{Row, Pool, MySql, QueryBuilder};
+use sqlx::types::time::Date;
+
+use crate::bh_libs::australian_date::australian_date_format;
+use crate::models::Employee;
+use crate::models::department::CURRENT_TO_DATE;
+use crate::models::title::push_current_title;
+
+/// The current department of an employee.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileDepartment {
+    pub dept_no: String,
+    pub dept_name: String,
+    /// When the employee joined the department.
+    #[serde(with = "australian_date_format")]
+    pub from_date: Date,
+}
+
+/// The current manager of an employee.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProfileManager {
+    pub emp_no: i32,
+    pub first_name: String,
+    pub last_name: String,
+}
+
+/// See the module documentation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmployeeProfile {
+    pub employee: Employee,
+    pub department: Option<ProfileDepartment>,
+    /// The current salary. ``None`` if there is none, or the salary was not requested.
+    pub salary: Option<Decimal>,
+    pub manager: Option<ProfileManager>,
+}
+
+/// Makes the profile query: ``employees`` columns, current title, current department
+/// ``dept_no``, ``dept_name`` and ``from_date``, current salary, and the current
+/// manager's ``emp_no``, ``first_name`` and ``last_name``.
+///
+/// The salary is ``NULL`` when ``with_salary`` is ``false``: the ``salaries`` table is
+/// then not read at all.
+fn profile_builder<'a>(emp_no: i32, with_salary: bool) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT employees.emp_no,
+        employees.email, employees.birth_date, employees.first_name, employees.last_name,
+        employees.gender, employees.hire_date, ");
+    push_current_title(&mut builder);
+    builder.push(", de.dept_no, d.dept_name, de.from_date, ")
+        .push(if with_salary { "CAST(s.salary AS DECIMAL(12, 2))" } else { "CAST(NULL AS DECIMAL(12, 2))" })
+        .push(", m.emp_no, m.first_name, m.last_name FROM employees")
+        .push(" LEFT JOIN dept_emp de ON de.emp_no = employees.emp_no AND de.to_date = ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" AND de.dept_no = (SELECT cde.dept_no FROM dept_emp cde WHERE cde.emp_no = employees.emp_no \
+            AND cde.to_date = ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" ORDER BY cde.from_date DESC, cde.dept_no LIMIT 1)")
+        .push(" LEFT JOIN departments d ON d.dept_no = de.dept_no");
+
+    if with_salary {
+        builder.push(" LEFT JOIN salaries s ON s.emp_no = employees.emp_no AND s.to_date = ")
+            .push_bind(CURRENT_TO_DATE);
+    }
+
+    // Deleted managers are skipped, not reported as a manager without a name.
+    builder.push(" LEFT JOIN (dept_manager dm JOIN employees m ON m.emp_no = dm.emp_no \
+            AND m.deleted_at IS NULL) ON dm.dept_no = de.dept_no AND dm.to_date = ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" AND dm.emp_no <> employees.emp_no WHERE employees.emp_no = ")
+        .push_bind(emp_no)
+        .push(" AND employees.deleted_at IS NULL ORDER BY dm.from_date DESC LIMIT 1");
+
+    builder
+}
+
+/// Retrieves the profile of an employee.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `emp_no` - the employee number.
+///
+/// * `with_salary` - ``false`` to leave out the salary, e.g. for callers without the
+///   HR permission, see [`crate::models::permission`].
+///
+/// # Return
+///
+/// * ``None`` if there is no such employee.
+///
+pub async fn select_employee_profile(
+    pool: &Pool<MySql>,
+    emp_no: i32,
+    with_salary: bool
+) -> Option<EmployeeProfile> {
+    profile_builder(emp_no, with_salary).build()
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let dept_no: Option<String> = row.get(8);
+        let manager_emp_no: Option<i32> = row.get(12);
+
+        EmployeeProfile {
+            employee: Employee {
+                emp_no: row.get(0),
+                email: row.get(1),
+                birth_date: row.get(2),
+                first_name: row.get(3),
+                last_name: row.get(4),
+                gender: row.get(5),
+                hire_date: row.get(6),
+                title: row.get(7),
+            },
+            department: dept_no.map(|dept_no| ProfileDepartment {
+                dept_no,
+                dept_name: row.get(9),
+                from_date: row.get(10),
+            }),
+            salary: row.get(11),
+            manager: manager_emp_no.map(|emp_no| ProfileManager {
+                emp_no,
+                first_name: row.get(13),
+                last_name: row.get(14),
+            }),
+        }
+    })
+    .fetch_optional(pool).await.unwrap()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::profile::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::profile::tests::test_profile_sql -- --exact
+///    * cargo test models::profile::tests::test_employee_profile_serde -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use super::*;
+
+    #[test]
+    fn test_profile_sql() {
+        let sql = String::from(profile_builder(10004, true).sql());
+        assert!(sql.contains("de.from_date, CAST(s.salary AS DECIMAL(12, 2)), m.emp_no"));
+        assert!(sql.contains(" LEFT JOIN salaries s ON s.emp_no = employees.emp_no AND s.to_date = ?"));
+        assert!(sql.ends_with(" LEFT JOIN (dept_manager dm JOIN employees m ON m.emp_no = dm.emp_no \
+            AND m.deleted_at IS NULL) ON dm.dept_no = de.dept_no AND dm.to_date = ? \
+            AND dm.emp_no <> employees.emp_no WHERE employees.emp_no = ? \
+            AND employees.deleted_at IS NULL ORDER BY dm.from_date DESC LIMIT 1"));
+
+        let sql = String::from(profile_builder(10004, false).sql());
+        assert!(sql.contains("de.from_date, CAST(NULL AS DECIMAL(12, 2)), m.emp_no"));
+        assert!(!sql.contains("salaries"));
+    }
+
+    #[test]
+    fn test_employee_profile_serde() {
+        let profile = EmployeeProfile {
+            employee: Employee {
+                emp_no: 10004,
+                email: String::from("chirstian.koblick.10004@gmail.com"),
+                birth_date: date!(1954 - 05 - 01),
+                first_name: String::from("Chirstian"),
+                last_name: String::from("Koblick"),
+                gender: String::from("M"),
+                hire_date: date!(1986 - 12 - 01),
+                title: Some(String::from("Senior Engineer")),
+            },
+            department: Some(ProfileDepartment {
+                dept_no: String::from("d004"),
+                dept_name: String::from("Production"),
+                from_date: date!(1986 - 12 - 01),
+            }),
+            salary: None,
+            manager: None,
+        };
+
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(json.ends_with(r#""title":"Senior Engineer"},"department":{"dept_no":"d004","dept_name":"Production","from_date":"01/12/1986"},"salary":null,"manager":null}"#));
+
+        let profile: EmployeeProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(profile.employee.emp_no, 10004);
+        assert_eq!(profile.department.unwrap().from_date, date!(1986 - 12 - 01));
+    }
+}
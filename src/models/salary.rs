@@ -0,0 +1,349 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents the ``salaries`` table in the database, and salary statistics.
+//!
+//! ``salaries`` rows are effective dated: from ``from_date`` to ``to_date``. The current
+//! rows have ``to_date`` of [`CURRENT_TO_DATE`].
+//!
+//! Amounts are [`Decimal`], which serialise as exact decimal strings, e.g. ``"60117.00"``,
+//! never as floats.
+//!
+//! Statistics are of the current salaries of current members of a department, or of
+//! current holders of a title. Percentiles are interpolated linearly between the two
+//! nearest ranks, as ``PERCENTILE_CONT``: the ``p`` percentile of ``n`` ordered salaries
+//! is at the 1-based position ``1 + p / 100 * (n - 1)``.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::salary::tests
+//!
+
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use serde::{Serialize, Deserialize};
+use sqlx::{Row, Pool, MySql, QueryBuilder};
+use sqlx::types::time::Date;
+
+use crate::bh_libs::australian_date::australian_date_format;
+use crate::helper::messages::SALARY_PERCENTILE_INVALID_MSG;
+use crate::models::department::CURRENT_TO_DATE;
+
+/// Percentiles computed when the request does not specify any.
+pub const DEFAULT_PERCENTILES: &str = "25,50,75,90";
+
+/// The most percentiles a request may ask for.
+pub const MAX_PERCENTILES: usize = 10;
+
+/// An employee's ``salaries`` row.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Salary {
+    pub salary: Decimal,
+    #[serde(with = "australian_date_format")]
+    pub from_date: Date,
+    #[serde(with = "australian_date_format")]
+    pub to_date: Date,
+    pub current: bool,
+}
+
+/// What salary statistics are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SalaryGroup {
+    /// Grouped by current ``dept_emp``: ``key`` is ``dept_no``, ``name`` is ``dept_name``.
+    Department,
+    /// Grouped by current ``titles``: ``key`` and ``name`` are the title.
+    Title,
+}
+
+impl SalaryGroup {
+    /// Selects ``group_key``, ``group_name`` and ``salary`` of current salaries of
+    /// employees who are not deleted.
+    fn push_current_salaries(&self, builder: &mut QueryBuilder<'_, MySql>) {
+        match self {
+            Self::Department => builder.push("SELECT de.dept_no AS group_key, d.dept_name AS group_name, s.salary
+                FROM salaries s JOIN dept_emp de ON de.emp_no = s.emp_no AND de.to_date = ")
+                .push_bind(CURRENT_TO_DATE)
+                .push(" JOIN departments d ON d.dept_no = de.dept_no"),
+            Self::Title => builder.push("SELECT t.title AS group_key, t.title AS group_name, s.salary
+                FROM salaries s JOIN titles t ON t.emp_no = s.emp_no AND t.to_date = ")
+                .push_bind(CURRENT_TO_DATE),
+        };
+
+        builder.push(" JOIN employees e ON e.emp_no = s.emp_no WHERE s.to_date = ")
+            .push_bind(CURRENT_TO_DATE)
+            .push(" AND e.deleted_at IS NULL");
+    }
+}
+
+/// The query string of salary statistics routes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SalaryStatsQuery {
+    /// Comma separated percentiles between ``0`` and ``100``, with at most 3 decimal
+    /// places, e.g. ``50,90,99.5``. Defaults to [`DEFAULT_PERCENTILES`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentiles: Option<String>,
+}
+
+impl SalaryStatsQuery {
+    /// Parses ``percentiles``, keeping the requested order.
+    ///
+    /// # Return
+    ///
+    /// * ``Err(String)`` - [`SALARY_PERCENTILE_INVALID_MSG`] followed by the percentiles,
+    ///   if any is not a number between ``0`` and ``100``, or has more than 3 decimal
+    ///   places, or there are none or more than [`MAX_PERCENTILES`] of them.
+    ///
+    pub fn resolve(&self) -> Result<Vec<Decimal>, String> {
+        let value = self.percentiles.as_deref().unwrap_or(DEFAULT_PERCENTILES);
+        let invalid = || format!("{} {}", SALARY_PERCENTILE_INVALID_MSG, value);
+
+        let percentiles = value.split(',')
+            .map(|percentile| percentile.trim().parse::<Decimal>().map_err(|_| invalid()))
+            .collect::<Result<Vec<Decimal>, String>>()?;
+
+        let valid = !percentiles.is_empty() && percentiles.len() <= MAX_PERCENTILES
+            && percentiles.iter().all(|percentile| percentile.scale() <= 3
+                && *percentile >= Decimal::ZERO && *percentile <= Decimal::ONE_HUNDRED);
+
+        if valid { Ok(percentiles) } else { Err(invalid()) }
+    }
+}
+
+/// A percentile of the salaries of a group.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SalaryPercentile {
+    pub percentile: Decimal,
+    pub salary: Decimal,
+}
+
+/// Salary statistics of a group, see [`SalaryGroup`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SalaryStats {
+    pub key: String,
+    pub name: String,
+    /// The number of salaries, i.e. employees.
+    pub employees: i64,
+    pub min: Decimal,
+    pub max: Decimal,
+    /// Rounded to 2 decimal places.
+    pub mean: Decimal,
+    /// In the requested order, rounded to 2 decimal places.
+    pub percentiles: Vec<SalaryPercentile>,
+}
+
+/// Retrieves the salary history of an employee, ordered by ``from_date``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `emp_no` - the employee number.
+///
+pub async fn select_employee_salaries(
+    pool: &Pool<MySql>,
+    emp_no: i32
+) -> Vec<Salary> {
+    select_employees_salaries(pool, &[emp_no]).await.remove(&emp_no).unwrap_or_default()
+}
+
+/// Retrieves the salary histories of employees, each ordered by ``from_date``.
+///
+/// # Return
+///
+/// - The histories by ``emp_no``. Employees without salaries are not in it.
+///
+pub async fn select_employees_salaries(
+    pool: &Pool<MySql>,
+    emp_nos: &[i32]
+) -> HashMap<i32, Vec<Salary>> {
+    let mut salaries: HashMap<i32, Vec<Salary>> = HashMap::new();
+    if emp_nos.is_empty() {
+        return salaries;
+    }
+
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT emp_no,
+        CAST(salary AS DECIMAL(12, 2)), from_date, to_date FROM salaries WHERE emp_no IN (");
+    let mut separated = builder.separated(", ");
+    for emp_no in emp_nos {
+        separated.push_bind(*emp_no);
+    }
+    separated.push_unseparated(") ORDER BY emp_no, from_date");
+
+    let rows = builder.build()
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let to_date: Date = row.get(3);
+
+        (row.get::<i32, _>(0), Salary {
+            salary: row.get(1),
+            from_date: row.get(2),
+            to_date,
+            current: to_date == CURRENT_TO_DATE,
+        })
+    })
+    .fetch_all(pool).await.unwrap();
+
+    for (emp_no, salary) in rows {
+        salaries.entry(emp_no).or_default().push(salary);
+    }
+    salaries
+}
+
+/// Makes the query reading the number, minimum, maximum and mean of the salaries
+/// of each group, ordered by ``group_key``.
+fn salary_summary_builder<'a>(group: SalaryGroup) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT group_key, group_name, COUNT(*),
+        CAST(MIN(salary) AS DECIMAL(12, 2)), CAST(MAX(salary) AS DECIMAL(12, 2)),
+        CAST(AVG(salary) AS DECIMAL(12, 2)) FROM (");
+    group.push_current_salaries(&mut builder);
+    builder.push(") cs GROUP BY group_key, group_name ORDER BY group_key");
+
+    builder
+}
+
+/// Makes the query reading the percentiles of the salaries of each group: rows of
+/// ``group_key``, the index of the percentile in ``percentiles``, and the salary.
+///
+/// Each salary is ranked within its group. For each percentile, the two ranks around
+/// its position are weighted by the distance to the position.
+fn salary_percentiles_builder<'a>(
+    group: SalaryGroup,
+    percentiles: &[Decimal]
+) -> QueryBuilder<'a, MySql> {
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new("WITH ranked AS (SELECT group_key, salary,
+        ROW_NUMBER() OVER (PARTITION BY group_key ORDER BY salary) AS rn,
+        COUNT(*) OVER (PARTITION BY group_key) AS cnt FROM (");
+    group.push_current_salaries(&mut builder);
+    builder.push(") cs), percentiles AS (");
+
+    for (idx, percentile) in percentiles.iter().enumerate() {
+        if idx > 0 {
+            builder.push(" UNION ALL ");
+        }
+        builder.push("SELECT ").push_bind(idx as i64)
+            .push(" AS idx, CAST(").push_bind(*percentile).push(" AS DECIMAL(6, 3)) AS p");
+    }
+
+    builder.push(") SELECT group_key, CAST(idx AS SIGNED), CAST(SUM(salary * CASE WHEN rn = FLOOR(pos)
+        THEN 1 - (pos - FLOOR(pos)) ELSE pos - FLOOR(pos) END) AS DECIMAL(12, 2))
+        FROM (SELECT r.group_key, r.rn, r.salary, pc.idx, 1 + pc.p / 100 * (r.cnt - 1) AS pos
+            FROM ranked r CROSS JOIN percentiles pc) rp
+        WHERE rn = FLOOR(pos) OR rn = FLOOR(pos) + 1
+        GROUP BY group_key, idx ORDER BY group_key, idx");
+
+    builder
+}
+
+/// Retrieves the salary statistics of each group, ordered by ``key``. See the module
+/// documentation.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `group` - by department or by title.
+///
+/// * `percentiles` - as resolved by [`SalaryStatsQuery::resolve`].
+///
+pub async fn select_salary_stats(
+    pool: &Pool<MySql>,
+    group: SalaryGroup,
+    percentiles: &[Decimal]
+) -> Vec<SalaryStats> {
+    let mut stats = salary_summary_builder(group).build()
+    .map(|row: sqlx::mysql::MySqlRow| SalaryStats {
+        key: row.get(0),
+        name: row.get(1),
+        employees: row.get(2),
+        min: row.get(3),
+        max: row.get(4),
+        mean: row.get(5),
+        percentiles: Vec::with_capacity(percentiles.len()),
+    })
+    .fetch_all(pool).await.unwrap();
+
+    let group_indexes: HashMap<String, usize> = stats.iter().enumerate()
+        .map(|(index, group_stats)| (group_stats.key.clone(), index))
+        .collect();
+
+    let rows = salary_percentiles_builder(group, percentiles).build()
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let (group_key, idx, salary): (String, i64, Decimal) = (row.get(0), row.get(1), row.get(2));
+        (group_key, idx, salary)
+    })
+    .fetch_all(pool).await.unwrap();
+
+    for (group_key, idx, salary) in rows {
+        if let Some(index) = group_indexes.get(&group_key) {
+            stats[*index].percentiles.push(SalaryPercentile {
+                percentile: percentiles[idx as usize], salary });
+        }
+    }
+
+    stats
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::salary::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::salary::tests::test_resolve_percentiles -- --exact
+///    * cargo test models::salary::tests::test_salary_serde -- --exact
+///    * cargo test models::salary::tests::test_salary_percentiles_sql -- --exact
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use time::macros::date;
+    use super::*;
+
+    fn query(percentiles: Option<&str>) -> SalaryStatsQuery {
+        SalaryStatsQuery { percentiles: percentiles.map(String::from) }
+    }
+
+    #[test]
+    fn test_resolve_percentiles() {
+        let decimals = |values: &[&str]| values.iter()
+            .map(|value| Decimal::from_str(value).unwrap()).collect::<Vec<Decimal>>();
+
+        assert_eq!(query(None).resolve().unwrap(), decimals(&["25", "50", "75", "90"]));
+        assert_eq!(query(Some("99.5, 0,100")).resolve().unwrap(), decimals(&["99.5", "0", "100"]));
+
+        for invalid in ["", "50,", "abc", "-1", "100.001", "12.3456", "1,2,3,4,5,6,7,8,9,10,11"] {
+            let err = query(Some(invalid)).resolve().unwrap_err();
+            assert!(err.starts_with(SALARY_PERCENTILE_INVALID_MSG), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_salary_serde() {
+        let salary = Salary {
+            salary: Decimal::from_str("60117.00").unwrap(),
+            from_date: date!(1986 - 06 - 26),
+            to_date: CURRENT_TO_DATE,
+            current: true,
+        };
+
+        let json = serde_json::to_value(&salary).unwrap();
+        assert_eq!(json["salary"], "60117.00");
+        assert_eq!(json["from_date"], "26/06/1986");
+
+        let salary: Salary = serde_json::from_value(json).unwrap();
+        assert_eq!(salary.salary, Decimal::new(6011700, 2));
+    }
+
+    #[test]
+    fn test_salary_percentiles_sql() {
+        let percentiles = [Decimal::from(50), Decimal::from(90)];
+
+        let sql = String::from(salary_percentiles_builder(SalaryGroup::Title, &percentiles).sql());
+        assert!(sql.contains("JOIN titles t ON t.emp_no = s.emp_no AND t.to_date = ?"));
+        assert!(sql.contains("SELECT ? AS idx, CAST(? AS DECIMAL(6, 3)) AS p UNION ALL SELECT ? AS idx"));
+        assert!(sql.ends_with("GROUP BY group_key, idx ORDER BY group_key, idx"));
+
+        let sql = String::from(salary_summary_builder(SalaryGroup::Department).sql());
+        assert!(sql.contains("JOIN departments d ON d.dept_no = de.dept_no \
+            JOIN employees e ON e.emp_no = s.emp_no WHERE s.to_date = ? AND e.deleted_at IS NULL"));
+        assert!(sql.ends_with("GROUP BY group_key, group_name ORDER BY group_key"));
+    }
+}
@@ -0,0 +1,272 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents the ``sessions`` table in the database: a registry of logged in
+//! sessions, which caps how many concurrent sessions an employee may hold.
+//!
+//! A session is identified by the access token session Id, see
+//! [`crate::helper::jwt_utils::JWTPayload::session_id`]. It is active while it is not
+//! revoked, and has been used within [`crate::config::Config::jwt_mins_valid_for`]
+//! minutes: after that, its access token has expired.
+//!
+//! The maximum number of active sessions of an employee is, in order of precedence:
+//! ``employees.max_sessions``, the ``MAX_SESSIONS_PER_ROLE`` entry for the employee
+//! ``employees.role``, then ``MAX_SESSIONS``. ``0`` means no limit. When a new login
+//! would exceed the maximum, ``SESSION_LIMIT_POLICY`` decides: ``reject`` the new
+//! login, or ``evict_oldest`` active sessions.
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::session::tests
+//!
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use sqlx::{Row, Pool, MySql, Executor};
+
+use crate::config::Config;
+
+/// ``sessions.revoked_reason`` of sessions evicted by a newer login.
+pub static REVOKED_EVICTED: &str = "evicted";
+/// ``sessions.revoked_reason`` of sessions ended by logging out.
+pub static REVOKED_LOGOUT: &str = "logout";
+
+/// ``SESSION_LIMIT_POLICY`` in the ``.env`` file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionLimitPolicy {
+    /// The new login fails.
+    Reject,
+    /// The oldest active sessions are revoked to make room for the new login.
+    EvictOldest,
+}
+
+impl FromStr for SessionLimitPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "reject" => Ok(Self::Reject),
+            "evict_oldest" => Ok(Self::EvictOldest),
+            _ => Err(format!("Unknown session limit policy {}", value)),
+        }
+    }
+}
+
+/// Parses ``MAX_SESSIONS_PER_ROLE``, e.g. ``admin:2,hr:5``, into role to maximum.
+///
+/// # Panics
+///
+/// On an entry which is not ``role:number``. This is a configuration error which
+/// should stop the application from starting.
+///
+pub fn parse_max_sessions_per_role(value: &str) -> HashMap<String, u32> {
+    value.split(',')
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (role, max) = entry.split_once(':')
+                .unwrap_or_else(|| panic!("MAX_SESSIONS_PER_ROLE entry {} must be role:number", entry));
+
+            (role.trim().to_lowercase(), max.trim().parse::<u32>()
+                .unwrap_or_else(|_| panic!("MAX_SESSIONS_PER_ROLE entry {} must be role:number", entry)))
+        })
+        .collect()
+}
+
+/// The state of a session, as seen by a request using its access token.
+#[derive(Debug, PartialEq)]
+pub enum SessionState {
+    /// Not revoked.
+    Active,
+    /// Not in the registry, e.g. tokens issued before the registry existed.
+    Unknown,
+    /// Revoked, the value is ``sessions.revoked_reason``.
+    Revoked(String),
+}
+
+/// Returns the maximum number of active sessions of an employee. ``0`` means no limit.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `email` - the logged in email. Logins without a matching ``employees`` row, e.g.
+///   some LDAP logins, get ``MAX_SESSIONS``.
+///
+/// * `config` - where ``MAX_SESSIONS`` and ``MAX_SESSIONS_PER_ROLE`` are.
+///
+pub async fn max_sessions(
+    pool: &Pool<MySql>,
+    email: &str,
+    config: &Config ) -> u32 {
+    let employee = sqlx::query("SELECT role, max_sessions FROM employees WHERE email = ?")
+    .bind(email)
+    .map(|row: sqlx::mysql::MySqlRow| -> (String, Option<u32>) {
+        (row.get(0), row.get(1))
+    })
+    .fetch_optional(pool).await.unwrap();
+
+    match employee {
+        Some((_, Some(max))) => max,
+        Some((role, None)) => *config.max_sessions_per_role
+            .get(&role.to_lowercase())
+            .unwrap_or(&config.max_sessions),
+        None => config.max_sessions,
+    }
+}
+
+/// Registers a new session, if the employee has not reached the maximum number of
+/// active sessions. See the [module documentation](self).
+///
+/// The count, the evictions and the insert are a transaction which locks the
+/// employee's row and sessions: concurrent logins of an employee are serialised, and
+/// can't exceed the maximum together.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `email` - the logged in email.
+///
+/// * `session_id` - the new access token session Id.
+///
+/// * `config` - where the limits and the policy are.
+///
+/// # Return
+///
+/// * ``true`` if the session has been registered, possibly after evicting older ones.
+///
+/// * ``false`` if the limit has been reached, and the policy is ``reject``.
+///
+pub async fn open_session(
+    pool: &Pool<MySql>,
+    email: &str,
+    session_id: &str,
+    config: &Config ) -> bool {
+    let max = max_sessions(pool, email, config).await as usize;
+
+    let mut transaction = pool.begin().await.unwrap();
+
+    if max > 0 {
+        // Also when the employee has no sessions yet, which leaves nothing to lock in
+        // ``sessions``.
+        sqlx::query("SELECT emp_no FROM employees WHERE email = ? FOR UPDATE")
+        .bind(email)
+        .execute(&mut *transaction).await.unwrap();
+
+        let active: Vec<String> = sqlx::query("SELECT session_id FROM sessions
+            WHERE email = ? AND revoked_at IS NULL AND last_active_at > UTC_TIMESTAMP() - INTERVAL ? MINUTE
+            ORDER BY created_at ASC FOR UPDATE")
+        .bind(email).bind(config.jwt_mins_valid_for)
+        .map(|row: sqlx::mysql::MySqlRow| row.get(0))
+        .fetch_all(&mut *transaction).await.unwrap();
+
+        if active.len() >= max {
+            match config.session_limit_policy {
+                SessionLimitPolicy::Reject => {
+                    tracing::info!("{} has {} active sessions, maximum {}: login rejected",
+                        email, active.len(), max);
+                    return false;
+                }
+                SessionLimitPolicy::EvictOldest => {
+                    for evicted in active.iter().take(active.len() + 1 - max) {
+                        tracing::info!("{} has {} active sessions, maximum {}: session {} evicted",
+                            email, active.len(), max, evicted);
+                        revoke_session(&mut *transaction, evicted, REVOKED_EVICTED).await;
+                    }
+                }
+            }
+        }
+    }
+
+    sqlx::query("INSERT INTO sessions (session_id, email, created_at, last_active_at)
+        VALUES (?, ?, UTC_TIMESTAMP(), UTC_TIMESTAMP())")
+    .bind(session_id).bind(email)
+    .execute(&mut *transaction).await.unwrap();
+
+    transaction.commit().await.unwrap();
+
+    true
+}
+
+/// Records that a session has been used, and returns its state.
+pub async fn touch_session(
+    pool: &Pool<MySql>,
+    session_id: &str ) -> SessionState {
+    let rows_affected = sqlx::query("UPDATE sessions SET last_active_at = UTC_TIMESTAMP()
+        WHERE session_id = ? AND revoked_at IS NULL")
+    .bind(session_id)
+    .execute(pool).await.unwrap()
+    .rows_affected();
+
+    if rows_affected > 0 {
+        return SessionState::Active;
+    }
+
+    sqlx::query("SELECT revoked_reason FROM sessions WHERE session_id = ?")
+    .bind(session_id)
+    .map(|row: sqlx::mysql::MySqlRow| -> Option<String> { row.get(0) })
+    .fetch_optional(pool).await.unwrap()
+    .map_or(SessionState::Unknown, |reason| SessionState::Revoked(reason.unwrap_or_default()))
+}
+
+/// Revokes a session: requests using its access token are rejected from now on.
+///
+/// # Arguments
+///
+/// * `executor` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL
+///   connection, or a transaction.
+///
+/// * `session_id` - the access token session Id.
+///
+/// * `reason` - e.g. [`REVOKED_EVICTED`], [`REVOKED_LOGOUT`].
+///
+pub async fn revoke_session<'c, E>(
+    executor: E,
+    session_id: &str,
+    reason: &str )
+where
+    E: Executor<'c, Database = MySql> {
+    sqlx::query("UPDATE sessions SET revoked_at = UTC_TIMESTAMP(), revoked_reason = ?
+        WHERE session_id = ? AND revoked_at IS NULL")
+    .bind(reason).bind(session_id)
+    .execute(executor).await.unwrap();
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::session::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::session::tests::test_session_limit_policy_from_str -- --exact
+///    * cargo test models::session::tests::test_parse_max_sessions_per_role -- --exact
+///    * cargo test models::session::tests::test_parse_max_sessions_per_role_invalid -- --exact
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_limit_policy_from_str() {
+        assert_eq!("reject".parse::<SessionLimitPolicy>(), Ok(SessionLimitPolicy::Reject));
+        assert_eq!("Evict_Oldest".parse::<SessionLimitPolicy>(), Ok(SessionLimitPolicy::EvictOldest));
+        assert!("evict".parse::<SessionLimitPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_parse_max_sessions_per_role() {
+        let max = parse_max_sessions_per_role(" admin:2, HR:5 ,");
+
+        assert_eq!(max.len(), 2);
+        assert_eq!(max.get("admin"), Some(&2));
+        assert_eq!(max.get("hr"), Some(&5));
+
+        assert!(parse_max_sessions_per_role("").is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "must be role:number")]
+    fn test_parse_max_sessions_per_role_invalid() {
+        parse_max_sessions_per_role("admin:two");
+    }
+}
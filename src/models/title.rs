@@ -0,0 +1,173 @@
+/* Date Created: 18/10/2026. */
+
+//! Represents the ``titles`` table in the database.
+//!
+//! ``titles`` rows are effective dated: from ``from_date`` to ``to_date``. The current
+//! rows have ``to_date`` of [`CURRENT_TO_DATE`]. An employee's current title is also
+//! in [`crate::models::Employee`].
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test models::title::tests
+//!
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use sqlx::{Row, Pool, MySql, QueryBuilder};
+use sqlx::types::time::Date;
+
+use crate::bh_libs::australian_date::australian_date_format;
+use crate::models::department::CURRENT_TO_DATE;
+
+/// Maximum length of ``titles.title``.
+pub const TITLE_MAX_LEN: usize = 50;
+
+/// An employee's ``titles`` row.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmployeeTitle {
+    pub title: String,
+    #[serde(with = "australian_date_format")]
+    pub from_date: Date,
+    #[serde(with = "australian_date_format")]
+    pub to_date: Date,
+    pub current: bool,
+}
+
+/// A title, and how many employees currently hold it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TitleCount {
+    pub title: String,
+    pub current_employees: i64,
+}
+
+/// Appends the current title of the ``employees`` row of the outer query, ``NULL`` if
+/// none. E.g. ``SELECT emp_no, ..., (SELECT ...) AS title FROM employees``.
+pub fn push_current_title(builder: &mut QueryBuilder<'_, MySql>) {
+    builder.push("(SELECT t.title FROM titles t WHERE t.emp_no = employees.emp_no AND t.to_date = ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" ORDER BY t.from_date DESC LIMIT 1)");
+}
+
+/// Appends the condition that the current title of the ``employees`` row of the outer
+/// query is like ``title``.
+pub fn push_current_title_like(builder: &mut QueryBuilder<'_, MySql>, title: &str) {
+    builder.push("EXISTS (SELECT 1 FROM titles t WHERE t.emp_no = employees.emp_no AND t.to_date = ")
+        .push_bind(CURRENT_TO_DATE)
+        .push(" AND t.title LIKE ")
+        .push_bind(String::from(title))
+        .push(")");
+}
+
+/// Retrieves the title history of an employee, ordered by ``from_date``.
+///
+/// # Arguments
+///
+/// * `pool` - [`sqlx::Pool`]&lt;[`sqlx::MySql`]&gt;, an already established MySQL connection.
+///
+/// * `emp_no` - the employee number.
+///
+pub async fn select_employee_titles(
+    pool: &Pool<MySql>,
+    emp_no: i32
+) -> Vec<EmployeeTitle> {
+    select_employees_titles(pool, &[emp_no]).await.remove(&emp_no).unwrap_or_default()
+}
+
+/// Retrieves the title histories of employees, each ordered by ``from_date``.
+///
+/// # Return
+///
+/// - The histories by ``emp_no``. Employees without titles are not in it.
+///
+pub async fn select_employees_titles(
+    pool: &Pool<MySql>,
+    emp_nos: &[i32]
+) -> HashMap<i32, Vec<EmployeeTitle>> {
+    let mut titles: HashMap<i32, Vec<EmployeeTitle>> = HashMap::new();
+    if emp_nos.is_empty() {
+        return titles;
+    }
+
+    let mut builder: QueryBuilder<MySql> = QueryBuilder::new(
+        "SELECT emp_no, title, from_date, to_date FROM titles WHERE emp_no IN (");
+    let mut separated = builder.separated(", ");
+    for emp_no in emp_nos {
+        separated.push_bind(*emp_no);
+    }
+    separated.push_unseparated(") ORDER BY emp_no, from_date, title");
+
+    let rows = builder.build()
+    .map(|row: sqlx::mysql::MySqlRow| {
+        let to_date: Date = row.get(3);
+
+        (row.get::<i32, _>(0), EmployeeTitle {
+            title: row.get(1),
+            from_date: row.get(2),
+            to_date,
+            current: to_date == CURRENT_TO_DATE,
+        })
+    })
+    .fetch_all(pool).await.unwrap();
+
+    for (emp_no, title) in rows {
+        titles.entry(emp_no).or_default().push(title);
+    }
+    titles
+}
+
+/// Retrieves all titles, ordered by ``title``, with their current number of employees.
+/// Deleted employees are not counted.
+pub async fn select_titles(pool: &Pool<MySql>) -> Vec<TitleCount> {
+    sqlx::query("SELECT t.title, CAST(SUM(t.to_date = ? AND e.deleted_at IS NULL) AS SIGNED)
+        FROM titles t JOIN employees e ON e.emp_no = t.emp_no GROUP BY t.title ORDER BY t.title")
+    .bind(CURRENT_TO_DATE)
+    .map(|row: sqlx::mysql::MySqlRow| TitleCount {
+        title: row.get(0),
+        current_employees: row.get(1),
+    })
+    .fetch_all(pool).await.unwrap()
+}
+
+/// To run these tests below:
+///
+///    * cargo test models::title::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test models::title::tests::test_current_title_sql -- --exact
+///    * cargo test models::title::tests::test_employee_title_serde -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use super::*;
+
+    #[test]
+    fn test_current_title_sql() {
+        let mut builder: QueryBuilder<MySql> = QueryBuilder::new("SELECT emp_no, ");
+        push_current_title(&mut builder);
+        builder.push(" AS title FROM employees WHERE ");
+        push_current_title_like(&mut builder, "Senior%");
+
+        assert_eq!(builder.sql(), "SELECT emp_no, (SELECT t.title FROM titles t \
+            WHERE t.emp_no = employees.emp_no AND t.to_date = ? ORDER BY t.from_date DESC LIMIT 1) \
+            AS title FROM employees WHERE EXISTS (SELECT 1 FROM titles t \
+            WHERE t.emp_no = employees.emp_no AND t.to_date = ? AND t.title LIKE ?)");
+    }
+
+    #[test]
+    fn test_employee_title_serde() {
+        let title = EmployeeTitle {
+            title: String::from("Senior Engineer"),
+            from_date: date!(1995 - 12 - 01),
+            to_date: CURRENT_TO_DATE,
+            current: true,
+        };
+
+        let json = serde_json::to_string(&title).unwrap();
+        assert_eq!(json, r#"{"title":"Senior Engineer","from_date":"01/12/1995","to_date":"01/01/9999","current":true}"#);
+
+        let title: EmployeeTitle = serde_json::from_str(&json).unwrap();
+        assert_eq!(title.to_date, CURRENT_TO_DATE);
+    }
+}
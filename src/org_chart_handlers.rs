@@ -0,0 +1,152 @@
+/* Date Created: 18/10/2026. */
+
+//! Org chart request handlers: the management tree of the whole company or of a
+//! department, and the current managers of an employee. As JSON under ``/data``.
+//!
+//! Management trees are also available as Graphviz DOT and SVG with the ``format``
+//! query parameter, see [`crate::helper::org_chart_export`].
+
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_web::http::{header, StatusCode};
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::select_employee_by_no;
+use crate::models::org_chart::{
+    OrgNode, select_company_org_chart, select_department_org_chart, select_employee_managers
+};
+use crate::helper::org_chart_export::{OrgChartFormat, to_dot, to_svg};
+use crate::helper::app_utils::{make_api_status_response, make_data_response};
+use crate::helper::messages::{DEPARTMENT_NOT_FOUND_MSG, EMPLOYEE_NOT_FOUND_MSG};
+
+/// Query parameters of the org chart routes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct OrgChartQuery {
+    /// ``json``, ``dot`` or ``svg``. Defaults to ``json``.
+    pub format: Option<String>,
+    /// Department org chart only: the maximum number of employees other than the
+    /// managers. Defaults to ``0``, capped at ``PAGE_LIMIT_MAX``.
+    pub limit: Option<u32>,
+}
+
+/// Retrieves the management tree of the whole company: departments, and their current
+/// managers.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/org-chart``
+/// * Route: ``http://localhost:5000/data/org-chart?format=svg``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an
+/// [OrgNode](`crate::models::org_chart::OrgNode`). Or the DOT or SVG document.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`).
+///
+#[get("/org-chart")]
+pub async fn company_org_chart(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<OrgChartQuery>
+) -> HttpResponse {
+    let format = match org_chart_format(&query) {
+        Ok(format) => format,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    org_chart_response(format, "org-chart", &select_company_org_chart(&app_state.db).await)
+}
+
+/// Retrieves the management tree of a department: its current managers, and optionally
+/// its other current employees.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/departments/d005/org-chart``
+/// * Route: ``http://localhost:5000/data/departments/d005/org-chart?limit=20&format=dot``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: same as [`company_org_chart`].
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) or
+/// [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/departments/{dept_no}/org-chart")]
+pub async fn department_org_chart(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    query: web::Query<OrgChartQuery>
+) -> HttpResponse {
+    let format = match org_chart_format(&query) {
+        Ok(format) => format,
+        Err(message) => return make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    };
+
+    let dept_no = req.match_info().get("dept_no").unwrap();
+    let reports = query.limit.unwrap_or(0).min(app_state.cfg.page_limit_max);
+
+    match select_department_org_chart(&app_state.db, dept_no, reports).await {
+        Some(department) => org_chart_response(format,
+            &format!("org-chart-{}", department.id), &department),
+        None => make_api_status_response(StatusCode::NOT_FOUND, DEPARTMENT_NOT_FOUND_MSG, None),
+    }
+}
+
+/// Retrieves the current managers of an employee: the current managers of the
+/// employee's current departments.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/10004/manager``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [ReportingLine](`crate::models::org_chart::ReportingLine`). Empty if the employee
+/// has no current manager.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/employees/{emp_no:\\d+}/manager")]
+pub async fn employee_manager_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+) -> HttpResponse {
+    let employee = match req.match_info().get("emp_no").unwrap().parse::<i32>() {
+        Ok(emp_no) => select_employee_by_no(&app_state.db, emp_no).await,
+        Err(_) => None,
+    };
+
+    match employee {
+        Some(employee) => make_data_response(StatusCode::OK,
+            select_employee_managers(&app_state.db, employee.emp_no).await),
+        None => make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    }
+}
+
+fn org_chart_format(query: &OrgChartQuery) -> Result<OrgChartFormat, String> {
+    query.format.as_deref().map_or(Ok(OrgChartFormat::Json), str::parse)
+}
+
+/// JSON is wrapped in [`crate::models::DataResponse`]. DOT is an attachment, to be
+/// rendered by Graphviz. SVG is inline, to be shown by browsers.
+fn org_chart_response(format: OrgChartFormat, file_stem: &str, root: &OrgNode) -> HttpResponse {
+    let (body, disposition) = match format {
+        OrgChartFormat::Json => return make_data_response(StatusCode::OK, root),
+        OrgChartFormat::Dot => (to_dot(root), "attachment"),
+        OrgChartFormat::Svg => (to_svg(root), "inline"),
+    };
+
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((header::CONTENT_DISPOSITION,
+            format!("{}; filename=\"{}.{}\"", disposition, file_stem, format.extension())))
+        .body(body)
+}
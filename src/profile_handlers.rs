@@ -0,0 +1,94 @@
+/* Date Created: 18/10/2026. */
+
+//! Employee profile request handlers: an employee with the current department, title,
+//! salary and manager, in one response. As JSON under ``/data``, and as HTML under
+//! ``/ui``.
+//!
+//! The salary is only included for the HR permission, see [`crate::models::permission`].
+//! It is ``null`` for everyone else.
+
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_web::http::StatusCode;
+
+use tera::{Context, Tera};
+
+use crate::helper::jwt_utils::JWTPayload;
+use crate::models::permission::{Permission, has_permission};
+use crate::models::profile::{EmployeeProfile, select_employee_profile};
+use crate::helper::app_utils::{make_api_status_response, make_data_response};
+use crate::helper::messages::EMPLOYEE_NOT_FOUND_MSG;
+
+/// Retrieves the profile of an employee.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/10004/profile``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an
+/// [EmployeeProfile](`crate::models::profile::EmployeeProfile`).
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/employees/{emp_no:\\d+}/profile")]
+pub async fn employee_profile_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>
+) -> HttpResponse {
+    match employee_profile(&req, &app_state, &jwt_payload).await {
+        Some(profile) => make_data_response(StatusCode::OK, profile),
+        None => make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    }
+}
+
+/// Retrieves the profile of an employee as a complete HTML page, based on
+/// ``templates/profile/employee_profile.html``. See [`employee_profile_json`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/ui/employees/10004``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: HTML. Failure: JSON, same as [`employee_profile_json`].
+///
+#[get("/employees/{emp_no:\\d+}")]
+pub async fn employee_profile_html(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>
+) -> HttpResponse {
+    let profile = match employee_profile(&req, &app_state, &jwt_payload).await {
+        Some(profile) => profile,
+        None => return make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    };
+
+    let mut ctx = Context::new();
+    ctx.insert("profile", &profile);
+
+    let tera = Tera::new("templates/profile/**/*").unwrap();
+
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(tera.render("employee_profile.html", &ctx).expect("Failed to render template"))
+}
+
+/// The profile of the ``emp_no`` path segment, with the salary if the logged in user
+/// has the HR permission.
+async fn employee_profile(
+    req: &HttpRequest,
+    app_state: &super::AppState,
+    jwt_payload: &JWTPayload
+) -> Option<EmployeeProfile> {
+    let emp_no = req.match_info().get("emp_no")?.parse::<i32>().ok()?;
+
+    let with_salary = has_permission(&app_state.db, &app_state.cfg,
+        &jwt_payload.email(), Permission::Hr).await;
+
+    select_employee_profile(&app_state.db, emp_no, with_salary).await
+}
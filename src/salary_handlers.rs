@@ -0,0 +1,125 @@
+/* Date Created: 18/10/2026. */
+
+//! Salary-related request handlers: the salary history of an employee, and salary
+//! statistics per department and per title. As JSON under ``/data``.
+//!
+//! All of them require the HR permission, see [`crate::models::permission`]. Requests
+//! without it get [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), before anything
+//! else is checked.
+
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_web::http::StatusCode;
+
+use crate::helper::jwt_utils::JWTPayload;
+use crate::models::select_employee_by_no;
+use crate::models::permission::Permission;
+use crate::models::salary::{
+    SalaryGroup, SalaryStatsQuery,
+    select_employee_salaries, select_salary_stats
+};
+use crate::helper::app_utils::{make_api_status_response, make_data_response, forbidden_response};
+use crate::helper::messages::EMPLOYEE_NOT_FOUND_MSG;
+
+/// Retrieves the salary history of an employee, ordered by ``from_date``.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/10004/salaries``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [Salary](`crate::models::salary::Salary`).
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or
+/// [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/employees/{emp_no:\\d+}/salaries")]
+pub async fn employee_salaries_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(&app_state, &jwt_payload.email(), Permission::Hr).await {
+        return response;
+    }
+
+    let employee = match req.match_info().get("emp_no").unwrap().parse::<i32>() {
+        Ok(emp_no) => select_employee_by_no(&app_state.db, emp_no).await,
+        Err(_) => None,
+    };
+
+    match employee {
+        Some(employee) => make_data_response(StatusCode::OK,
+            select_employee_salaries(&app_state.db, employee.emp_no).await),
+        None => make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    }
+}
+
+/// Retrieves the current salary statistics of each department: number of employees,
+/// minimum, maximum, mean and percentiles.
+///
+/// # Arguments
+///
+/// * `stats_query` - ``percentiles``, see [`SalaryStatsQuery`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/salaries/stats/departments``
+/// * Route: ``http://localhost:5000/data/salaries/stats/departments?percentiles=10,50,90``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [SalaryStats](`crate::models::salary::SalaryStats`), ordered by ``dept_no``.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [FORBIDDEN](`actix_web::http::StatusCode::FORBIDDEN`), or
+/// [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for invalid percentiles.
+///
+#[get("/salaries/stats/departments")]
+pub async fn department_salary_stats_json(
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    stats_query: web::Query<SalaryStatsQuery>
+) -> HttpResponse {
+    salary_stats_response(&app_state, &jwt_payload, &stats_query, SalaryGroup::Department).await
+}
+
+/// Retrieves the current salary statistics of each title. See
+/// [`department_salary_stats_json`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/salaries/stats/titles``
+/// * Route: ``http://localhost:5000/data/salaries/stats/titles?percentiles=50``
+/// * Method: ``GET``
+///
+#[get("/salaries/stats/titles")]
+pub async fn title_salary_stats_json(
+    app_state: web::Data<super::AppState>,
+    jwt_payload: web::ReqData<JWTPayload>,
+    stats_query: web::Query<SalaryStatsQuery>
+) -> HttpResponse {
+    salary_stats_response(&app_state, &jwt_payload, &stats_query, SalaryGroup::Title).await
+}
+
+async fn salary_stats_response(
+    app_state: &super::AppState,
+    jwt_payload: &JWTPayload,
+    stats_query: &SalaryStatsQuery,
+    group: SalaryGroup
+) -> HttpResponse {
+    if let Some(response) = forbidden_response(app_state, &jwt_payload.email(), Permission::Hr).await {
+        return response;
+    }
+
+    match stats_query.resolve() {
+        Ok(percentiles) => make_data_response(StatusCode::OK,
+            select_salary_stats(&app_state.db, group, &percentiles).await),
+        Err(message) => make_api_status_response(StatusCode::BAD_REQUEST, &message, None),
+    }
+}
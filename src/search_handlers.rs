@@ -0,0 +1,88 @@
+/* Date Created: 18/10/2026. */
+
+//! Full-text employee search request handlers, under ``/data``. See
+//! [`crate::search_index`].
+
+use actix_web::{get, web, HttpResponse};
+use actix_web::http::StatusCode;
+
+use serde::{Serialize, Deserialize};
+
+use crate::models::Employee;
+use crate::models::employee_search::select_employees_by_nos;
+use crate::helper::app_utils::{make_api_status_response, make_data_response};
+use crate::helper::messages::{SEARCH_TEXT_REQUIRED_MSG, SEARCH_INDEX_FAILURE_MSG};
+
+/// Query parameters of the full-text search.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FullTextQuery {
+    /// Words to find in names, emails and titles. E.g. ``jon smith``.
+    pub q: Option<String>,
+    /// The maximum number of results. Defaults to ``PAGE_LIMIT_DEFAULT``, capped at
+    /// ``PAGE_LIMIT_MAX``.
+    pub limit: Option<u32>,
+}
+
+/// A search result: the relevance score, and the employee.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScoredEmployee {
+    pub score: f32,
+    #[serde(flatten)]
+    pub employee: Employee,
+}
+
+/// Searches employees by names, emails and titles, typo tolerant and ranked. E.g.
+/// ``jon`` also finds ``John``.
+///
+/// # Arguments
+///
+/// * `query` - ``q`` and ``limit``, see [`FullTextQuery`].
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/search?q=jon``
+/// * Route: ``http://localhost:5000/data/employees/search?q=chirstian%20koblik&limit=5``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [ScoredEmployee], highest ``score`` first.
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [BAD_REQUEST](`actix_web::http::StatusCode::BAD_REQUEST`) for no
+/// ``q``, or [INTERNAL_SERVER_ERROR](`actix_web::http::StatusCode::INTERNAL_SERVER_ERROR`)
+/// when the index can't be read.
+///
+#[get("/employees/search")]
+pub async fn search_employees_json(
+    app_state: web::Data<super::AppState>,
+    query: web::Query<FullTextQuery>
+) -> HttpResponse {
+    let text = query.q.as_deref().unwrap_or_default().trim();
+    if text.is_empty() {
+        return make_api_status_response(StatusCode::BAD_REQUEST, SEARCH_TEXT_REQUIRED_MSG, None);
+    }
+
+    let limit = query.limit.unwrap_or(app_state.cfg.page_limit_default)
+        .clamp(1, app_state.cfg.page_limit_max);
+
+    let hits = match app_state.search_index.search(text, limit as usize) {
+        Ok(hits) => hits,
+        Err(err) => {
+            tracing::error!("Search of {:?} failed: {}", text, err);
+            return make_api_status_response(StatusCode::INTERNAL_SERVER_ERROR, SEARCH_INDEX_FAILURE_MSG, None);
+        }
+    };
+
+    let emp_nos: Vec<i32> = hits.iter().map(|hit| hit.emp_no).collect();
+
+    // In hit order. Employees deleted since they were indexed are left out.
+    let results: Vec<ScoredEmployee> = select_employees_by_nos(&app_state.db, &emp_nos).await
+        .into_iter()
+        .filter_map(|employee| hits.iter().find(|hit| hit.emp_no == employee.emp_no)
+            .map(|hit| ScoredEmployee { score: hit.score, employee }))
+        .collect();
+
+    make_data_response(StatusCode::OK, results)
+}
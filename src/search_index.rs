@@ -0,0 +1,401 @@
+/* Date Created: 18/10/2026. */
+
+//! Full-text employee search, using a [tantivy](https://docs.rs/tantivy/latest/tantivy/)
+//! index on local disk, in the ``SEARCH_INDEX_DIR`` entry of the ``.env`` file.
+//!
+//! Each employee is a document of ``emp_no``, ``first_name``, ``last_name``, ``email``
+//! and the current ``title``. The database remains the source of truth: a search only
+//! finds ``emp_no``s, the employees are then read from the database.
+//!
+//! A search is typo tolerant: each word of the query matches a word of the employee
+//! exactly, as a prefix, or within an edit distance of 1, or 2 for words of 6 or more
+//! letters. E.g. ``Jon`` finds ``John``. Exact matches rank highest, then prefixes, then
+//! typos; and names rank above emails and titles.
+//!
+//! Handlers which write employees keep the index in sync, see
+//! [`EmployeeIndex::sync_employees`]. ``learn_actix_web rebuild-search-index`` rebuilds
+//! it from the database, see [`crate::cli`].
+//!
+//! To run tests for this module only:
+//!
+//!     * cargo test search_index::tests
+//!
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use actix_web::web;
+use futures_util::StreamExt;
+use sqlx::{Pool, MySql};
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query, TermQuery};
+use tantivy::schema::{Field, IndexRecordOption, Schema, Value, FAST, INDEXED, STORED, TEXT};
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+
+use crate::models::Employee;
+use crate::models::employee_search::{employees_select_builder, select_employees_by_nos};
+
+/// The writer memory budget of a few documents, the tantivy minimum.
+const SYNC_WRITER_MEMORY: usize = 15_000_000;
+/// The writer memory budget of a rebuild.
+const REBUILD_WRITER_MEMORY: usize = 100_000_000;
+
+/// The relative weight of a match in each text field.
+const NAME_BOOST: f32 = 3.0;
+const EMAIL_BOOST: f32 = 1.0;
+const TITLE_BOOST: f32 = 1.0;
+
+/// The relative weight of prefix and typo matches, to exact matches.
+const PREFIX_BOOST: f32 = 0.5;
+const FUZZY_BOOST: f32 = 0.25;
+
+struct IndexFields {
+    emp_no: Field,
+    first_name: Field,
+    last_name: Field,
+    email: Field,
+    title: Field,
+}
+
+impl IndexFields {
+    fn schema() -> (Schema, IndexFields) {
+        let mut builder = Schema::builder();
+
+        let fields = IndexFields {
+            emp_no: builder.add_i64_field("emp_no", INDEXED | STORED | FAST),
+            first_name: builder.add_text_field("first_name", TEXT),
+            last_name: builder.add_text_field("last_name", TEXT),
+            email: builder.add_text_field("email", TEXT),
+            title: builder.add_text_field("title", TEXT),
+        };
+
+        (builder.build(), fields)
+    }
+
+    /// The text fields, and the weight of a match in each.
+    fn text_fields(&self) -> [(Field, f32); 4] {
+        [
+            (self.first_name, NAME_BOOST),
+            (self.last_name, NAME_BOOST),
+            (self.email, EMAIL_BOOST),
+            (self.title, TITLE_BOOST),
+        ]
+    }
+
+    fn document(&self, employee: &Employee) -> TantivyDocument {
+        doc!(
+            self.emp_no => i64::from(employee.emp_no),
+            self.first_name => employee.first_name.as_str(),
+            self.last_name => employee.last_name.as_str(),
+            self.email => employee.email.as_str(),
+            self.title => employee.title.as_deref().unwrap_or_default(),
+        )
+    }
+
+    fn emp_no_term(&self, emp_no: i32) -> Term {
+        Term::from_field_i64(self.emp_no, i64::from(emp_no))
+    }
+}
+
+/// The edit distance allowed for a query word: none for short words, which would match
+/// too much otherwise.
+fn fuzzy_distance(word: &str) -> u8 {
+    match word.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// A ranked search result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchHit {
+    pub emp_no: i32,
+    pub score: f32,
+}
+
+/// The employee search index. Searches run concurrently; writes are serialised, and
+/// each is committed and visible to searches when it returns.
+///
+/// The index writer is only held while writing, so that another process, e.g. a
+/// rebuild, can write to the same directory.
+pub struct EmployeeIndex {
+    index: Index,
+    reader: IndexReader,
+    fields: IndexFields,
+    write_lock: Mutex<()>,
+}
+
+impl EmployeeIndex {
+    /// Opens the index in a directory, creating both if they don't exist. A new index
+    /// is empty: run a rebuild to fill it.
+    pub fn open(dir: &Path) -> tantivy::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+
+        let (schema, fields) = IndexFields::schema();
+        let index = Index::open_or_create(MmapDirectory::open(dir)?, schema)?;
+
+        // Also picks up commits by other processes.
+        let reader = index.reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()?;
+
+        Ok(EmployeeIndex { index, reader, fields, write_lock: Mutex::new(()) })
+    }
+
+    /// The number of indexed employees.
+    pub fn num_docs(&self) -> u64 {
+        self.reader.searcher().num_docs()
+    }
+
+    /// Makes the query of a search text. ``None`` if the text has no words.
+    fn query(&self, text: &str) -> tantivy::Result<Option<BooleanQuery>> {
+        let mut words: Vec<String> = Vec::new();
+        self.index.tokenizer_for_field(self.fields.first_name)?
+            .token_stream(text)
+            .process(&mut |token| words.push(token.text.clone()));
+
+        if words.is_empty() {
+            return Ok(None);
+        }
+
+        // Every word must match, in any field.
+        let word_queries = words.iter().map(|word| {
+            let mut matches: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+            for (field, boost) in self.fields.text_fields() {
+                let term = Term::from_field_text(field, word);
+
+                matches.push((Occur::Should, Box::new(BoostQuery::new(
+                    Box::new(TermQuery::new(term.clone(), IndexRecordOption::WithFreqs)), boost))));
+                matches.push((Occur::Should, Box::new(BoostQuery::new(
+                    Box::new(FuzzyTermQuery::new_prefix(term.clone(), 0, true)), boost * PREFIX_BOOST))));
+
+                let distance = fuzzy_distance(word);
+                if distance > 0 {
+                    matches.push((Occur::Should, Box::new(BoostQuery::new(
+                        Box::new(FuzzyTermQuery::new(term, distance, true)), boost * FUZZY_BOOST))));
+                }
+            }
+
+            (Occur::Must, Box::new(BooleanQuery::new(matches)) as Box<dyn Query>)
+        }).collect();
+
+        Ok(Some(BooleanQuery::new(word_queries)))
+    }
+
+    /// Searches employees.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - words to find in names, emails and titles, e.g. ``jon smit``.
+    ///
+    /// * `limit` - the maximum number of results.
+    ///
+    /// # Return
+    ///
+    /// * The best matches, highest score first. Empty if `text` has no words.
+    ///
+    pub fn search(&self, text: &str, limit: usize) -> tantivy::Result<Vec<SearchHit>> {
+        let Some(query) = self.query(text)? else {
+            return Ok(Vec::new());
+        };
+
+        let searcher = self.reader.searcher();
+        let mut hits: Vec<SearchHit> = Vec::new();
+
+        for (score, address) in searcher.search(&query, &TopDocs::with_limit(limit))? {
+            let document: TantivyDocument = searcher.doc(address)?;
+
+            if let Some(emp_no) = document.get_first(self.fields.emp_no).and_then(|value| value.as_i64()) {
+                hits.push(SearchHit { emp_no: emp_no as i32, score });
+            }
+        }
+
+        Ok(hits)
+    }
+
+    /// Runs `write` with the index writer, then commits, and makes the changes visible.
+    fn write<F>(&self, memory: usize, write: F) -> tantivy::Result<()>
+    where
+        F: FnOnce(&mut IndexWriter) -> tantivy::Result<()>
+    {
+        let _guard = self.write_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut writer: IndexWriter = self.index.writer_with_num_threads(1, memory)?;
+        write(&mut writer)?;
+        writer.commit()?;
+
+        self.reader.reload()
+    }
+
+    /// Adds employees, replacing those already indexed.
+    pub fn index_employees(&self, employees: &[Employee]) -> tantivy::Result<()> {
+        self.write(SYNC_WRITER_MEMORY, |writer| {
+            for employee in employees {
+                writer.delete_term(self.fields.emp_no_term(employee.emp_no));
+                writer.add_document(self.fields.document(employee))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes employees.
+    pub fn remove_employees(&self, emp_nos: &[i32]) -> tantivy::Result<()> {
+        self.write(SYNC_WRITER_MEMORY, |writer| {
+            for emp_no in emp_nos {
+                writer.delete_term(self.fields.emp_no_term(*emp_no));
+            }
+            Ok(())
+        })
+    }
+
+    /// Brings employees up to date with the database, after they were written: those
+    /// found are indexed with their current title, the others are removed.
+    ///
+    /// The index is derived data: a failure is logged, not returned, so that it does not
+    /// fail the write. A rebuild fixes the index.
+    ///
+    /// Writing the index blocks, e.g. the commit syncs its files to disk: it runs on the
+    /// blocking thread pool, see [`actix_web::web::block`], not on the worker thread.
+    pub async fn sync_employees(self: &Arc<Self>, pool: &Pool<MySql>, emp_nos: &[i32]) {
+        if emp_nos.is_empty() {
+            return;
+        }
+
+        let employees = select_employees_by_nos(pool, emp_nos).await;
+        let removed: Vec<i32> = emp_nos.iter().copied()
+            .filter(|emp_no| !employees.iter().any(|employee| employee.emp_no == *emp_no))
+            .collect();
+
+        let index = Arc::clone(self);
+        let result = web::block(move || index.index_employees(&employees)
+            .and_then(|_| match removed.is_empty() {
+                true => Ok(()),
+                false => index.remove_employees(&removed),
+            }))
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|result| result.map_err(|err| err.to_string()));
+
+        if let Err(err) = result {
+            tracing::error!("Search index sync of {:?} failed: {}", emp_nos, err);
+        }
+    }
+
+    /// Replaces the whole index with all employees in the database. Searches see the old
+    /// index until this returns.
+    ///
+    /// The index directory lock keeps other writers out meanwhile: their writes fail,
+    /// and are logged, see [`EmployeeIndex::sync_employees`].
+    ///
+    /// # Return
+    ///
+    /// * The number of employees indexed.
+    ///
+    pub async fn rebuild(&self, pool: &Pool<MySql>) -> Result<u64, String> {
+        let mut writer: IndexWriter = self.index.writer_with_num_threads(1, REBUILD_WRITER_MEMORY)
+            .map_err(|err| err.to_string())?;
+        writer.delete_all_documents().map_err(|err| err.to_string())?;
+
+        let mut builder = employees_select_builder();
+        let mut rows = builder.build_query_as::<Employee>().fetch(pool);
+        let mut count: u64 = 0;
+
+        while let Some(row) = rows.next().await {
+            let employee = row.map_err(|err| err.to_string())?;
+            writer.add_document(self.fields.document(&employee)).map_err(|err| err.to_string())?;
+            count += 1;
+        }
+
+        writer.commit().map_err(|err| err.to_string())?;
+        self.reader.reload().map_err(|err| err.to_string())?;
+
+        Ok(count)
+    }
+}
+
+/// To run these tests below:
+///
+///    * cargo test search_index::tests
+///
+/// To run a specific test method:
+///
+///    * cargo test search_index::tests::test_fuzzy_distance -- --exact
+///    * cargo test search_index::tests::test_search -- --exact
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+    use super::*;
+
+    fn employee(emp_no: i32, first_name: &str, last_name: &str, title: Option<&str>) -> Employee {
+        Employee {
+            emp_no,
+            email: format!("{}.{}.{}@gmail.com", first_name, last_name, emp_no).to_lowercase(),
+            birth_date: date!(1955 - 12 - 14),
+            first_name: String::from(first_name),
+            last_name: String::from(last_name),
+            gender: String::from("M"),
+            hire_date: date!(1985 - 04 - 26),
+            title: title.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_distance() {
+        assert_eq!(fuzzy_distance("Al"), 0);
+        assert_eq!(fuzzy_distance("Jon"), 1);
+        assert_eq!(fuzzy_distance("Smith"), 1);
+        assert_eq!(fuzzy_distance("Koblick"), 2);
+    }
+
+    #[test]
+    fn test_search() {
+        let dir = std::env::temp_dir().join(format!("search_index_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let index = EmployeeIndex::open(&dir).unwrap();
+        assert_eq!(index.num_docs(), 0);
+
+        index.index_employees(&[
+            employee(1, "John", "Smith", Some("Engineer")),
+            employee(2, "Jon", "Smithers", Some("Senior Engineer")),
+            employee(3, "Jonathan", "Koblick", None),
+            employee(4, "Mary", "Jones", Some("Staff")),
+        ]).unwrap();
+        assert_eq!(index.num_docs(), 4);
+
+        let emp_nos = |text: &str| -> Vec<i32> {
+            index.search(text, 10).unwrap().iter().map(|hit| hit.emp_no).collect()
+        };
+
+        // The exact match first, then the prefix, then typos.
+        let hits = emp_nos("jon");
+        assert_eq!(hits[0], 2);
+        assert!(hits.contains(&1) && hits.contains(&3) && hits.contains(&4));
+        assert!(index.search("jon", 10).unwrap().windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        // Every word must match. A typo in a longer word.
+        assert_eq!(emp_nos("Jon Smiht"), vec![1]);
+        assert_eq!(emp_nos("koblik"), vec![3]);
+        // Titles and emails.
+        assert_eq!(emp_nos("senior"), vec![2]);
+        assert_eq!(emp_nos("mary.jones.4"), vec![4]);
+
+        assert!(emp_nos(" ,. ").is_empty());
+        assert_eq!(index.search("jon", 1).unwrap().len(), 1);
+
+        // Replaced, then removed.
+        index.index_employees(&[employee(4, "Mary", "Brown", Some("Staff"))]).unwrap();
+        assert_eq!(index.num_docs(), 4);
+        assert!(emp_nos("jones").is_empty());
+
+        index.remove_employees(&[1, 4]).unwrap();
+        assert_eq!(emp_nos("smith"), vec![2]);
+        assert_eq!(index.num_docs(), 2);
+
+        drop(index);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+/* Date Created: 18/10/2026. */
+
+//! Title-related request handlers: all titles, and the title history of an employee.
+//! As JSON under ``/data``.
+//!
+//! Employees are searched by current title with the ``title`` filter, see
+//! [`crate::models::employee_search::EmployeeFilter`].
+
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use actix_web::http::StatusCode;
+
+use crate::models::select_employee_by_no;
+use crate::models::title::{select_titles, select_employee_titles};
+use crate::helper::app_utils::{make_api_status_response, make_data_response};
+use crate::helper::messages::EMPLOYEE_NOT_FOUND_MSG;
+
+/// Retrieves all titles, with their current number of employees.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/titles``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [TitleCount](`crate::models::title::TitleCount`), ordered by ``title``.
+///
+#[get("/titles")]
+pub async fn titles_json(
+    app_state: web::Data<super::AppState>,
+) -> HttpResponse {
+    make_data_response(StatusCode::OK, select_titles(&app_state.db).await)
+}
+
+/// Retrieves the title history of an employee, ordered by ``from_date``.
+///
+/// # Usage Example
+///
+/// * Route: ``http://localhost:5000/data/employees/10004/titles``
+/// * Method: ``GET``
+///
+/// # Return
+///
+/// * Success: JSON of [`crate::models::DataResponse`], whose ``data`` is an array of
+/// [EmployeeTitle](`crate::models::title::EmployeeTitle`).
+///
+/// * Failure: JSON of [ApiStatus](`crate::bh_libs::api_status::ApiStatus`). Response
+/// status code is [NOT_FOUND](`actix_web::http::StatusCode::NOT_FOUND`).
+///
+#[get("/employees/{emp_no:\\d+}/titles")]
+pub async fn employee_titles_json(
+    req: HttpRequest,
+    app_state: web::Data<super::AppState>,
+) -> HttpResponse {
+    let employee = match req.match_info().get("emp_no").unwrap().parse::<i32>() {
+        Ok(emp_no) => select_employee_by_no(&app_state.db, emp_no).await,
+        Err(_) => None,
+    };
+
+    match employee {
+        Some(employee) => make_data_response(StatusCode::OK,
+            select_employee_titles(&app_state.db, employee.emp_no).await),
+        None => make_api_status_response(StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, None),
+    }
+}
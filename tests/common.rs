@@ -18,6 +18,7 @@ use learn_actix_web::models::LoginSuccessResponse;
 use learn_actix_web::helper::endpoint::http_status_code;
 
 use learn_actix_web::config::Config;
+use learn_actix_web::database::get_mysql_pool;
 
 use learn_actix_web::helper::jwt_utils::{
     make_token,
@@ -30,6 +31,9 @@ use learn_actix_web::helper::app_logger::init_app_logger;
 
 pub static JWT_SECS_VALID_FOR: u64 = 1800; // 30 minutes.
 
+/// An employee whose role tests set to ``hr``, and back to ``employee``.
+pub const HR_EMAIL: &str = "parto.bamford.10003@gmail.com";
+
 /// Reads and returns the JWT_SECRET_KEY value from .env file.
 /// 
 pub fn jwt_secret_key() -> String {
@@ -46,8 +50,11 @@ pub struct TestApp {
 
 impl TestApp {
     pub fn mock_access_token(&self, secs_valid_for: u64) -> String {
-        let token = make_token("chirstian.koblick.10004@gmail.com", 
-        jwt_secret_key().as_ref(), secs_valid_for);
+        self.mock_access_token_for("chirstian.koblick.10004@gmail.com", secs_valid_for)
+    }
+
+    pub fn mock_access_token_for(&self, email: &str, secs_valid_for: u64) -> String {
+        let token = make_token(email, jwt_secret_key().as_ref(), secs_valid_for);
 
         BEARER_TOKEN.to_owned() + &token
     }
@@ -153,6 +160,52 @@ pub fn make_api_url(root: &str, path: &str) -> String {
     format!("{}/api{}", root, path)
 }
 
+/// A connection to the test database, for setting up and checking rows directly.
+pub async fn test_pool() -> sqlx::Pool<sqlx::MySql> {
+    let config = Config::init();
+    get_mysql_pool(config.max_connections, &config.database_url).await
+}
+
+/// Sets ``employees.role`` of an employee.
+pub async fn set_role(email: &str, role: &str) {
+    sqlx::query("UPDATE employees SET role = ? WHERE email = ?")
+        .bind(role).bind(email)
+        .execute(&test_pool().await).await.unwrap();
+}
+
+/// Sends an authenticated ``GET`` request to ``url``, as the employee of
+/// [`TestApp::mock_access_token`].
+pub async fn get_authorised(client: &reqwest::Client, test_app: &TestApp, url: &str) -> reqwest::Response {
+    client
+        .get(url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// Sends a ``GET`` request to ``url`` with the access token of ``email``.
+pub async fn get_as(client: &reqwest::Client, test_app: &TestApp, email: &str, url: &str) -> reqwest::Response {
+    client
+        .get(url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token_for(email, JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// Returns the value of a response header, which must be present.
+pub fn header_value(response: &reqwest::Response, name: header::HeaderName) -> String {
+    String::from(response.headers().get(name).unwrap().to_str().unwrap())
+}
+
+/// Extracts the URL of a relation from a ``Link`` header.
+pub fn link_url(link: &str, rel: &str) -> Option<String> {
+    link.split(", ")
+        .find(|link| link.ends_with(&format!("; rel=\"{}\"", rel)))
+        .map(|link| String::from(&link[1..link.find('>').unwrap()]))
+}
+
 pub fn write_to_file(file_name: &str, content: &str) {
     use std::fs::File;
     use std::io::Write;
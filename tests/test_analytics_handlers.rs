@@ -0,0 +1,186 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in analytics_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/analytics/headcount``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/analytics/hires-separations``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/analytics/gender/departments``
+//! * Route: ``http://localhost:5000/data/analytics/gender/titles``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/analytics/tenure/departments``
+//! * Route: ``http://localhost:5000/data/analytics/tenure/titles``
+//! * Method: ``GET``
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_analytics_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_headcount_json -- --exact
+//!     * cargo test get_headcount_csv -- --exact
+//!     * cargo test get_headcount_invalid -- --exact
+//!     * cargo test get_hires_separations_json -- --exact
+//!     * cargo test get_gender_json -- --exact
+//!     * cargo test get_tenure_csv -- --exact
+//!
+use actix_web::http::{StatusCode, header};
+use rust_decimal::Decimal;
+
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::analytics::{Headcount, HiresSeparations, GenderDistribution, Tenure};
+
+mod common;
+use common::{spawn_app, make_data_url, header_value, get_authorised};
+
+use learn_actix_web::helper::messages::{REPORT_FORMAT_INVALID_MSG, ANALYTICS_YEARS_INVALID_MSG};
+
+/// * Route: ``http://localhost:5000/data/analytics/headcount?from_year=1995&to_year=1996``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_headcount_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/headcount?from_year=1995&to_year=1996")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let headcounts = response.json::<DataResponse<Vec<Headcount>>>().await.unwrap().data;
+    // 9 departments per year.
+    assert_eq!(headcounts.len(), 18);
+    assert!(headcounts[..9].iter().all(|headcount| headcount.year == 1995));
+    assert!(headcounts[9..].iter().all(|headcount| headcount.year == 1996));
+    assert_eq!(headcounts[0].dept_no, "d001");
+    assert!(headcounts.iter().all(|headcount| headcount.headcount > 0));
+
+    // Development grew.
+    assert_eq!(headcounts[4].dept_name, "Development");
+    assert!(headcounts[4].headcount < headcounts[13].headcount);
+}
+
+/// * Route: ``http://localhost:5000/data/analytics/headcount?format=csv``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_headcount_csv() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/headcount?format=csv")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_value(&response, header::CONTENT_TYPE), "text/csv; charset=utf-8");
+    assert_eq!(header_value(&response, header::CONTENT_DISPOSITION),
+        "attachment; filename=\"headcount.csv\"");
+
+    let csv = response.text().await.unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "year,dept_no,dept_name,headcount");
+    // The data is from 1985, each year has a row per department.
+    assert!(lines[1].starts_with("1985,d001,Marketing,"));
+    assert_eq!((lines.len() - 1) % 9, 0);
+}
+
+/// * Route: ``http://localhost:5000/data/analytics/headcount?format=xml``
+/// * Route: ``http://localhost:5000/data/analytics/headcount?from_year=2000&to_year=1999``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_headcount_invalid() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/headcount?format=xml")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, REPORT_FORMAT_INVALID_MSG, false).await;
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/hires-separations?from_year=2000&to_year=1999")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, ANALYTICS_YEARS_INVALID_MSG, true).await;
+}
+
+/// * Route: ``http://localhost:5000/data/analytics/hires-separations?from_year=1985&to_year=2002``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_hires_separations_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/hires-separations?from_year=1985&to_year=2002")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let years = response.json::<DataResponse<Vec<HiresSeparations>>>().await.unwrap().data;
+    assert_eq!(years.len(), 18);
+    assert_eq!(years[0].year, 1985);
+    assert!(years[0].hires > 0);
+    assert!(years.iter().map(|year| year.separations).sum::<i64>() > 0);
+}
+
+/// * Route: ``http://localhost:5000/data/analytics/gender/departments``
+/// * Route: ``http://localhost:5000/data/analytics/gender/titles``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_gender_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    // 9 departments, and 7 titles.
+    for (path, groups) in [("/analytics/gender/departments", 9), ("/analytics/gender/titles", 7)] {
+        let response = get_authorised(&client, test_app,
+            &make_data_url(&test_app.app_url, path)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let distributions = response.json::<DataResponse<Vec<GenderDistribution>>>().await.unwrap().data;
+        assert_eq!(distributions.len(), groups);
+
+        for distribution in &distributions {
+            assert_eq!(distribution.female + distribution.male, distribution.employees);
+        }
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/analytics/tenure/titles?format=csv``
+/// * Route: ``http://localhost:5000/data/analytics/tenure/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_tenure_csv() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/tenure/titles?format=csv")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_value(&response, header::CONTENT_DISPOSITION),
+        "attachment; filename=\"tenure-titles.csv\"");
+
+    let csv = response.text().await.unwrap();
+    assert!(csv.starts_with("key,name,employees,average_years\n"));
+    assert!(csv.contains("\nSenior Engineer,Senior Engineer,"));
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/analytics/tenure/departments")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let tenures = response.json::<DataResponse<Vec<Tenure>>>().await.unwrap().data;
+    assert_eq!(tenures.len(), 9);
+    assert!(tenures.iter().all(|tenure| tenure.employees > 0 && tenure.average_years > Decimal::ZERO));
+}
@@ -16,6 +16,8 @@
 //! * Method: ``POST``
 //! * Content Type: ``application/json``
 //! * Body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password"}``
+//! * Body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password", "remember_me": true}``
+//! * Body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password", "next": "/ui/home"}``
 //! 
 //! * Content Type: ``application/x-www-form-urlencoded`` (charset=UTF-8)
 //! * Body: ``email=chirstian.koblick.10004@gmail.com&password=password``
@@ -24,6 +26,20 @@
 //! * Method: ``POST``
 //! * Response: ``HTML``
 //! 
+//! * Route: ``http://localhost:5000/api/magic-link``
+//! * Method: ``POST``
+//! * Content Type: ``application/json``
+//! * Body: ``{"email": "chirstian.koblick.10004@gmail.com"}``
+//! 
+//! * Route: ``http://localhost:5000/api/magic-link/{token}``
+//! * Method: ``GET``
+//! * Response: ``HTML``
+//! 
+//! * Route: ``http://localhost:5000/api/password``
+//! * Method: ``POST``
+//! * Content Type: ``application/json``
+//! * Body: ``{"current_password": "password", "new_password": "letmein"}``
+//! 
 //! To run test for this module only: 
 //! 
 //!     * cargo test --test test_auth_handlers
@@ -33,6 +49,8 @@
 //!     * cargo test get_home_page_html -- --exact
 //!     * cargo test get_login_page_html -- --exact
 //!     * cargo test post_login_html -- --exact
+//!     * cargo test post_login_html_returns_to_next -- --exact
+//!     * cargo test post_login_json_next -- --exact
 //!     * cargo test post_login_html_error_empty -- --exact
 //!     * cargo test post_login_html_missing_field -- --exact
 //!     * cargo test post_login_json -- --exact
@@ -44,15 +62,43 @@
 //!     * cargo test post_login_json_failure_1 -- --exact 
 //!     * cargo test post_login_json_failure_2 -- --exact
 //!     * cargo test post_login_json_failure_3 -- --exact 
+//!     * cargo test post_login_json_remember_me -- --exact
 //!     * cargo test post_logout_html -- --exact
+//!     * cargo test post_login_json_session_evicted -- --exact
+//!     * cargo test post_login_json_concurrent_sessions -- --exact
+//!     * cargo test account_disabled_json -- --exact
+//!     * cargo test account_locked_json -- --exact
+//!     * cargo test account_must_change_password_json -- --exact
+//!     * cargo test magic_link_login_html -- --exact
+//!     * cargo test magic_link_login_other_browser -- --exact
+//!     * cargo test post_magic_link_json_unknown_email -- --exact
+//!     * cargo test post_magic_link_json_quoted_email -- --exact
+//!     * cargo test post_password_json_breached -- --exact
+//!     * cargo test post_password_json_current_mismatch -- --exact
 //! 
 use std::collections::HashMap;
 use actix_web::http::{StatusCode, header};
 
 mod common;
-use common::{spawn_app, JWT_SECS_VALID_FOR, make_api_url, make_ui_url};
-
-use learn_actix_web::helper::messages::LOGIN_FAILURE_MSG;
+use common::{spawn_app, JWT_SECS_VALID_FOR, make_api_url, make_ui_url, test_pool};
+
+use learn_actix_web::config::Config;
+use learn_actix_web::mailers::file::FileMailer;
+use learn_actix_web::helper::constants::PERSISTENT_LOGIN;
+use learn_actix_web::helper::messages::{
+    LOGIN_FAILURE_MSG,
+    UNAUTHORISED_ACCESS_MSG,
+    MAGIC_LINK_SENT_MSG,
+    MAGIC_LINK_INVALID_MSG,
+    BREACHED_PASSWORD_MSG,
+    CURRENT_PASSWORD_MISMATCH_MSG,
+    SESSION_EVICTED_MSG,
+    ACCOUNT_DISABLED_MSG,
+    ACCOUNT_LOCKED_MSG,
+    PASSWORD_CHANGE_REQUIRED_MSG,
+};
+use learn_actix_web::models::LoginSuccessResponse;
+use learn_actix_web::models::account_status::{AccountStatus, update_account_status};
 
 // use learn_actix_web::helper::jwt_utils::make_bearer_token;
 
@@ -123,6 +169,77 @@ async fn post_login_html() {
     common::assert_html_home_page(response).await;
 }
 
+/// * Route: ``http://localhost:5000/ui/home``
+/// * Method: ``GET``
+/// 
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/x-www-form-urlencoded``
+/// * Body: ``email=chirstian.koblick.10004@gmail.com&password=password``
+/// 
+/// A page requested while not logged in is returned to after logging in.
+#[actix_web::test]
+async fn post_login_html_returns_to_next() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_ui_url(&test_app.app_url, "/home"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_redirected_html_login_page(response, StatusCode::UNAUTHORIZED, 
+        UNAUTHORISED_ACCESS_MSG).await;
+
+    let mut params = HashMap::new();
+    params.insert("email", "chirstian.koblick.10004@gmail.com");
+    params.insert("password", "password");
+
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .form(&params)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Redirected, rather than served by /api/login.
+    assert_eq!(response.url().path(), "/ui/home");
+    common::assert_html_home_page(response).await;
+}
+
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password", "next": "/ui/home"}``
+/// 
+/// Only a same-origin ``next`` is returned.
+#[actix_web::test]
+async fn post_login_json_next() {
+    let test_app = &spawn_app().await;
+
+    for (next, expected) in [("/ui/home", Some("/ui/home")), ("https://evil.com/ui/home", None)] {
+        let json_data = serde_json::json!({
+            "email": "chirstian.koblick.10004@gmail.com",
+            "password": "password",
+            "next": next
+        });
+
+        let response = common::reqwest_client()
+            .post(make_api_url(&test_app.app_url, "/login"))
+            .json(&json_data)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let login_success = response.json::<LoginSuccessResponse>().await.unwrap();
+        assert_eq!(login_success.data.next.as_deref(), expected);
+    }
+}
+
 /// * Route: ``http://localhost:5000/api/login``
 /// * Method: ``POST``
 /// * Content Type: not set.
@@ -197,7 +314,6 @@ async fn post_login_json() {
         .await
         .expect("Failed to execute request.");
 
-
     let email = "saniya.kalloufi.10008@gmail.com";
 
     common::assert_access_token_in_header(&response, email);
@@ -223,7 +339,6 @@ async fn post_login_json_error_empty() {
         .await
         .expect("Failed to execute request.");
 
-
     common::assert_access_token_not_in_header(&response);
     common::assert_access_token_not_in_cookie(&response);
     //NOTE: "Content type error" message.
@@ -252,7 +367,6 @@ async fn post_login_json_missing_field() {
         .await
         .expect("Failed to execute request.");
 
-
     common::assert_access_token_not_in_header(&response);
     common::assert_access_token_not_in_cookie(&response);
     common::assert_json_failure(response, StatusCode::BAD_REQUEST, "missing field `email`", true).await;
@@ -292,7 +406,6 @@ async fn post_login_html_failure_1() {
         StatusCode::UNAUTHORIZED, LOGIN_FAILURE_MSG).await;
 }
 
-
 /// Password don't match. Returns login HTML page with message 
 /// [`learn_actix_web::helper::messages::LOGIN_FAILURE_MSG`]
 /// 
@@ -453,6 +566,85 @@ async fn post_login_json_failure_3() {
         "missing field `email`", true).await;
 }
 
+/// Returns the value of the ``persistent-login`` cookie set by the response, if any.
+fn persistent_login_cookie(response: &reqwest::Response) -> Option<String> {
+    response.cookies()
+        .find(|c| c.name() == PERSISTENT_LOGIN)
+        .map(|c| String::from(c.value()))
+}
+
+/// Logs in with ``remember_me``. A new client, which has only the ``persistent-login``
+/// cookie, gets the home page, and a rotated ``persistent-login`` cookie. The old
+/// ``persistent-login`` cookie still works right after, as for a concurrent request,
+/// but presenting it later revokes the persistent login.
+/// 
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "chirstian.koblick.10004@gmail.com", "password": "password", "remember_me": true}``
+/// 
+#[actix_web::test]
+async fn post_login_json_remember_me() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let json_data = serde_json::json!({
+        "email": "chirstian.koblick.10004@gmail.com",
+        "password": "password",
+        "remember_me": true
+    });
+
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    let persistent_login = persistent_login_cookie(&response);
+    assert!(persistent_login.is_some(), "Should have a persistent-login cookie.");
+    let persistent_login = persistent_login.unwrap();
+
+    common::assert_json_successful_login(response, "chirstian.koblick.10004@gmail.com").await;
+
+    // A new browser session: no access token, only the persistent-login cookie.
+    let response = common::reqwest_client()
+        .get(make_ui_url(&test_app.app_url, "/home"))
+        .header(header::COOKIE, format!("{}={}", PERSISTENT_LOGIN, persistent_login))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    let rotated = persistent_login_cookie(&response);
+    assert!(rotated.is_some(), "Should have a rotated persistent-login cookie.");
+    assert_ne!(rotated.unwrap(), persistent_login);
+
+    common::assert_access_token_in_header(&response, "chirstian.koblick.10004@gmail.com");
+    common::assert_html_home_page(response).await;
+
+    let old_login = || common::reqwest_client()
+        .get(make_ui_url(&test_app.app_url, "/home"))
+        .header(header::COOKIE, format!("{}={}", PERSISTENT_LOGIN, persistent_login))
+        .send();
+
+    // Within the grace window, e.g. another tab: accepted, the cookie is kept.
+    let response = old_login().await.expect("Failed to execute request.");
+    assert_eq!(persistent_login_cookie(&response), None);
+    common::assert_html_home_page(response).await;
+
+    // After it, the old persistent-login cookie no longer works.
+    let (selector, _) = persistent_login.split_once(':').unwrap();
+    sqlx::query("UPDATE persistent_logins SET rotated_at = rotated_at - INTERVAL 1 HOUR WHERE selector = ?")
+        .bind(selector)
+        .execute(&test_pool().await).await.unwrap();
+
+    let response = old_login().await.expect("Failed to execute request.");
+
+    common::assert_redirected_html_login_page(response, StatusCode::UNAUTHORIZED, 
+        UNAUTHORISED_ACCESS_MSG).await;
+}
+
 /// * Route: ``http://localhost:5000/api/logout``
 /// * Method: ``POST``
 /// * Response: ``HTML``
@@ -484,4 +676,421 @@ async fn post_logout_html() {
         .expect("Failed to execute request.");
 
     common::assert_html_login_page(response).await;
-}
\ No newline at end of file
+}
+
+/// Sets ``employees.max_sessions`` of an employee. ``None`` restores the configured limit.
+async fn set_max_sessions(email: &str, max_sessions: Option<u32>) {
+    sqlx::query("UPDATE employees SET max_sessions = ? WHERE email = ?")
+        .bind(max_sessions).bind(email)
+        .execute(&test_pool().await).await.unwrap();
+}
+
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "suzette.pettey.10024@gmail.com", "password": "password"}``
+/// 
+/// With a maximum of one active session, a second login evicts the first session.
+/// 
+/// Assumes ``SESSION_LIMIT_POLICY=evict_oldest`` in the ``.env`` file.
+#[actix_web::test]
+async fn post_login_json_session_evicted() {
+    let test_app = &spawn_app().await;
+
+    set_max_sessions("suzette.pettey.10024@gmail.com", Some(1)).await;
+
+    let mut json_data = HashMap::new();
+    json_data.insert("email", "suzette.pettey.10024@gmail.com");
+    json_data.insert("password", "password");
+
+    let first_client = common::reqwest_client();
+    let response = first_client
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_successful_login(response, "suzette.pettey.10024@gmail.com").await;
+
+    // Another browser.
+    let response = common::reqwest_client()
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_successful_login(response, "suzette.pettey.10024@gmail.com").await;
+
+    let response = first_client
+        .get(make_ui_url(&test_app.app_url, "/home"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    set_max_sessions("suzette.pettey.10024@gmail.com", None).await;
+
+    common::assert_json_failure(response, StatusCode::UNAUTHORIZED, SESSION_EVICTED_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "mayumi.schueller.10054@gmail.com", "password": "password"}``
+/// 
+/// With a maximum of one active session, concurrent logins leave one active session.
+/// 
+/// Assumes ``SESSION_LIMIT_POLICY=evict_oldest`` in the ``.env`` file.
+#[actix_web::test]
+async fn post_login_json_concurrent_sessions() {
+    let test_app = &spawn_app().await;
+
+    let email = "mayumi.schueller.10054@gmail.com";
+    set_max_sessions(email, Some(1)).await;
+
+    let mut json_data = HashMap::new();
+    json_data.insert("email", email);
+    json_data.insert("password", "password");
+
+    let logins = (0..5).map(|_| common::reqwest_client()
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send());
+
+    let responses = futures_util::future::join_all(logins).await;
+
+    let active: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sessions
+        WHERE email = ? AND revoked_at IS NULL AND last_active_at > UTC_TIMESTAMP() - INTERVAL 5 MINUTE")
+        .bind(email)
+        .fetch_one(&test_pool().await).await.unwrap();
+
+    set_max_sessions(email, None).await;
+
+    for response in responses {
+        assert_eq!(response.expect("Failed to execute request.").status(), StatusCode::OK);
+    }
+    assert_eq!(active, 1);
+}
+
+/// * Route: ``http://localhost:5000/ui/home``
+/// * Method: ``GET``
+/// 
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "bezalel.simmel.10002@gmail.com", "password": "password"}``
+/// 
+/// Disabling an account ends its existing session, and stops it from logging in.
+#[actix_web::test]
+async fn account_disabled_json() {
+    let test_app = &spawn_app().await;
+
+    let mut json_data = HashMap::new();
+    json_data.insert("email", "bezalel.simmel.10002@gmail.com");
+    json_data.insert("password", "password");
+
+    let client = common::reqwest_client();
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_successful_login(response, "bezalel.simmel.10002@gmail.com").await;
+
+    update_account_status(&test_pool().await, "bezalel.simmel.10002@gmail.com", AccountStatus::Disabled).await;
+
+    let response = client
+        .get(make_ui_url(&test_app.app_url, "/home"))
+        .header(header::CONTENT_TYPE, "application/json")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    let login_response = common::reqwest_client()
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    update_account_status(&test_pool().await, "bezalel.simmel.10002@gmail.com", AccountStatus::Active).await;
+
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, ACCOUNT_DISABLED_MSG, false).await;
+    common::assert_json_failure(login_response, StatusCode::FORBIDDEN, ACCOUNT_DISABLED_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/api/login``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "berni.genin.10014@gmail.com", "password": "password"}``
+/// 
+/// A locked account can't log in, and JSON clients get ``423 Locked``.
+#[actix_web::test]
+async fn account_locked_json() {
+    let test_app = &spawn_app().await;
+
+    let mut json_data = HashMap::new();
+    json_data.insert("email", "berni.genin.10014@gmail.com");
+    json_data.insert("password", "password");
+
+    update_account_status(&test_pool().await, "berni.genin.10014@gmail.com", AccountStatus::Locked).await;
+
+    let response = common::reqwest_client()
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    update_account_status(&test_pool().await, "berni.genin.10014@gmail.com", AccountStatus::Active).await;
+
+    common::assert_json_failure(response, StatusCode::LOCKED, ACCOUNT_LOCKED_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/ui/home``
+/// * Method: ``GET``
+/// 
+/// A ``must_change_password`` account can log in, but can only change password or log out.
+#[actix_web::test]
+async fn account_must_change_password_json() {
+    let test_app = &spawn_app().await;
+
+    update_account_status(&test_pool().await, "kyoichi.maliniak.10005@gmail.com", 
+        AccountStatus::MustChangePassword).await;
+
+    let mut json_data = HashMap::new();
+    json_data.insert("email", "kyoichi.maliniak.10005@gmail.com");
+    json_data.insert("password", "password");
+
+    let client = common::reqwest_client();
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/login"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_successful_login(response, "kyoichi.maliniak.10005@gmail.com").await;
+
+    let response = client
+        .get(make_ui_url(&test_app.app_url, "/home"))
+        .header(header::CONTENT_TYPE, "application/json")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    update_account_status(&test_pool().await, "kyoichi.maliniak.10005@gmail.com", AccountStatus::Active).await;
+
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, PASSWORD_CHANGE_REQUIRED_MSG, false).await;
+}
+
+/// Requests a sign-in link for `email` using `client`, and returns the link token from
+/// the message written by the ``file`` mailer.
+async fn request_magic_link_token(
+    test_app: &common::TestApp,
+    client: &reqwest::Client,
+    email: &str
+) -> String {
+    let mut json_data = HashMap::new();
+    json_data.insert("email", email);
+
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/magic-link"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::OK, MAGIC_LINK_SENT_MSG, false).await;
+
+    let config = Config::init();
+    let message = std::fs::read_to_string(
+        FileMailer::message_path(std::path::Path::new(&config.mail_file_dir), email)).unwrap();
+
+    let (_, token) = message.split_once("/api/magic-link/").expect("Should have a sign-in link.");
+    String::from(token.split_whitespace().next().unwrap())
+}
+
+/// Signs in with a sign-in link from the browser which requested it. The link 
+/// can't be used again.
+/// 
+/// * Route: ``http://localhost:5000/api/magic-link/{token}``
+/// * Method: ``GET``
+/// * Response: ``HTML``
+#[actix_web::test]
+async fn magic_link_login_html() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let email = "bezalel.simmel.10002@gmail.com";
+    let token = request_magic_link_token(test_app, &client, email).await;
+
+    let response = client
+        .get(make_api_url(&test_app.app_url, &format!("/magic-link/{}", token)))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_access_token_in_header(&response, email);
+    common::assert_access_token_in_cookie(&response, email);
+    common::assert_html_home_page(response).await;
+
+    // Single use. A new client: the first one is now logged in.
+    let response = common::reqwest_client()
+        .get(make_api_url(&test_app.app_url, &format!("/magic-link/{}", token)))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_redirected_html_login_page(response, StatusCode::UNAUTHORIZED, 
+        MAGIC_LINK_INVALID_MSG).await;
+}
+
+/// A sign-in link followed from a browser other than the requesting one, e.g. a 
+/// forwarded email, is rejected.
+/// 
+/// * Route: ``http://localhost:5000/api/magic-link/{token}``
+/// * Method: ``GET``
+/// * Response: ``HTML``
+#[actix_web::test]
+async fn magic_link_login_other_browser() {
+    let test_app = &spawn_app().await;
+
+    let email = "parto.bamford.10003@gmail.com";
+    let token = request_magic_link_token(test_app, &common::reqwest_client(), email).await;
+
+    let response = common::reqwest_client()
+        .get(make_api_url(&test_app.app_url, &format!("/magic-link/{}", token)))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_access_token_not_in_header(&response);
+    common::assert_redirected_html_login_page(response, StatusCode::UNAUTHORIZED, 
+        MAGIC_LINK_INVALID_MSG).await;
+}
+
+/// An unknown email gets the same response as a registered one.
+/// 
+/// * Route: ``http://localhost:5000/api/magic-link``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "suzette.petXXtey.10024@gmail.com"}``
+#[actix_web::test]
+async fn post_magic_link_json_unknown_email() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let mut json_data = HashMap::new();
+    json_data.insert("email", "suzette.petXXtey.10024@gmail.com");
+
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/magic-link"))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::OK, MAGIC_LINK_SENT_MSG, false).await;
+}
+
+/// An email with quotes is only an unknown email: it neither fails, nor selects another
+/// employee, who would then get a sign-in link.
+/// 
+/// * Route: ``http://localhost:5000/api/magic-link``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"email": "o'neill.10007@gmail.com"}``
+/// * Body: ``{"email": "x' OR email = 'tzvetan.zielinski.10007@gmail.com"}``
+#[actix_web::test]
+async fn post_magic_link_json_quoted_email() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    for email in ["o'neill.10007@gmail.com", "x' OR email = 'tzvetan.zielinski.10007@gmail.com"] {
+        let mut json_data = HashMap::new();
+        json_data.insert("email", email);
+
+        let response = client
+            .post(make_api_url(&test_app.app_url, "/magic-link"))
+            .json(&json_data)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        common::assert_json_failure(response, StatusCode::OK, MAGIC_LINK_SENT_MSG, false).await;
+    }
+
+    let links: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM magic_links WHERE email = ?")
+        .bind("tzvetan.zielinski.10007@gmail.com")
+        .fetch_one(&test_pool().await).await.unwrap();
+    assert_eq!(links, 0);
+}
+
+/// The new password is in the breached passwords file. Returns JSON 
+/// [`learn_actix_web::bh_libs::api_status::ApiStatus`] with code 400 and message
+/// [`learn_actix_web::helper::messages::BREACHED_PASSWORD_MSG`].
+/// 
+/// * Route: ``http://localhost:5000/api/password``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"current_password": "password", "new_password": "letmein"}``
+/// 
+#[actix_web::test]
+async fn post_password_json_breached() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let mut json_data = HashMap::new();
+    json_data.insert("current_password", "password");
+    json_data.insert("new_password", "letmein");
+
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/password"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, 
+        BREACHED_PASSWORD_MSG, false).await;
+}
+
+/// The current password does not match. Returns JSON 
+/// [`learn_actix_web::bh_libs::api_status::ApiStatus`] with code 401 and message
+/// [`learn_actix_web::helper::messages::CURRENT_PASSWORD_MISMATCH_MSG`].
+/// 
+/// * Route: ``http://localhost:5000/api/password``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"current_password": "passworKKd", "new_password": "Correct Horse Battery Staple"}``
+/// 
+#[actix_web::test]
+async fn post_password_json_current_mismatch() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let mut json_data = HashMap::new();
+    json_data.insert("current_password", "passworKKd");
+    json_data.insert("new_password", "Correct Horse Battery Staple");
+
+    let response = client
+        .post(make_api_url(&test_app.app_url, "/password"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::UNAUTHORIZED, 
+        CURRENT_PASSWORD_MISMATCH_MSG, false).await;
+}
@@ -0,0 +1,385 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in department_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/departments``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/departments/{dept_no}``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/departments/{dept_no}/employees?current=false&limit=2``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}/departments``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/ui/departments``
+//! * Method: ``GET``
+//! * Response: ``HTML``
+//!
+//! * Route: ``http://localhost:5000/ui/departments/{dept_no}``
+//! * Method: ``GET``
+//! * Response: ``HTML``
+//!
+//! * Route: ``http://localhost:5000/ui/employees/{emp_no}/departments``
+//! * Method: ``GET``
+//! * Response: ``HTML``
+//!
+//! * Route: ``http://localhost:5000/data/departments``, and the above
+//! * Method: ``GET``
+//! * Header: ``If-None-Match``, ``If-Modified-Since``
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_department_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_departments_json -- --exact
+//!     * cargo test get_department_json -- --exact
+//!     * cargo test get_department_json_not_found -- --exact
+//!     * cargo test get_department_employees_json_pages -- --exact
+//!     * cargo test get_department_employees_json_invalid_page -- --exact
+//!     * cargo test get_employee_departments_json -- --exact
+//!     * cargo test get_employee_departments_json_not_found -- --exact
+//!     * cargo test get_departments_html -- --exact
+//!     * cargo test get_department_html -- --exact
+//!     * cargo test get_employee_departments_html -- --exact
+//!     * cargo test get_departments_json_no_access_token -- --exact
+//!     * cargo test get_departments_conditional -- --exact
+//!     * cargo test get_department_employees_conditional -- --exact
+//!
+use actix_web::http::{StatusCode, header};
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::department::{
+    Department, DepartmentDetail, DepartmentEmployee, DepartmentMembership
+};
+
+mod common;
+use common::{spawn_app, JWT_SECS_VALID_FOR, make_full_url, make_data_url, make_ui_url, header_value, link_url, get_authorised};
+
+use learn_actix_web::helper::messages::{
+    UNAUTHORISED_ACCESS_MSG,
+    DEPARTMENT_NOT_FOUND_MSG,
+    EMPLOYEE_NOT_FOUND_MSG,
+    PAGE_CURSOR_INVALID_MSG
+};
+
+/// * Route: ``http://localhost:5000/data/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_departments_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let res = response.json::<DataResponse<Vec<Department>>>().await;
+    assert!(res.is_ok(), "Should have a JSON response.");
+
+    let departments = res.unwrap().data;
+    assert_eq!(departments.len(), 9);
+    assert!(departments.iter().any(|dept| dept.dept_no == "d005" && dept.dept_name == "Development"));
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let res = response.json::<DataResponse<DepartmentDetail>>().await;
+    assert!(res.is_ok(), "Should have a JSON response.");
+
+    let department = res.unwrap().data;
+    assert_eq!(department.department.dept_name, "Development");
+    assert!(department.current_employees > 0);
+    assert!(!department.managers.is_empty());
+    assert_eq!(department.managers.iter().filter(|manager| manager.current).count(), 1);
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d999``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_json_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d999")).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, DEPARTMENT_NOT_FOUND_MSG, false).await;
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d999/employees")).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, DEPARTMENT_NOT_FOUND_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005/employees?current=false&limit=2``,
+/// then following the ``Link`` header.
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_employees_json_pages() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/employees?current=false&limit=2")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    let first_page = response.json::<DataResponse<Vec<DepartmentEmployee>>>().await.unwrap();
+
+    let page = first_page.page.unwrap();
+    assert_eq!(first_page.data.len(), 2);
+    assert!(page.total > 2);
+    assert_eq!(page.limit, 2);
+    assert!(link_url(&link, "prev").is_none());
+
+    let next_url = link_url(&link, "next").unwrap();
+    assert!(next_url.starts_with("/data/departments/d005/employees?current=false&limit=2&cursor="));
+
+    let response = get_authorised(&client, test_app,
+        &make_full_url(&test_app.app_url, &next_url)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let second_page = response.json::<DataResponse<Vec<DepartmentEmployee>>>().await.unwrap();
+    assert!(second_page.data[0].emp_no > first_page.data[1].emp_no);
+    assert_eq!(second_page.page.unwrap().total, page.total);
+
+    // Current employees only, by default.
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/employees?limit=5")).await;
+
+    let current_page = response.json::<DataResponse<Vec<DepartmentEmployee>>>().await.unwrap();
+    assert!(current_page.data.iter().all(|employee| employee.current));
+    assert!(current_page.page.unwrap().total <= page.total);
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005/employees?cursor=xyz``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_employees_json_invalid_page() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/employees?cursor=xyz")).await;
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, PAGE_CURSOR_INVALID_MSG, false).await;
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/employees?limit=0")).await;
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, "limit must be between 1 and", true).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10010/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_departments_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/10010/departments")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let memberships = response.json::<DataResponse<Vec<DepartmentMembership>>>().await.unwrap().data;
+    assert_eq!(memberships.len(), 2);
+    assert_eq!(memberships[0].dept_no, "d004");
+    assert!(!memberships[0].current);
+    assert_eq!(memberships[1].dept_no, "d006");
+    assert!(memberships[1].current);
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/10010/departments?current=true")).await;
+
+    let memberships = response.json::<DataResponse<Vec<DepartmentMembership>>>().await.unwrap().data;
+    assert_eq!(memberships.len(), 1);
+    assert_eq!(memberships[0].dept_no, "d006");
+}
+
+/// * Route: ``http://localhost:5000/data/employees/1/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_departments_json_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/1/departments")).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/ui/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_departments_html() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_ui_url(&test_app.app_url, "/departments")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("Development"), "HTML: department Development not found.");
+    assert!(html.contains("Customer Service"), "HTML: department Customer Service not found.");
+}
+
+/// * Route: ``http://localhost:5000/ui/departments/d005?limit=2``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_html() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_ui_url(&test_app.app_url, "/departments/d005?limit=2")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    let next_url = link_url(&link, "next").unwrap();
+    assert!(next_url.starts_with("/ui/departments/d005?"));
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<h1>Development</h1>"), "HTML: department name not found.");
+    // Tera escapes the URL.
+    let next_url = next_url.replace('&', "&amp;").replace('/', "&#x2F;");
+    assert!(html.contains(&next_url), "HTML: next page link not found.");
+}
+
+/// * Route: ``http://localhost:5000/ui/employees/10010/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_departments_html() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_ui_url(&test_app.app_url, "/employees/10010/departments")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("Production"), "HTML: department Production not found.");
+    assert!(html.contains("Quality Management"), "HTML: department Quality Management not found.");
+}
+
+/// * Route: ``http://localhost:5000/data/departments``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_departments_json_no_access_token() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/departments"))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_access_token_not_in_header(&response);
+    common::assert_redirected_html_login_page(response, 
+        StatusCode::UNAUTHORIZED, UNAUTHORISED_ACCESS_MSG).await;
+}
+
+/// Sends an authenticated ``GET`` request with a conditional header.
+async fn get_conditional(
+    client: &reqwest::Client,
+    test_app: &common::TestApp,
+    url: &str,
+    name: header::HeaderName,
+    value: &str
+) -> reqwest::Response {
+    client
+        .get(url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .header(name, value)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// * Route: ``http://localhost:5000/data/departments``
+/// * Route: ``http://localhost:5000/ui/departments``
+/// * Method: ``GET``
+/// * Header: ``If-None-Match``, ``If-Modified-Since``
+#[actix_web::test]
+async fn get_departments_conditional() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    for url in [make_data_url(&test_app.app_url, "/departments"), make_ui_url(&test_app.app_url, "/departments")] {
+        let response = get_authorised(&client, test_app, &url).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = header_value(&response, header::ETAG);
+        // A collection, by ETag only.
+        assert!(response.headers().get(header::LAST_MODIFIED).is_none());
+
+        let response = get_conditional(&client, test_app, &url, header::IF_NONE_MATCH, &etag).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(header_value(&response, header::ETAG), etag);
+
+        let response = get_conditional(&client, test_app, &url, header::IF_MODIFIED_SINCE,
+            "Fri, 01 Jan 2100 00:00:00 GMT").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005/employees?limit=2``
+/// * Method: ``GET``
+/// * Header: ``If-None-Match``
+#[actix_web::test]
+async fn get_department_employees_conditional() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let url = make_data_url(&test_app.app_url, "/departments/d005/employees?limit=2");
+
+    let response = get_authorised(&client, test_app, &url).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = header_value(&response, header::ETAG);
+
+    let response = get_conditional(&client, test_app, &url, header::IF_NONE_MATCH, &etag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert!(response.headers().get(header::LINK).is_some(), "304 should keep the Link header.");
+
+    // Another page, another ETag.
+    let url = make_data_url(&test_app.app_url, "/departments/d005/employees?limit=3");
+    let response = get_conditional(&client, test_app, &url, header::IF_NONE_MATCH, &etag).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
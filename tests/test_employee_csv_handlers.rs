@@ -0,0 +1,280 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in employee_csv_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/employees/export.csv``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/employees/import``
+//! * Method: ``POST``
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_employee_csv_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_employees_export_csv -- --exact
+//!     * cargo test get_employees_export_csv_columns -- --exact
+//!     * cargo test get_employees_export_csv_invalid -- --exact
+//!     * cargo test post_employees_import_forbidden -- --exact
+//!     * cargo test post_employees_import_dry_run -- --exact
+//!     * cargo test post_employees_import_commit -- --exact
+//!     * cargo test post_employees_import_invalid -- --exact
+//!
+use actix_web::http::{StatusCode, header};
+
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::employee_import::ImportReport;
+
+mod common;
+use common::{spawn_app, JWT_SECS_VALID_FOR, make_data_url, set_role, test_pool, get_authorised};
+
+use learn_actix_web::helper::messages::{
+    EXPORT_COLUMN_INVALID_MSG,
+    SEARCH_SORT_INVALID_MSG,
+    HR_PERMISSION_REQUIRED_MSG,
+    IMPORT_MODE_INVALID_MSG,
+    IMPORT_COLUMN_INVALID_MSG,
+    IMPORT_DATE_INVALID_MSG,
+    IMPORT_EMP_NO_REPEATED_MSG,
+    EMPLOYEE_NO_EXISTS_MSG,
+    EMPLOYEE_EMAIL_EXISTS_MSG,
+    EMPLOYEE_GENDER_INVALID_MSG
+};
+
+// The import tests run in parallel: each has its own HR employee, whose role no other
+// test changes.
+const DRY_RUN_HR_EMAIL: &str = "mary.sluis.10011@gmail.com";
+const COMMIT_HR_EMAIL: &str = "patricio.bridgland.10012@gmail.com";
+const INVALID_HR_EMAIL: &str = "eberhardt.terkki.10013@gmail.com";
+
+const IMPORT_HEADER: &str = "emp_no,email,birth_date,first_name,last_name,gender,hire_date";
+
+/// Deletes the imported employees, also those left behind by an earlier failed run.
+async fn delete_imported(emp_nos: &[i32]) {
+    for emp_no in emp_nos {
+        sqlx::query("DELETE FROM employees WHERE emp_no = ?")
+            .bind(emp_no)
+            .execute(&test_pool().await).await.unwrap();
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/employees/export.csv?last_name=%25chi&first_name=%25ak&limit=1``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_export_csv() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    // limit is ignored: all matched employees are exported.
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/export.csv?last_name=%25chi&first_name=%25ak&limit=1")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get(header::CONTENT_TYPE).unwrap(), "text/csv; charset=utf-8");
+    assert_eq!(response.headers().get(header::CONTENT_DISPOSITION).unwrap(),
+        "attachment; filename=\"employees.csv\"");
+
+    let csv = response.text().await.unwrap();
+    let records: Vec<&str> = csv.split_terminator("\r\n").collect();
+
+    assert_eq!(records[0], "emp_no,email,birth_date,first_name,last_name,gender,hire_date,title");
+    assert!(records.len() > 2);
+
+    // Ordered by emp_no, with dates in dd/mm/yyyy.
+    let emp_nos: Vec<i32> = records[1..].iter()
+        .map(|record| record.split(',').next().unwrap().parse::<i32>().unwrap())
+        .collect();
+    assert!(emp_nos.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let fields: Vec<&str> = records[1].split(',').collect();
+    assert_eq!(fields.len(), 8);
+    assert_eq!(fields[2].len(), 10);
+    assert_eq!(&fields[2][2..3], "/");
+}
+
+/// * Route: ``http://localhost:5000/data/employees/export.csv?last_name=Koblick&first_name=Chirstian&columns=last_name,emp_no,hire_date&header=false``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_export_csv_columns() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/export.csv?last_name=Koblick\
+        &first_name=Chirstian&columns=last_name,emp_no,hire_date&header=false")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.text().await.unwrap(), "Koblick,10004,01/12/1986\r\n");
+}
+
+/// * Route: ``http://localhost:5000/data/employees/export.csv?last_name=%25&first_name=%25&columns=salary``
+/// * Route: ``http://localhost:5000/data/employees/export.csv?last_name=%25&first_name=%25&sort=salary``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_export_csv_invalid() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/export.csv?last_name=%25&first_name=%25&columns=salary")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, EXPORT_COLUMN_INVALID_MSG, true).await;
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/export.csv?last_name=%25&first_name=%25&sort=salary")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, SEARCH_SORT_INVALID_MSG, true).await;
+}
+
+/// Sends a CSV ``POST`` request with the access token of ``email``.
+async fn post_csv_as(
+    client: &reqwest::Client,
+    test_app: &common::TestApp,
+    email: &str,
+    path: &str,
+    csv: String
+) -> reqwest::Response {
+    client
+        .post(make_data_url(&test_app.app_url, path))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token_for(email, JWT_SECS_VALID_FOR))
+        .header(header::CONTENT_TYPE, "text/csv")
+        .body(csv)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// * Route: ``http://localhost:5000/data/employees/import``
+/// * Method: ``POST``
+///
+/// The default user, chirstian.koblick.10004@gmail.com, is an ``employee``.
+#[actix_web::test]
+async fn post_employees_import_forbidden() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = post_csv_as(&client, test_app, "chirstian.koblick.10004@gmail.com",
+        "/employees/import", String::from(IMPORT_HEADER)).await;
+
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, HR_PERMISSION_REQUIRED_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/import``
+/// * Method: ``POST``
+#[actix_web::test]
+async fn post_employees_import_dry_run() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    delete_imported(&[600101]).await;
+
+    // Valid; emp_no and email taken; gender and date invalid; emp_no repeated.
+    let csv = format!("{}\r\n\
+        600101,siamak.bernardeschi.600101@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\r\n\
+        10004,chirstian.koblick.10004@gmail.com,01/05/1954,Chirstian,Koblick,M,01/12/1986\r\n\
+        600102,siamak.bernardeschi.600102@gmail.com,14/12/1955,Siamak,Bernardeschi,X,31/04/1985\r\n\
+        600101,siamak.bernardeschi.600103@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\r\n", IMPORT_HEADER);
+
+    set_role(DRY_RUN_HR_EMAIL, "hr").await;
+
+    let response = post_csv_as(&client, test_app, DRY_RUN_HR_EMAIL, "/employees/import", csv).await;
+
+    set_role(DRY_RUN_HR_EMAIL, "employee").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let report = response.json::<DataResponse<ImportReport>>().await.unwrap().data;
+    assert_eq!(report.mode, "dry-run");
+    assert_eq!(report.rows, 4);
+    assert_eq!(report.imported, 0);
+    assert_eq!(report.errors.len(), 3);
+
+    assert_eq!(report.errors[0].row, 2);
+    assert_eq!(report.errors[0].emp_no, Some(10004));
+    assert_eq!(report.errors[0].errors, vec![EMPLOYEE_NO_EXISTS_MSG, EMPLOYEE_EMAIL_EXISTS_MSG]);
+
+    assert_eq!(report.errors[1].row, 3);
+    assert_eq!(report.errors[1].errors, vec![format!("{} hire_date", IMPORT_DATE_INVALID_MSG),
+        String::from(EMPLOYEE_GENDER_INVALID_MSG)]);
+
+    assert_eq!(report.errors[2].row, 4);
+    assert_eq!(report.errors[2].errors, vec![format!("{} 1", IMPORT_EMP_NO_REPEATED_MSG)]);
+
+    // Nothing was written.
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM employees WHERE emp_no = 600101")
+        .fetch_one(&test_pool().await).await.unwrap();
+    assert_eq!(count, 0);
+}
+
+/// * Route: ``http://localhost:5000/data/employees/import?mode=commit``
+/// * Method: ``POST``
+#[actix_web::test]
+async fn post_employees_import_commit() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    delete_imported(&[600111, 600112]).await;
+
+    let valid = format!("{}\n\
+        600111,siamak.bernardeschi.600111@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n\
+        600112,siamak.bernardeschi.600112@gmail.com,14/12/1955,Siamak,Bernardeschi,F,26/04/1985\n", IMPORT_HEADER);
+    // One invalid row: the other is not written either.
+    let invalid = format!("{}\n\
+        600111,siamak.bernardeschi.600111@gmail.com,14/12/1955,Siamak,Bernardeschi,M,26/04/1985\n\
+        600112,siamak.bernardeschi.600112@gmail.com,14/12/1955,Siamak,Bernardeschi,F,26/04/1955\n", IMPORT_HEADER);
+
+    set_role(COMMIT_HR_EMAIL, "hr").await;
+
+    let rejected = post_csv_as(&client, test_app, COMMIT_HR_EMAIL, "/employees/import?mode=commit", invalid).await;
+    let committed = post_csv_as(&client, test_app, COMMIT_HR_EMAIL, "/employees/import?mode=commit", valid.clone()).await;
+    let repeated = post_csv_as(&client, test_app, COMMIT_HR_EMAIL, "/employees/import?mode=dry-run", valid).await;
+
+    set_role(COMMIT_HR_EMAIL, "employee").await;
+
+    assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+    let report = rejected.json::<DataResponse<ImportReport>>().await.unwrap().data;
+    assert_eq!((report.rows, report.imported, report.errors.len()), (2, 0, 1));
+
+    assert_eq!(committed.status(), StatusCode::CREATED);
+    let report = committed.json::<DataResponse<ImportReport>>().await.unwrap().data;
+    assert_eq!(report.mode, "commit");
+    assert_eq!((report.rows, report.imported), (2, 2));
+    assert!(report.errors.is_empty());
+
+    // Now both are taken.
+    let report = repeated.json::<DataResponse<ImportReport>>().await.unwrap().data;
+    assert_eq!(report.errors.len(), 2);
+    assert!(report.errors.iter().all(|error| error.errors.contains(&String::from(EMPLOYEE_NO_EXISTS_MSG))));
+
+    delete_imported(&[600111, 600112]).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/import?mode=force``
+/// * Route: ``http://localhost:5000/data/employees/import``
+/// * Method: ``POST``
+#[actix_web::test]
+async fn post_employees_import_invalid() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    set_role(INVALID_HR_EMAIL, "hr").await;
+
+    let invalid_mode = post_csv_as(&client, test_app, INVALID_HR_EMAIL, "/employees/import?mode=force",
+        String::from(IMPORT_HEADER)).await;
+    let invalid_column = post_csv_as(&client, test_app, INVALID_HR_EMAIL, "/employees/import",
+        format!("{},salary\n", IMPORT_HEADER)).await;
+
+    set_role(INVALID_HR_EMAIL, "employee").await;
+
+    common::assert_json_failure(invalid_mode, StatusCode::BAD_REQUEST, IMPORT_MODE_INVALID_MSG, false).await;
+    common::assert_json_failure(invalid_column, StatusCode::BAD_REQUEST, IMPORT_COLUMN_INVALID_MSG, true).await;
+}
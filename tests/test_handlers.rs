@@ -23,6 +23,46 @@
 //! * Route: ``http://localhost:5000/helloemployee/%chi/%ak``; i.e., /employees/{last_name}/{first_name}.
 //! * Method: ``GET``
 //! 
+//! * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``
+//! * Method: ``GET``
+//! 
+//! * Route: ``http://localhost:5000/ui/employees?last_name=%25chi&first_name=%25ak&limit=2``
+//! * Method: ``GET``
+//! 
+//! * Route: ``http://localhost:5000/data/employees?last_name=%25&first_name=%25&gender=F&hire_date_from=01/01/1990&sort=-hire_date``
+//! * Method: ``GET``
+//! 
+//! * Route: ``http://localhost:5000/data/employees?last_name=%25&first_name=%25&title=Senior+Engineer``
+//! * Method: ``GET``
+//! 
+//! * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``
+//! * Route: ``http://localhost:5000/ui/employees/%chi/%ak?limit=2``
+//! * Method: ``GET``
+//! * Header: ``Accept: text/csv``, ``application/xml``, ``text/html`` or ``application/json``
+//! 
+//! * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&fields=emp_no,last_name``
+//! * Route: ``http://localhost:5000/data/employees/10004?fields=emp_no&include=departments,titles,salaries``
+//! * Method: ``GET``
+//! 
+//! * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``
+//! * Route: ``http://localhost:5000/data/employees/10004``
+//! * Method: ``GET``
+//! * Header: ``If-None-Match``, ``If-Modified-Since``
+//! 
+//! * Route: ``http://localhost:5000/data/employees/new``
+//! * Method: ``POST``
+//! * Content Type: ``application/json``
+//! 
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}``
+//! * Method: ``GET``, ``PUT``, ``PATCH``, ``DELETE``
+//! * Header: ``If-Match`` of ``PUT`` and ``PATCH``
+//! 
+//! * Route: ``http://localhost:5000/data/employees/deleted``
+//! * Method: ``GET``
+//! 
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}/restore``
+//! * Method: ``POST``
+//! 
 //! To run test for this module only: 
 //! 
 //!     * cargo test --test test_handlers
@@ -45,16 +85,66 @@
 //!     * cargo test post_employees_html1_no_access_token -- --exact
 //!     * cargo test get_employees_html2_no_access_token -- --exact
 //!     * cargo test get_helloemployee_has_data_no_access_token -- --exact
+//!     * cargo test employee_crud_json -- --exact
+//!     * cargo test get_employee_json_not_found -- --exact
+//!     * cargo test post_employee_json_invalid -- --exact
+//!     * cargo test put_employee_json_no_mismatch -- --exact
+//!     * cargo test employee_write_json_forbidden -- --exact
+//!     * cargo test get_employees_json3_pages -- --exact
+//!     * cargo test get_employees_json2_offset -- --exact
+//!     * cargo test get_employees_json3_invalid_page -- --exact
+//!     * cargo test get_employees_html3_pages -- --exact
+//!     * cargo test get_employees_json3_filter_sort -- --exact
+//!     * cargo test post_employees_json1_filter -- --exact
+//!     * cargo test get_employees_json3_invalid_filter_sort -- --exact
+//!     * cargo test get_employee_json_title -- --exact
+//!     * cargo test get_employees_json3_title -- --exact
+//!     * cargo test get_employees_html3_title -- --exact
+//!     * cargo test get_employees_accept_csv -- --exact
+//!     * cargo test get_employees_accept_xml -- --exact
+//!     * cargo test get_employees_accept_html_json -- --exact
+//!     * cargo test get_employees_not_acceptable -- --exact
+//!     * cargo test get_employees_json3_fields -- --exact
+//!     * cargo test get_employee_json_include -- --exact
+//!     * cargo test get_employees_json3_fields_invalid -- --exact
+//!     * cargo test get_employees_conditional -- --exact
+//!     * cargo test get_employee_json_conditional -- --exact
+//!     * cargo test patch_employee_json_if_match -- --exact
+//!     * cargo test delete_restore_employee_json -- --exact
 //!
 use std::collections::HashMap;
 use time::macros::date;
 use actix_web::http::{StatusCode, header, header::ContentType};
-use learn_actix_web::models::Employee;
+use learn_actix_web::models::{Employee, DataResponse};
+use learn_actix_web::models::deleted_employee::DeletedEmployee;
 
 mod common;
-use common::{spawn_app, JWT_SECS_VALID_FOR, make_full_url, make_data_url, make_ui_url};
-
-use learn_actix_web::helper::messages::UNAUTHORISED_ACCESS_MSG;
+use common::{spawn_app, JWT_SECS_VALID_FOR, make_full_url, make_data_url, make_ui_url, HR_EMAIL, set_role, test_pool, header_value, link_url, get_as};
+
+use learn_actix_web::helper::messages::{
+    UNAUTHORISED_ACCESS_MSG,
+    EMPLOYEE_NOT_FOUND_MSG,
+    EMPLOYEE_NO_EXISTS_MSG,
+    EMPLOYEE_NO_MISMATCH_MSG,
+    EMPLOYEE_DELETED_MSG,
+    EMPLOYEE_GENDER_INVALID_MSG,
+    EMPLOYEE_IF_MATCH_REQUIRED_MSG,
+    EMPLOYEE_MODIFIED_MSG,
+    EMPLOYEE_RESTORED_MSG,
+    ACCOUNT_DISABLED_MSG,
+    DELETED_EMPLOYEE_NOT_FOUND_MSG,
+    ADMIN_PERMISSION_REQUIRED_MSG,
+    PAGE_CURSOR_INVALID_MSG,
+    PAGE_CURSOR_OFFSET_MSG,
+    SEARCH_SORT_INVALID_MSG,
+    SEARCH_RANGE_INVALID_MSG,
+    SEARCH_TITLE_INVALID_MSG,
+    NOT_ACCEPTABLE_MSG,
+    HR_PERMISSION_REQUIRED_MSG,
+    FIELD_UNKNOWN_MSG,
+    FIELD_DUPLICATE_MSG,
+    INCLUDE_UNKNOWN_MSG
+};
 
 #[actix_web::test]
 async fn dummy_test() {
@@ -85,13 +175,16 @@ async fn post_employees_json1() {
         .expect("Failed to execute request.");
 
     assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::LINK).is_some(), "Should have a Link header.");
 
-    let res = response.json::<Vec<Employee>>().await;
+    let res = response.json::<DataResponse<Vec<Employee>>>().await;
     assert!(res.is_ok(), "Should have a JSON response.");
 
     // This should now always succeed.    
-    if let Ok(json_list) = res {
+    if let Ok(json_obj) = res {
+        let json_list = json_obj.data;
         assert!(json_list.len() >= 1, "Should have at least one employee.");
+        assert!(json_obj.page.unwrap().total >= json_list.len() as u64);
 
         let emp = &json_list[0];
         assert_eq!(emp.emp_no, 67115);
@@ -165,13 +258,16 @@ async fn get_employees_json2() {
         .expect("Failed to execute request.");
 
     assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::LINK).is_some(), "Should have a Link header.");
 
-    let res = response.json::<Vec<Employee>>().await;
+    let res = response.json::<DataResponse<Vec<Employee>>>().await;
     assert!(res.is_ok(), "Should have a JSON response.");
 
     // This should now always succeed.    
-    if let Ok(json_list) = res {
+    if let Ok(json_obj) = res {
+        let json_list = json_obj.data;
         assert!(json_list.len() >= 1, "Should have at least one employee.");
+        assert!(json_obj.page.unwrap().total >= json_list.len() as u64);
 
         let emp = &json_list[0];
         assert_eq!(emp.emp_no, 67115);
@@ -495,3 +591,1032 @@ async fn get_helloemployee_has_data_no_access_token() {
     common::assert_redirected_html_login_page(response, 
         StatusCode::UNAUTHORIZED, UNAUTHORISED_ACCESS_MSG).await;
 }
+
+/// Creates, updates and deletes employees. No other test changes its role.
+const HR_WRITER_EMAIL: &str = "anneke.preusig.10006@gmail.com";
+
+/// An access token of an employee with the HR permission, which creating, updating and
+/// deleting employees require.
+async fn hr_access_token(test_app: &common::TestApp) -> String {
+    set_role(HR_WRITER_EMAIL, "hr").await;
+    test_app.mock_access_token_for(HR_WRITER_EMAIL, JWT_SECS_VALID_FOR)
+}
+
+/// A new employee, whose ``emp_no`` is above the sample database's.
+fn new_employee_json(emp_no: i32) -> serde_json::Value {
+    serde_json::json!({
+        "emp_no": emp_no,
+        "email": format!("siamak.bernardeschi.{}@gmail.com", emp_no),
+        "birth_date": "14/12/1955",
+        "first_name": "Siamak",
+        "last_name": "Bernardeschi",
+        "gender": "M",
+        "hire_date": "26/04/1985"
+    })
+}
+
+/// Deletes for good the employee left behind by an earlier run: ``DELETE`` only marks
+/// it deleted.
+async fn delete_employee_if_exists(client: &reqwest::Client, test_app: &common::TestApp, emp_no: i32) {
+    client
+        .delete(make_data_url(&test_app.app_url, &format!("/employees/{}", emp_no)))
+        .header(header::AUTHORIZATION, &hr_access_token(test_app).await)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    sqlx::query("DELETE FROM employees WHERE emp_no = ?")
+        .bind(emp_no)
+        .execute(&test_pool().await).await.unwrap();
+}
+
+/// * Route: ``http://localhost:5000/data/employees/new``, then 
+/// ``http://localhost:5000/data/employees/600001``
+/// * Method: ``POST``, ``GET``, ``PATCH``, ``PUT``, ``DELETE``
+/// * Content Type: ``application/json``
+#[actix_web::test]
+async fn employee_crud_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let hr_token = hr_access_token(test_app).await;
+    let emp_no = 600001;
+    let employee_url = make_data_url(&test_app.app_url, &format!("/employees/{}", emp_no));
+
+    delete_employee_if_exists(&client, test_app, emp_no).await;
+
+    // Create.
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees/new"))
+        .header(header::AUTHORIZATION, &hr_token)
+        .json(&new_employee_json(emp_no))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers().get(header::LOCATION).unwrap().to_str().unwrap(), 
+        format!("/data/employees/{}", emp_no));
+
+    let res = response.json::<DataResponse<Employee>>().await;
+    assert!(res.is_ok(), "Should have a JSON response.");
+    let employee = res.unwrap().data;
+    assert_eq!(employee.birth_date, date!(1955 - 12 - 14));
+    // A new employee has no title.
+    assert_eq!(employee.title, None);
+
+    // Creating it again is a conflict.
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees/new"))
+        .header(header::AUTHORIZATION, &hr_token)
+        .json(&new_employee_json(emp_no))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::CONFLICT, EMPLOYEE_NO_EXISTS_MSG, false).await;
+
+    // Read.
+    let response = client
+        .get(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = header_value(&response, header::ETAG);
+    let employee = response.json::<DataResponse<Employee>>().await.unwrap().data;
+    assert_eq!(employee.emp_no, emp_no);
+    assert_eq!(employee.last_name, "Bernardeschi");
+
+    // Partial update. Names are stored trimmed.
+    let response = client
+        .patch(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .header(header::IF_MATCH, &etag)
+        .json(&serde_json::json!({"last_name": " Koblick ", "hire_date": "01/02/1990"}))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = header_value(&response, header::ETAG);
+    let employee = response.json::<DataResponse<Employee>>().await.unwrap().data;
+    assert_eq!(employee.last_name, "Koblick");
+    assert_eq!(employee.first_name, "Siamak");
+    assert_eq!(employee.hire_date, date!(1990 - 02 - 01));
+
+    // The ETag of the update is that of the stored employee.
+    let response = client
+        .get(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(header_value(&response, header::ETAG), etag);
+
+    // Full update.
+    let mut json_data = new_employee_json(emp_no);
+    json_data["first_name"] = serde_json::json!("Chirstian");
+
+    let response = client
+        .put(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .header(header::IF_MATCH, &etag)
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let employee = response.json::<DataResponse<Employee>>().await.unwrap().data;
+    assert_eq!(employee.first_name, "Chirstian");
+    assert_eq!(employee.last_name, "Bernardeschi");
+    assert_eq!(employee.hire_date, date!(1985 - 04 - 26));
+
+    // Delete.
+    let response = client
+        .delete(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::OK, EMPLOYEE_DELETED_MSG, false).await;
+
+    // Gone.
+    let response = client
+        .delete(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/999999999``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_json_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees/999999999"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/new``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: an employee whose ``gender`` is ``X``.
+#[actix_web::test]
+async fn post_employee_json_invalid() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let hr_token = hr_access_token(test_app).await;
+
+    let mut json_data = new_employee_json(600002);
+    json_data["gender"] = serde_json::json!("X");
+
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees/new"))
+        .header(header::AUTHORIZATION, &hr_token)
+        .json(&json_data)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, EMPLOYEE_GENDER_INVALID_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10004``
+/// * Method: ``PUT``
+/// * Content Type: ``application/json``
+/// * Body: an employee whose ``emp_no`` is ``600003``.
+#[actix_web::test]
+async fn put_employee_json_no_mismatch() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let hr_token = hr_access_token(test_app).await;
+
+    let response = client
+        .put(make_data_url(&test_app.app_url, "/employees/10004"))
+        .header(header::AUTHORIZATION, &hr_token)
+        .json(&new_employee_json(600003))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, EMPLOYEE_NO_MISMATCH_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/new``, then
+/// ``http://localhost:5000/data/employees/10004``
+/// * Method: ``POST``, ``PUT``, ``PATCH``, ``DELETE``
+/// * Content Type: ``application/json``
+/// 
+/// Without the HR permission.
+#[actix_web::test]
+async fn employee_write_json_forbidden() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let employee_url = make_data_url(&test_app.app_url, "/employees/10004");
+    let token = test_app.mock_access_token(JWT_SECS_VALID_FOR);
+
+    let mut employee = new_employee_json(10004);
+    employee["email"] = serde_json::json!("chirstian.koblick.10004@gmail.com");
+
+    let requests = [
+        client.post(make_data_url(&test_app.app_url, "/employees/new")).json(&new_employee_json(600006)),
+        client.put(&employee_url).header(header::IF_MATCH, "*").json(&employee),
+        client.patch(&employee_url).header(header::IF_MATCH, "*").json(&serde_json::json!({"last_name": "Facello"})),
+        client.delete(&employee_url),
+    ];
+
+    for request in requests {
+        let response = request
+            .header(header::AUTHORIZATION, &token)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        common::assert_json_failure(response, StatusCode::FORBIDDEN, HR_PERMISSION_REQUIRED_MSG, false).await;
+    }
+
+    // Unchanged.
+    let response = get_accept(&client, test_app, employee_url, "application/json").await;
+    let employee = response.json::<DataResponse<Employee>>().await.unwrap().data;
+    assert_eq!(employee.last_name, "Koblick");
+}
+
+/// Retrieves a page of employees as JSON, with its ``Link`` header.
+async fn get_employees_page(
+    client: &reqwest::Client,
+    test_app: &common::TestApp,
+    path: &str
+) -> (DataResponse<Vec<Employee>>, String) {
+    let response = client
+        .get(make_full_url(&test_app.app_url, path))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    let res = response.json::<DataResponse<Vec<Employee>>>().await;
+    assert!(res.is_ok(), "Should have a JSON response.");
+
+    (res.unwrap(), link)
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``,
+/// then following the ``Link`` header.
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_pages() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let (first_page, link) = get_employees_page(&client, test_app, 
+        "/data/employees?last_name=%25chi&first_name=%25ak&limit=2").await;
+
+    let page = first_page.page.unwrap();
+    assert_eq!(first_page.data.len(), 2);
+    assert_eq!(first_page.data[0].emp_no, 67115);
+    assert!(page.total > 2);
+    assert_eq!(page.limit, 2);
+    assert_eq!(page.offset, Some(0));
+    assert!(page.prev_cursor.is_none());
+    assert!(link_url(&link, "prev").is_none());
+    assert_eq!(link_url(&link, "first").unwrap(), 
+        "/data/employees?last_name=%25chi&first_name=%25ak&limit=2");
+
+    // The next page starts after the first page.
+    let (second_page, link) = get_employees_page(&client, test_app, 
+        &link_url(&link, "next").unwrap()).await;
+
+    assert!(second_page.data[0].emp_no > first_page.data[1].emp_no);
+    assert_eq!(second_page.page.as_ref().unwrap().offset, None);
+    assert_eq!(second_page.page.as_ref().unwrap().total, page.total);
+
+    // And back.
+    let (prev_page, _) = get_employees_page(&client, test_app, 
+        &link_url(&link, "prev").unwrap()).await;
+
+    assert_eq!(prev_page.data.len(), 2);
+    assert_eq!(prev_page.data[0].emp_no, first_page.data[0].emp_no);
+    assert_eq!(prev_page.data[1].emp_no, first_page.data[1].emp_no);
+    assert!(prev_page.page.unwrap().prev_cursor.is_none());
+}
+
+/// * Route: ``http://localhost:5000/data/employees/%chi/%ak?limit=1&offset=1``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json2_offset() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let (first_page, _) = get_employees_page(&client, test_app, 
+        "/data/employees/%chi/%ak?limit=2").await;
+    let (page, link) = get_employees_page(&client, test_app, 
+        "/data/employees/%chi/%ak?limit=1&offset=1").await;
+
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.data[0].emp_no, first_page.data[1].emp_no);
+    assert_eq!(page.page.unwrap().offset, Some(1));
+    assert!(link_url(&link, "prev").is_some());
+    assert!(link_url(&link, "next").is_some());
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak``,
+/// with invalid page parameters.
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_invalid_page() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&cursor=xyz"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, PAGE_CURSOR_INVALID_MSG, false).await;
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&cursor=6131&offset=2"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, PAGE_CURSOR_OFFSET_MSG, false).await;
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=0"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, "limit must be between 1 and", true).await;
+}
+
+/// * Route: ``http://localhost:5000/ui/employees?last_name=%25chi&first_name=%25ak&limit=2``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_html3_pages() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_ui_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    let next_url = link_url(&link, "next").unwrap();
+    assert!(next_url.starts_with("/ui/employees?"));
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<td>Siamak</td>"), "HTML: first name Siamak not found.");
+    // Tera escapes the URL.
+    let next_url = next_url.replace('&', "&amp;").replace('/', "&#x2F;");
+    assert!(html.contains(&next_url), "HTML: next page link not found.");
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25&first_name=%25&gender=F&hire_date_from=01/01/1990&sort=-hire_date&limit=3``,
+/// then following the ``Link`` header.
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_filter_sort() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let (first_page, link) = get_employees_page(&client, test_app, 
+        "/data/employees?last_name=%25&first_name=%25&gender=F&hire_date_from=01/01/1990&sort=-hire_date&limit=3").await;
+
+    let next_url = link_url(&link, "next").unwrap();
+    assert!(next_url.contains("gender=F"), "Next page should keep the filters.");
+    assert!(next_url.contains("sort=-hire_date"), "Next page should keep the sort order.");
+
+    let (second_page, _) = get_employees_page(&client, test_app, &next_url).await;
+
+    let employees: Vec<Employee> = first_page.data.into_iter().chain(second_page.data).collect();
+    assert_eq!(employees.len(), 6);
+
+    for employee in &employees {
+        assert_eq!(employee.gender, "F");
+        assert!(employee.hire_date >= date!(1990 - 01 - 01));
+    }
+
+    for pair in employees.windows(2) {
+        assert!(pair[0].hire_date > pair[1].hire_date || 
+            (pair[0].hire_date == pair[1].hire_date && pair[0].emp_no < pair[1].emp_no));
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/employees``
+/// * Method: ``POST``
+/// * Content Type: ``application/json``
+/// * Body: ``{"last_name": "%", "first_name": "%", "emp_no_from": 10001, "emp_no_to": 10010, "sort": "-emp_no"}``
+#[actix_web::test]
+async fn post_employees_json1_filter() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .json(&serde_json::json!({"last_name": "%", "first_name": "%", 
+            "emp_no_from": 10001, "emp_no_to": 10010, "sort": "-emp_no"}))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json_obj = response.json::<DataResponse<Vec<Employee>>>().await.unwrap();
+    let emp_nos: Vec<i32> = json_obj.data.iter().map(|employee| employee.emp_no).collect();
+
+    assert_eq!(emp_nos, (10001..=10010).rev().collect::<Vec<i32>>());
+    assert_eq!(json_obj.page.unwrap().total, 10);
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak``,
+/// with invalid filters or sort order.
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_invalid_filter_sort() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&sort=salary"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, 
+        &format!("{} salary", SEARCH_SORT_INVALID_MSG), false).await;
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees/%chi/%ak?birth_date_from=01/01/1960&birth_date_to=01/01/1950"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, 
+        &format!("{} birth_date.", SEARCH_RANGE_INVALID_MSG), false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10004``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_json_title() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, "/employees/10004"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let employee = response.json::<DataResponse<Employee>>().await.unwrap().data;
+    assert_eq!(employee.title.as_deref(), Some("Senior Engineer"));
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25&first_name=%25&title=Senior+Engineer&limit=5``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_title() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let (page, link) = get_employees_page(&client, test_app, 
+        "/data/employees?last_name=%25&first_name=%25&title=Senior+Engineer&limit=5").await;
+
+    assert_eq!(page.data.len(), 5);
+    assert!(page.data.iter().all(|employee| employee.title.as_deref() == Some("Senior Engineer")));
+    assert!(link_url(&link, "next").unwrap().contains("title=Senior+Engineer"), 
+        "Next page should keep the title filter.");
+
+    let (all_titles, _) = get_employees_page(&client, test_app, 
+        "/data/employees?last_name=%25&first_name=%25&limit=5").await;
+    assert!(page.page.unwrap().total < all_titles.page.unwrap().total);
+
+    let response = client
+        .get(make_data_url(&test_app.app_url, &format!("/employees?last_name=%25&first_name=%25&title={}", "a".repeat(51))))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, SEARCH_TITLE_INVALID_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/ui/employees?last_name=%25&first_name=%25&title=Senior+Staff&limit=2``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_html3_title() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = client
+        .get(make_ui_url(&test_app.app_url, "/employees?last_name=%25&first_name=%25&title=Senior+Staff&limit=2"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<th>Title</th>"), "HTML: title column not found.");
+    assert!(html.contains("<td>Senior Staff</td>"), "HTML: title Senior Staff not found.");
+}
+
+/// Sends an authenticated ``GET`` request with an ``Accept`` header.
+async fn get_accept(client: &reqwest::Client, test_app: &common::TestApp, url: String, accept: &str) -> reqwest::Response {
+    client
+        .get(url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .header(header::ACCEPT, accept)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+fn content_type(response: &reqwest::Response) -> String {
+    String::from(response.headers().get(header::CONTENT_TYPE).unwrap().to_str().unwrap())
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``
+/// * Method: ``GET``
+/// * Header: ``Accept: text/csv``
+#[actix_web::test]
+async fn get_employees_accept_csv() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_accept(&client, test_app,
+        make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2"), "text/csv").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_type(&response), "text/csv; charset=utf-8");
+    assert_eq!(header_value(&response, header::VARY), "accept");
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    assert!(link_url(&link, "next").unwrap().starts_with("/data/employees?"));
+
+    let csv = response.text().await.unwrap();
+    let lines: Vec<&str> = csv.split_terminator("\r\n").collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "emp_no,email,birth_date,first_name,last_name,gender,hire_date,title");
+}
+
+/// * Route: ``http://localhost:5000/ui/employees/%chi/%ak?limit=2``
+/// * Method: ``GET``
+/// * Header: ``Accept: application/xml``
+#[actix_web::test]
+async fn get_employees_accept_xml() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_accept(&client, test_app,
+        make_ui_url(&test_app.app_url, "/employees/%chi/%ak?limit=2"), "application/xml").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_type(&response), "application/xml; charset=utf-8");
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    assert!(link_url(&link, "next").unwrap().starts_with("/ui/employees?"));
+
+    let xml = response.text().await.unwrap();
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<employees total=\""));
+    assert_eq!(xml.matches("<employee>").count(), 2);
+    assert!(xml.contains("<first_name>Siamak</first_name>"), "XML: first name Siamak not found.");
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``
+/// * Route: ``http://localhost:5000/ui/employees?last_name=%25chi&first_name=%25ak&limit=2``
+/// * Method: ``GET``
+/// * Header: ``Accept: text/html``, ``application/json``
+#[actix_web::test]
+async fn get_employees_accept_html_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    // A browser, under /data.
+    let response = get_accept(&client, test_app,
+        make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2"),
+        "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_type(&response), "text/html; charset=utf-8");
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<td>Siamak</td>"), "HTML: first name Siamak not found.");
+    assert!(html.contains("&#x2F;data&#x2F;employees?"), "HTML: next page link not under /data.");
+
+    // JSON, under /ui.
+    let response = get_accept(&client, test_app,
+        make_ui_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2"), "application/json").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(content_type(&response), "application/json");
+    assert_eq!(header_value(&response, header::VARY), "accept");
+
+    let res = response.json::<DataResponse<Vec<Employee>>>().await.unwrap();
+    assert_eq!(res.data.len(), 2);
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak``
+/// * Route: ``http://localhost:5000/ui/employees``
+/// * Method: ``GET``, ``POST``
+/// * Header: ``Accept: image/png``, ``application/pdf``
+#[actix_web::test]
+async fn get_employees_not_acceptable() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_accept(&client, test_app,
+        make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak"), "image/png").await;
+
+    common::assert_json_failure(response, StatusCode::NOT_ACCEPTABLE, NOT_ACCEPTABLE_MSG, false).await;
+
+    let response = client
+        .post(make_ui_url(&test_app.app_url, "/employees"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .header(header::ACCEPT, "application/pdf")
+        .form(&[("last_name", "%chi"), ("first_name", "%ak")])
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::NOT_ACCEPTABLE, NOT_ACCEPTABLE_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2&fields=last_name,emp_no``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_fields() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2&fields=last_name,emp_no")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let link = String::from(response.headers().get(header::LINK).unwrap().to_str().unwrap());
+    assert!(link_url(&link, "next").unwrap().contains("fields=last_name%2Cemp_no"));
+
+    let res = response.json::<DataResponse<Vec<serde_json::Map<String, serde_json::Value>>>>().await.unwrap();
+    assert_eq!(res.data.len(), 2);
+    for employee in &res.data {
+        assert_eq!(employee.keys().collect::<Vec<&String>>(), vec!["emp_no", "last_name"]);
+        assert!(employee["emp_no"].is_i64());
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10004?fields=emp_no&include=departments,titles,salaries``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_json_include() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/10004?fields=emp_no&include=departments,titles")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let employee = response.json::<DataResponse<serde_json::Value>>().await.unwrap().data;
+    assert_eq!(employee["emp_no"], 10004);
+    assert!(employee.get("first_name").is_none());
+    assert_eq!(employee["departments"][0]["dept_no"], "d004");
+    assert!(!employee["titles"].as_array().unwrap().is_empty());
+    assert!(employee.get("salaries").is_none());
+
+    // Salaries need the HR permission.
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/10004?include=salaries")).await;
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, HR_PERMISSION_REQUIRED_MSG, false).await;
+
+    set_role(HR_EMAIL, "hr").await;
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/10004?include=salaries")).await;
+
+    set_role(HR_EMAIL, "employee").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let employee = response.json::<DataResponse<serde_json::Value>>().await.unwrap().data;
+    assert_eq!(employee["first_name"], "Chirstian");
+    assert!(!employee["salaries"].as_array().unwrap().is_empty());
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&fields=emp_no,salary``
+/// * Route: ``http://localhost:5000/data/employees/10004?include=manager``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employees_json3_fields_invalid() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&fields=emp_no,salary")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, &format!("{} salary", FIELD_UNKNOWN_MSG), false).await;
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/10004?fields=emp_no,emp_no")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, &format!("{} emp_no", FIELD_DUPLICATE_MSG), false).await;
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/10004?include=manager")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, &format!("{} manager", INCLUDE_UNKNOWN_MSG), false).await;
+}
+
+/// Sends an authenticated ``GET`` request with a conditional header.
+async fn get_conditional(
+    client: &reqwest::Client,
+    test_app: &common::TestApp,
+    url: String,
+    name: header::HeaderName,
+    value: &str
+) -> reqwest::Response {
+    client
+        .get(url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .header(name, value)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+/// * Route: ``http://localhost:5000/data/employees?last_name=%25chi&first_name=%25ak&limit=2``
+/// * Route: ``http://localhost:5000/ui/employees?last_name=%25chi&first_name=%25ak&limit=2``
+/// * Method: ``GET``
+/// * Header: ``If-None-Match``, ``If-Modified-Since``
+#[actix_web::test]
+async fn get_employees_conditional() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let url = make_data_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2");
+
+    let response = get_accept(&client, test_app, url.clone(), "application/json").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = header_value(&response, header::ETAG);
+    assert!(etag.starts_with('"') && etag.ends_with('"'), "ETag should be strong.");
+    // A collection, by ETag only.
+    assert!(response.headers().get(header::LAST_MODIFIED).is_none());
+
+    let response = get_conditional(&client, test_app, url.clone(), header::IF_NONE_MATCH, &etag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(header_value(&response, header::ETAG), etag);
+    assert_eq!(header_value(&response, header::VARY), "accept");
+    assert!(response.text().await.unwrap().is_empty());
+
+    let response = get_conditional(&client, test_app, url.clone(), header::IF_MODIFIED_SINCE,
+        "Fri, 01 Jan 2100 00:00:00 GMT").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get_conditional(&client, test_app, url.clone(), header::IF_NONE_MATCH, "\"stale\"").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Another representation, another ETag.
+    let response = get_accept(&client, test_app, url, "text/csv").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(header_value(&response, header::ETAG), etag);
+
+    // HTML as JSON.
+    let url = make_ui_url(&test_app.app_url, "/employees?last_name=%25chi&first_name=%25ak&limit=2");
+    let response = get_accept(&client, test_app, url.clone(), "text/html").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = header_value(&response, header::ETAG);
+
+    let response = get_conditional(&client, test_app, url, header::IF_NONE_MATCH, &etag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10004``
+/// * Method: ``GET``
+/// * Header: ``If-None-Match``
+#[actix_web::test]
+async fn get_employee_json_conditional() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let url = make_data_url(&test_app.app_url, "/employees/10004");
+
+    let response = get_accept(&client, test_app, url.clone(), "application/json").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = header_value(&response, header::ETAG);
+
+    let response = get_conditional(&client, test_app, url.clone(), header::IF_NONE_MATCH, &etag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    // Other fields, another ETag.
+    let response = get_conditional(&client, test_app, format!("{}?fields=emp_no", url), header::IF_NONE_MATCH, &etag).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(header_value(&response, header::ETAG), etag);
+}
+
+/// * Route: ``http://localhost:5000/data/employees/new``, then 
+/// ``http://localhost:5000/data/employees/600004``
+/// * Method: ``POST``, ``PATCH``, ``DELETE``
+/// * Header: ``If-Match``, missing or stale.
+#[actix_web::test]
+async fn patch_employee_json_if_match() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let hr_token = hr_access_token(test_app).await;
+    let emp_no = 600004;
+    let employee_url = make_data_url(&test_app.app_url, &format!("/employees/{}", emp_no));
+
+    delete_employee_if_exists(&client, test_app, emp_no).await;
+
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees/new"))
+        .header(header::AUTHORIZATION, &hr_token)
+        .json(&new_employee_json(emp_no))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let etag = header_value(&response, header::ETAG);
+
+    let patch = |if_match: Option<&str>, last_name: &str| {
+        let mut request = client
+            .patch(&employee_url)
+            .header(header::AUTHORIZATION, &hr_token)
+            .json(&serde_json::json!({"last_name": last_name}));
+        if let Some(if_match) = if_match {
+            request = request.header(header::IF_MATCH, if_match);
+        }
+        request.send()
+    };
+
+    // No If-Match.
+    let response = patch(None, "Koblick").await.expect("Failed to execute request.");
+    common::assert_json_failure(response, StatusCode::PRECONDITION_REQUIRED, EMPLOYEE_IF_MATCH_REQUIRED_MSG, false).await;
+
+    // The first of two users editing the employee.
+    let response = patch(Some(&etag), "Koblick").await.expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::OK);
+    let new_etag = header_value(&response, header::ETAG);
+    assert_ne!(new_etag, etag);
+
+    // The second, whose copy is stale: the current employee is returned.
+    let response = patch(Some(&etag), "Facello").await.expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(header_value(&response, header::ETAG), new_etag);
+
+    let res = response.json::<DataResponse<Employee>>().await.unwrap();
+    assert_eq!(res.api_status.get_message(), Some(String::from(EMPLOYEE_MODIFIED_MSG)));
+    assert_eq!(res.data.last_name, "Koblick");
+
+    // Which the GET of the employee also has.
+    let response = get_accept(&client, test_app, employee_url.clone(), "application/json").await;
+    assert_eq!(header_value(&response, header::ETAG), new_etag);
+
+    delete_employee_if_exists(&client, test_app, emp_no).await;
+}
+
+const ADMIN_EMAIL: &str = "georgi.facello.10001@gmail.com";
+
+/// * Route: ``http://localhost:5000/data/employees/600005``, then
+/// ``http://localhost:5000/data/employees/deleted`` and
+/// ``http://localhost:5000/data/employees/600005/restore``
+/// * Method: ``POST``, ``DELETE``, ``GET``
+#[actix_web::test]
+async fn delete_restore_employee_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let hr_token = hr_access_token(test_app).await;
+    let emp_no = 600005;
+    let employee_url = make_data_url(&test_app.app_url, &format!("/employees/{}", emp_no));
+    let restore_url = make_data_url(&test_app.app_url, &format!("/employees/{}/restore", emp_no));
+
+    delete_employee_if_exists(&client, test_app, emp_no).await;
+
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees/new"))
+        .header(header::AUTHORIZATION, &hr_token)
+        .json(&new_employee_json(emp_no))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = client
+        .delete(&employee_url)
+        .header(header::AUTHORIZATION, &hr_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    common::assert_json_failure(response, StatusCode::OK, EMPLOYEE_DELETED_MSG, false).await;
+
+    // Deleted employees are not found.
+    let response = get_accept(&client, test_app, employee_url.clone(), "application/json").await;
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+
+    // Nor can they use their account.
+    let response = get_as(&client, test_app, &format!("siamak.bernardeschi.{}@gmail.com", emp_no),
+        &make_data_url(&test_app.app_url, "/employees/10004")).await;
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, ACCOUNT_DISABLED_MSG, false).await;
+
+    let post_as = |email: &str, url: &str| client
+        .post(url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token_for(email, JWT_SECS_VALID_FOR))
+        .send();
+
+    // Admins only.
+    let response = get_as(&client, test_app, ADMIN_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/deleted")).await;
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, ADMIN_PERMISSION_REQUIRED_MSG, false).await;
+
+    let response = post_as(ADMIN_EMAIL, &restore_url).await.expect("Failed to execute request.");
+    common::assert_json_failure(response, StatusCode::FORBIDDEN, ADMIN_PERMISSION_REQUIRED_MSG, false).await;
+
+    set_role(ADMIN_EMAIL, "admin").await;
+
+    let response = get_as(&client, test_app, ADMIN_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/deleted?limit=100")).await;
+    let status = response.status();
+    let res = response.json::<DataResponse<Vec<DeletedEmployee>>>().await;
+
+    let restored = post_as(ADMIN_EMAIL, &restore_url).await.expect("Failed to execute request.");
+
+    // Not deleted any more.
+    let response = post_as(ADMIN_EMAIL, &restore_url).await.expect("Failed to execute request.");
+
+    set_role(ADMIN_EMAIL, "employee").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let deleted = res.unwrap().data;
+    let employee = deleted.iter().find(|employee| employee.emp_no == emp_no).unwrap();
+    assert_eq!(employee.deleted_by.as_deref(), Some(HR_WRITER_EMAIL));
+
+    common::assert_json_failure(restored, StatusCode::OK, EMPLOYEE_RESTORED_MSG, false).await;
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, DELETED_EMPLOYEE_NOT_FOUND_MSG, false).await;
+
+    let response = get_accept(&client, test_app, employee_url.clone(), "application/json").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    delete_employee_if_exists(&client, test_app, emp_no).await;
+}
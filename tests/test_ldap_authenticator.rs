@@ -0,0 +1,74 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for the ``ldap`` authentication backend,
+//! [`learn_actix_web::authenticators::ldap::LdapAuthenticator`].
+//!
+//! These tests need a local LDAP server at ``LDAP_URL`` in the ``.env`` file, which has
+//! an entry for ``chirstian.koblick.10004@gmail.com`` with password ``password`` at the
+//! DN given by ``LDAP_BIND_DN_TEMPLATE``. For example, with OpenLDAP:
+//!
+//! ```text
+//! dn: mail=chirstian.koblick.10004@gmail.com,ou=people,dc=example,dc=com
+//! objectClass: inetOrgPerson
+//! cn: Chirstian Koblick
+//! sn: Koblick
+//! mail: chirstian.koblick.10004@gmail.com
+//! userPassword: password
+//! ```
+//!
+//! They are ignored by default. To run test for this module only:
+//!
+//!     * cargo test --test test_ldap_authenticator -- --ignored
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test ldap_bind_success -- --exact --ignored
+//!     * cargo test ldap_bind_wrong_password -- --exact --ignored
+//!     * cargo test ldap_bind_unknown_email -- --exact --ignored
+//!
+use dotenv::dotenv;
+
+use learn_actix_web::config::Config;
+use learn_actix_web::models::EmployeeLogin;
+use learn_actix_web::authenticators::{Authenticator, AuthOutcome, ldap::LdapAuthenticator};
+
+fn ldap_authenticator() -> LdapAuthenticator {
+    dotenv().ok();
+    let config = Config::init();
+
+    LdapAuthenticator::new(&config.ldap_url, &config.ldap_bind_dn_template)
+}
+
+fn employee_login(email: &str, password: &str) -> EmployeeLogin {
+    EmployeeLogin {
+        email: String::from(email),
+        password: String::from(password),
+        remember_me: false,
+        next: None,
+    }
+}
+
+#[actix_web::test]
+#[ignore = "needs a local LDAP server"]
+async fn ldap_bind_success() {
+    let login = employee_login("chirstian.koblick.10004@gmail.com", "password");
+
+    assert_eq!(ldap_authenticator().authenticate(&login).await,
+        AuthOutcome::Authenticated(String::from("chirstian.koblick.10004@gmail.com")));
+}
+
+#[actix_web::test]
+#[ignore = "needs a local LDAP server"]
+async fn ldap_bind_wrong_password() {
+    let login = employee_login("chirstian.koblick.10004@gmail.com", "passworKKd");
+
+    assert_eq!(ldap_authenticator().authenticate(&login).await, AuthOutcome::Rejected);
+}
+
+#[actix_web::test]
+#[ignore = "needs a local LDAP server"]
+async fn ldap_bind_unknown_email() {
+    let login = employee_login("suzette.petXXtey.10024@gmail.com", "password");
+
+    assert_eq!(ldap_authenticator().authenticate(&login).await, AuthOutcome::Rejected);
+}
@@ -0,0 +1,204 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in org_chart_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/org-chart``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/departments/{dept_no}/org-chart``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}/manager``
+//! * Method: ``GET``
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_org_chart_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_company_org_chart_json -- --exact
+//!     * cargo test get_department_org_chart_json -- --exact
+//!     * cargo test get_department_org_chart_dot -- --exact
+//!     * cargo test get_department_org_chart_svg -- --exact
+//!     * cargo test get_department_org_chart_invalid_format -- --exact
+//!     * cargo test get_department_org_chart_not_found -- --exact
+//!     * cargo test get_employee_manager_json -- --exact
+//!     * cargo test get_employee_manager_json_not_found -- --exact
+//!
+use actix_web::http::{StatusCode, header};
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::org_chart::{OrgNode, OrgNodeKind, ReportingLine};
+
+mod common;
+use common::{spawn_app, make_data_url, header_value, get_authorised};
+
+use learn_actix_web::helper::messages::{
+    DEPARTMENT_NOT_FOUND_MSG,
+    EMPLOYEE_NOT_FOUND_MSG,
+    ORG_CHART_FORMAT_INVALID_MSG
+};
+
+/// * Route: ``http://localhost:5000/data/org-chart``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_company_org_chart_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/org-chart")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let company = response.json::<DataResponse<OrgNode>>().await.unwrap().data;
+    assert_eq!(company.kind, OrgNodeKind::Company);
+    assert!(company.employees.unwrap() > 0);
+
+    assert_eq!(company.children.len(), 9);
+    assert_eq!(company.children[0].id, "d001");
+    assert_eq!(company.children[8].id, "d009");
+
+    for department in &company.children {
+        assert_eq!(department.kind, OrgNodeKind::Department);
+        assert_eq!(department.children.len(), 1);
+        assert_eq!(department.children[0].kind, OrgNodeKind::Manager);
+        assert!(department.children[0].children.is_empty());
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005/org-chart?limit=3``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_org_chart_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/org-chart?limit=3")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let department = response.json::<DataResponse<OrgNode>>().await.unwrap().data;
+    assert_eq!(department.kind, OrgNodeKind::Department);
+    assert_eq!(department.id, "d005");
+    assert_eq!(department.name, "Development");
+
+    assert_eq!(department.children.len(), 1);
+    let manager = &department.children[0];
+    assert_eq!(manager.kind, OrgNodeKind::Manager);
+
+    assert_eq!(manager.children.len(), 3);
+    assert!(manager.children.iter().all(|employee| employee.kind == OrgNodeKind::Employee
+        && employee.id != manager.id));
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005/org-chart?format=dot``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_org_chart_dot() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/org-chart?format=dot")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_value(&response, header::CONTENT_TYPE), "text/vnd.graphviz; charset=utf-8");
+    assert_eq!(header_value(&response, header::CONTENT_DISPOSITION),
+        "attachment; filename=\"org-chart-d005.dot\"");
+
+    let dot = response.text().await.unwrap();
+    assert!(dot.starts_with("digraph org_chart {"));
+    assert!(dot.contains("\"department:d005\" [label=\"Development ("));
+    assert!(dot.contains("\"department:d005\" -> \"manager:"));
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d005/org-chart?format=svg&limit=2``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_org_chart_svg() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/org-chart?format=svg&limit=2")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(header_value(&response, header::CONTENT_TYPE), "image/svg+xml; charset=utf-8");
+    assert_eq!(header_value(&response, header::CONTENT_DISPOSITION),
+        "inline; filename=\"org-chart-d005.svg\"");
+
+    let svg = response.text().await.unwrap();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    // Department, manager and 2 employees.
+    assert_eq!(svg.matches("<rect ").count(), 4);
+}
+
+/// * Route: ``http://localhost:5000/data/org-chart?format=png``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_org_chart_invalid_format() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d005/org-chart?format=png")).await;
+
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, ORG_CHART_FORMAT_INVALID_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/departments/d999/org-chart``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_department_org_chart_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/departments/d999/org-chart")).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, DEPARTMENT_NOT_FOUND_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10010/manager``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_manager_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/10010/manager")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 10010 was in d004, and is now in d006 only.
+    let managers = response.json::<DataResponse<Vec<ReportingLine>>>().await.unwrap().data;
+    assert_eq!(managers.len(), 1);
+    assert_eq!(managers[0].dept_no, "d006");
+    assert_eq!(managers[0].manager_title.as_deref(), Some("Manager"));
+}
+
+/// * Route: ``http://localhost:5000/data/employees/1/manager``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_manager_json_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/1/manager")).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+}
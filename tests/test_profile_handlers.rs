@@ -0,0 +1,127 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in profile_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}/profile``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/ui/employees/{emp_no}``
+//! * Method: ``GET``
+//!
+//! Assumes ``HR_ROLES`` in the ``.env`` file includes ``hr``. The HR user's role is set
+//! to ``hr`` by the test, and restored to ``employee`` after.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_profile_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_employee_profile_json -- --exact
+//!     * cargo test get_employee_profile_json_hr -- --exact
+//!     * cargo test get_employee_profile_json_not_found -- --exact
+//!     * cargo test get_employee_profile_html -- --exact
+//!
+use actix_web::http::StatusCode;
+use time::macros::date;
+
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::profile::EmployeeProfile;
+
+mod common;
+use common::{spawn_app, make_data_url, make_ui_url, HR_EMAIL, set_role, get_as};
+
+use learn_actix_web::helper::messages::EMPLOYEE_NOT_FOUND_MSG;
+
+const EMPLOYEE_EMAIL: &str = "chirstian.koblick.10004@gmail.com";
+
+/// * Route: ``http://localhost:5000/data/employees/10010/profile``
+/// * Method: ``GET``
+///
+/// The default user, chirstian.koblick.10004@gmail.com, is an ``employee``: no salary.
+#[actix_web::test]
+async fn get_employee_profile_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let url = make_data_url(&test_app.app_url, "/employees/10010/profile");
+    let response = get_as(&client, test_app, EMPLOYEE_EMAIL, &url).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = response.json::<serde_json::Value>().await.unwrap();
+    assert!(json["data"]["salary"].is_null());
+
+    let profile: EmployeeProfile = serde_json::from_value(json["data"].clone()).unwrap();
+    assert_eq!(profile.employee.emp_no, 10010);
+    assert!(profile.employee.title.is_some());
+
+    // 10010 was in d004, and is now in d006.
+    let department = profile.department.unwrap();
+    assert_eq!(department.dept_no, "d006");
+    assert_eq!(department.dept_name, "Quality Management");
+
+    let manager = profile.manager.unwrap();
+    assert_ne!(manager.emp_no, 10010);
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10001/profile``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_profile_json_hr() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    set_role(HR_EMAIL, "hr").await;
+
+    let url = make_data_url(&test_app.app_url, "/employees/10001/profile");
+    let response = get_as(&client, test_app, HR_EMAIL, &url).await;
+
+    set_role(HR_EMAIL, "employee").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let profile = response.json::<DataResponse<EmployeeProfile>>().await.unwrap().data;
+    assert_eq!(profile.employee.emp_no, 10001);
+    assert_eq!(profile.employee.hire_date, date!(1986 - 06 - 26));
+    assert!(profile.salary.unwrap() > rust_decimal::Decimal::ZERO);
+    assert!(profile.department.is_some());
+}
+
+/// * Route: ``http://localhost:5000/data/employees/1/profile``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_profile_json_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let url = make_data_url(&test_app.app_url, "/employees/1/profile");
+    let response = get_as(&client, test_app, EMPLOYEE_EMAIL, &url).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+}
+
+/// * Route: ``http://localhost:5000/ui/employees/10010``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_profile_html() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let url = make_ui_url(&test_app.app_url, "/employees/10010");
+    let response = get_as(&client, test_app, EMPLOYEE_EMAIL, &url).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let html = response.text().await.unwrap();
+    assert!(html.contains("<title>Employee Profile</title>"));
+    assert!(html.contains("<td>10010</td>"));
+    assert!(html.contains("\">Quality Management</a>, since "));
+    assert!(!html.contains("<th>Salary</th>"));
+}
@@ -0,0 +1,128 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in salary_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}/salaries``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/salaries/stats/departments?percentiles=50,90``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/salaries/stats/titles``
+//! * Method: ``GET``
+//!
+//! Assumes ``HR_ROLES`` in the ``.env`` file includes ``hr``. The HR user's role is set
+//! to ``hr`` by the test, and restored to ``employee`` after.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_salary_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_salaries_json_forbidden -- --exact
+//!     * cargo test get_salaries_json_hr -- --exact
+//!
+use actix_web::http::StatusCode;
+use rust_decimal::Decimal;
+
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::salary::{Salary, SalaryStats};
+
+mod common;
+use common::{spawn_app, make_data_url, HR_EMAIL, set_role, get_as};
+
+use learn_actix_web::helper::messages::{
+    HR_PERMISSION_REQUIRED_MSG,
+    EMPLOYEE_NOT_FOUND_MSG,
+    SALARY_PERCENTILE_INVALID_MSG
+};
+
+/// * Route: ``http://localhost:5000/data/employees/10001/salaries``, and statistics routes.
+/// * Method: ``GET``
+///
+/// The default user, chirstian.koblick.10004@gmail.com, is an ``employee``.
+#[actix_web::test]
+async fn get_salaries_json_forbidden() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    for path in ["/employees/10001/salaries", "/employees/1/salaries",
+        "/salaries/stats/departments", "/salaries/stats/titles?percentiles=abc"] {
+        let response = get_as(&client, test_app, "chirstian.koblick.10004@gmail.com",
+            &make_data_url(&test_app.app_url, path)).await;
+
+        common::assert_json_failure(response, StatusCode::FORBIDDEN, HR_PERMISSION_REQUIRED_MSG, false).await;
+    }
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10001/salaries``
+/// * Route: ``http://localhost:5000/data/salaries/stats/departments?percentiles=0,50,100``
+/// * Route: ``http://localhost:5000/data/salaries/stats/titles``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_salaries_json_hr() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    set_role(HR_EMAIL, "hr").await;
+
+    // Salary history.
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/10001/salaries")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let json = response.json::<serde_json::Value>().await.unwrap();
+    assert_eq!(json["data"][0]["salary"], "60117.00", "Should be an exact decimal string.");
+    assert_eq!(json["data"][0]["from_date"], "26/06/1986");
+
+    let salaries: DataResponse<Vec<Salary>> = serde_json::from_value(json).unwrap();
+    assert!(salaries.data.len() > 1);
+    assert!(salaries.data.windows(2).all(|pair| pair[0].from_date < pair[1].from_date));
+    assert_eq!(salaries.data.iter().filter(|salary| salary.current).count(), 1);
+    assert!(salaries.data.last().unwrap().current);
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/employees/1/salaries")).await;
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+
+    // Per department: the 0 and 100 percentiles are the minimum and maximum.
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/salaries/stats/departments?percentiles=0,50,100")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stats = response.json::<DataResponse<Vec<SalaryStats>>>().await.unwrap().data;
+    assert_eq!(stats.len(), 9);
+    assert_eq!(stats[0].key, "d001");
+
+    for group in &stats {
+        assert!(group.employees > 0);
+        assert!(group.min <= group.mean && group.mean <= group.max);
+        assert_eq!(group.percentiles.len(), 3);
+        assert_eq!(group.percentiles[0].percentile, Decimal::from(0));
+        assert_eq!(group.percentiles[0].salary, group.min);
+        assert!(group.min <= group.percentiles[1].salary && group.percentiles[1].salary <= group.max);
+        assert_eq!(group.percentiles[2].salary, group.max);
+    }
+
+    // Per title, default percentiles.
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/salaries/stats/titles")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stats = response.json::<DataResponse<Vec<SalaryStats>>>().await.unwrap().data;
+    let engineers = stats.iter().find(|group| group.key == "Engineer").unwrap();
+    assert_eq!(engineers.name, "Engineer");
+    assert_eq!(engineers.percentiles.len(), 4);
+    assert!(engineers.percentiles.windows(2).all(|pair| pair[0].salary <= pair[1].salary));
+
+    let response = get_as(&client, test_app, HR_EMAIL,
+        &make_data_url(&test_app.app_url, "/salaries/stats/titles?percentiles=50,101")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, SALARY_PERCENTILE_INVALID_MSG, true).await;
+
+    set_role(HR_EMAIL, "employee").await;
+}
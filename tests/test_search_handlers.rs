@@ -0,0 +1,172 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in search_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/employees/search``
+//! * Method: ``GET``
+//!
+//! The search index in ``SEARCH_INDEX_DIR`` is rebuilt first if it is empty.
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_search_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_search_employees -- --exact
+//!     * cargo test get_search_employees_fuzzy -- --exact
+//!     * cargo test get_search_employees_sync -- --exact
+//!     * cargo test get_search_employees_invalid -- --exact
+//!
+use std::path::Path;
+
+use actix_web::http::{StatusCode, header};
+use tokio::sync::OnceCell;
+
+use learn_actix_web::config::Config;
+use learn_actix_web::database::get_mysql_pool;
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::search_index::EmployeeIndex;
+use learn_actix_web::search_handlers::ScoredEmployee;
+
+mod common;
+use common::{spawn_app, JWT_SECS_VALID_FOR, make_data_url, get_authorised};
+
+use learn_actix_web::helper::messages::SEARCH_TEXT_REQUIRED_MSG;
+
+/// Rebuilds the search index once, if it is empty.
+static SEARCH_INDEX_READY: OnceCell<()> = OnceCell::const_new();
+
+async fn ensure_search_index() {
+    SEARCH_INDEX_READY.get_or_init(|| async {
+        let config = Config::init();
+        let search_index = EmployeeIndex::open(Path::new(&config.search_index_dir)).unwrap();
+
+        if search_index.num_docs() == 0 {
+            let pool = get_mysql_pool(config.max_connections, &config.database_url).await;
+            search_index.rebuild(&pool).await.unwrap();
+        }
+    }).await;
+}
+
+async fn search(client: &reqwest::Client, test_app: &common::TestApp, path: &str) -> Vec<ScoredEmployee> {
+    let response = get_authorised(client, test_app,
+        &make_data_url(&test_app.app_url, path)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json::<DataResponse<Vec<ScoredEmployee>>>().await.unwrap().data
+}
+
+/// * Route: ``http://localhost:5000/data/employees/search?q=Koblick&limit=10``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_search_employees() {
+    ensure_search_index().await;
+
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let results = search(&client, test_app, "/employees/search?q=Koblick&limit=10").await;
+
+    assert_eq!(results.len(), 10);
+    assert!(results.iter().all(|result| result.employee.last_name == "Koblick"));
+    assert!(results.windows(2).all(|pair| pair[0].score >= pair[1].score));
+}
+
+/// * Route: ``http://localhost:5000/data/employees/search?q=chirstain+koblik+10004``
+/// * Route: ``http://localhost:5000/data/employees/search?q=Georgi+Facelo&limit=500``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_search_employees_fuzzy() {
+    ensure_search_index().await;
+
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    // Typos in both names, the email has the emp_no.
+    let results = search(&client, test_app, "/employees/search?q=chirstain+koblik+10004").await;
+
+    assert_eq!(results[0].employee.emp_no, 10004);
+    assert_eq!(results[0].employee.first_name, "Chirstian");
+
+    // Exact matches rank above typos.
+    let results = search(&client, test_app, "/employees/search?q=Georgi+Facelo&limit=500").await;
+
+    assert!(results.iter().any(|result| result.employee.emp_no == 10001));
+    assert_eq!(results[0].employee.first_name, "Georgi");
+}
+
+/// * Route: ``http://localhost:5000/data/employees/new``, then
+/// ``http://localhost:5000/data/employees/search?q=Quixotezyx``
+/// * Method: ``POST``, ``GET``, ``DELETE``
+#[actix_web::test]
+async fn get_search_employees_sync() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let emp_no = 600201;
+    let employee_url = make_data_url(&test_app.app_url, &format!("/employees/{}", emp_no));
+
+    client.delete(&employee_url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send().await.expect("Failed to execute request.");
+
+    let response = client
+        .post(make_data_url(&test_app.app_url, "/employees/new"))
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .json(&serde_json::json!({
+            "emp_no": emp_no,
+            "email": format!("siamak.quixotezyx.{}@gmail.com", emp_no),
+            "birth_date": "14/12/1955",
+            "first_name": "Siamak",
+            "last_name": "Quixotezyx",
+            "gender": "M",
+            "hire_date": "26/04/1985"
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // Found as soon as created, also with a typo.
+    let results = search(&client, test_app, "/employees/search?q=Quixotezyx").await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].employee.emp_no, emp_no);
+
+    let results = search(&client, test_app, "/employees/search?q=Quixotzyx").await;
+    assert_eq!(results.len(), 1);
+
+    let response = client.delete(&employee_url)
+        .header(header::AUTHORIZATION, &test_app.mock_access_token(JWT_SECS_VALID_FOR))
+        .send().await.expect("Failed to execute request.");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Gone as soon as deleted.
+    let results = search(&client, test_app, "/employees/search?q=Quixotezyx").await;
+    assert!(results.is_empty());
+}
+
+/// * Route: ``http://localhost:5000/data/employees/search``
+/// * Route: ``http://localhost:5000/data/employees/search?q=+.,+``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_search_employees_invalid() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/search")).await;
+    common::assert_json_failure(response, StatusCode::BAD_REQUEST, SEARCH_TEXT_REQUIRED_MSG, false).await;
+
+    // No words: nothing is found.
+    let results = search(&client, test_app, "/employees/search?q=+.,+").await;
+    assert!(results.is_empty());
+}
@@ -0,0 +1,133 @@
+/* Date Created: 18/10/2026. */
+
+//! Integration test cases for endpoint handler methods defined in title_handlers.rs.
+//!
+//! Test the following routes:
+//!
+//! * Route: ``http://localhost:5000/data/titles``
+//! * Method: ``GET``
+//!
+//! * Route: ``http://localhost:5000/data/employees/{emp_no}/titles``
+//! * Method: ``GET``
+//!
+//! To run test for this module only:
+//!
+//!     * cargo test --test test_title_handlers
+//!
+//! To run a specific test method:
+//!
+//!     * cargo test get_titles_json -- --exact
+//!     * cargo test get_titles_json_deleted_employee -- --exact
+//!     * cargo test get_employee_titles_json -- --exact
+//!     * cargo test get_employee_titles_json_not_found -- --exact
+//!
+use actix_web::http::StatusCode;
+use time::macros::date;
+use learn_actix_web::models::DataResponse;
+use learn_actix_web::models::title::{EmployeeTitle, TitleCount};
+
+mod common;
+use common::{spawn_app, make_data_url, HR_EMAIL, test_pool, get_authorised};
+
+use learn_actix_web::helper::messages::EMPLOYEE_NOT_FOUND_MSG;
+
+/// * Route: ``http://localhost:5000/data/titles``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_titles_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/titles")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let titles = response.json::<DataResponse<Vec<TitleCount>>>().await.unwrap().data;
+    assert!(titles.windows(2).all(|pair| pair[0].title < pair[1].title));
+
+    let senior_engineers = titles.iter().find(|title| title.title == "Senior Engineer").unwrap();
+    assert!(senior_engineers.current_employees > 0);
+}
+
+/// The current number of employees of a title, while one of them is deleted.
+/// 
+/// * Route: ``http://localhost:5000/data/titles``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_titles_json_deleted_employee() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+    let pool = test_pool().await;
+    let emp_no = 10009;
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, &format!("/employees/{}/titles", emp_no))).await;
+    let titles = response.json::<DataResponse<Vec<EmployeeTitle>>>().await.unwrap().data;
+    let title = titles.into_iter().find(|title| title.current).unwrap().title;
+
+    let current_employees = |titles: Vec<TitleCount>| titles.into_iter()
+        .find(|count| count.title == title).unwrap().current_employees;
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/titles")).await;
+    let before = current_employees(response.json::<DataResponse<Vec<TitleCount>>>().await.unwrap().data);
+
+    sqlx::query("UPDATE employees SET deleted_at = UTC_TIMESTAMP(), deleted_by = ? WHERE emp_no = ?")
+        .bind(HR_EMAIL).bind(emp_no)
+        .execute(&pool).await.unwrap();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/titles")).await;
+    let status = response.status();
+    let res = response.json::<DataResponse<Vec<TitleCount>>>().await;
+
+    sqlx::query("UPDATE employees SET deleted_at = NULL, deleted_by = NULL WHERE emp_no = ?")
+        .bind(emp_no)
+        .execute(&pool).await.unwrap();
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(current_employees(res.unwrap().data), before - 1);
+}
+
+/// * Route: ``http://localhost:5000/data/employees/10004/titles``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_titles_json() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/10004/titles")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let titles = response.json::<DataResponse<Vec<EmployeeTitle>>>().await.unwrap().data;
+    assert_eq!(titles.len(), 2);
+
+    assert_eq!(titles[0].title, "Engineer");
+    assert_eq!(titles[0].from_date, date!(1986 - 12 - 01));
+    assert_eq!(titles[0].to_date, date!(1995 - 12 - 01));
+    assert!(!titles[0].current);
+
+    assert_eq!(titles[1].title, "Senior Engineer");
+    assert_eq!(titles[1].from_date, date!(1995 - 12 - 01));
+    assert!(titles[1].current);
+}
+
+/// * Route: ``http://localhost:5000/data/employees/1/titles``
+/// * Method: ``GET``
+#[actix_web::test]
+async fn get_employee_titles_json_not_found() {
+    let test_app = &spawn_app().await;
+
+    let client = common::reqwest_client();
+
+    let response = get_authorised(&client, test_app,
+        &make_data_url(&test_app.app_url, "/employees/1/titles")).await;
+
+    common::assert_json_failure(response, StatusCode::NOT_FOUND, EMPLOYEE_NOT_FOUND_MSG, false).await;
+}